@@ -0,0 +1,463 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Minimal Delta Lake transaction log writer. Every uploaded parquet file is committed to
+//! `<bucket_root>/<table>/_delta_log/<version>.json` so that engines reading the table see
+//! a consistent snapshot without having to merge the raw files themselves.
+//! Commits are made atomic with a GCS `ifGenerationMatch=0` precondition: if another writer
+//! already created the commit for a version, or the upload failed, we refresh the latest
+//! version and try again.
+//! A failed upload may still have created our commit, so the commit at that version is checked
+//! for our file before retrying, otherwise the file would be added twice.
+
+use crate::utils::util::hash_str;
+use anyhow::{Context, Result};
+use google_cloud_storage::{
+    client::Client as GCSClient,
+    http::objects::{
+        download::Range,
+        get::GetObjectRequest,
+        list::ListObjectsRequest,
+        upload::{Media, UploadObjectRequest, UploadType},
+    },
+};
+use hyper::Body;
+use parquet::{
+    basic::{ConvertedType, LogicalType, Repetition, TimeUnit, Type as PhysicalType},
+    schema::types::Type,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use tokio::time::{sleep, Duration};
+use tracing::{info, warn};
+
+const DELTA_LOG_DIR: &str = "_delta_log";
+const MAX_COMMIT_RETRIES: usize = 5;
+const COMMIT_RETRY_DELAY_MS: u64 = 500;
+const MIN_READER_VERSION: i64 = 1;
+const MIN_WRITER_VERSION: i64 = 2;
+// Protocol versions supporting table features, required by timestamp_ntz columns
+const TABLE_FEATURES_READER_VERSION: i64 = 3;
+const TABLE_FEATURES_WRITER_VERSION: i64 = 7;
+const TIMESTAMP_NTZ_FEATURE: &str = "timestampNtz";
+const ENGINE_INFO: &str = "aptos-indexer-processor";
+
+/// Output format of the parquet processors.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetTableFormat {
+    /// Bare parquet files, merged into tables downstream (e.g. with `parquet-bq-scripts`)
+    #[default]
+    Parquet,
+    /// Parquet files committed to a Delta Lake table log next to the files
+    DeltaLake,
+}
+
+pub struct DeltaLogWriter {
+    bucket_name: String,
+    table_root: PathBuf,
+    // Next commit version, loaded from the bucket before the first commit
+    next_version: Option<i64>,
+    // Every process writes the table metadata in its first commit. The schema can only change
+    // with a new binary, so this is what gives us schema evolution.
+    metadata_committed: bool,
+}
+
+impl DeltaLogWriter {
+    pub fn new(bucket_name: String, bucket_root: &Path, table_name: &str) -> Self {
+        Self {
+            bucket_name,
+            table_root: bucket_root.join(table_name),
+            next_version: None,
+            metadata_committed: false,
+        }
+    }
+
+    /// Commits one uploaded parquet file to the table log.
+    pub async fn commit(
+        &mut self,
+        client: &GCSClient,
+        schema: &Type,
+        file_path: &Path,
+        file_size: i64,
+        num_records: i64,
+    ) -> Result<i64> {
+        let relative_path = file_path
+            .strip_prefix(&self.table_root)
+            .with_context(|| {
+                format!(
+                    "File {:?} is not under the table root {:?}",
+                    file_path, self.table_root
+                )
+            })?
+            .to_str()
+            .context("File path is not valid utf-8")?
+            .to_string();
+
+        let mut retries = 0;
+        loop {
+            match self
+                .try_commit(client, schema, &relative_path, file_size, num_records)
+                .await
+            {
+                Ok(version) => return Ok(version),
+                Err(e) if retries < MAX_COMMIT_RETRIES => {
+                    warn!(
+                        table_root = self.table_root.to_string_lossy().as_ref(),
+                        retries,
+                        error = ?e,
+                        "[Delta Log] Commit failed, retrying"
+                    );
+                    // Another writer may have won the race for the version, or the upload
+                    // failed. Either way retry under the next version of the log.
+                    self.next_version = None;
+                    retries += 1;
+                    sleep(Duration::from_millis(
+                        COMMIT_RETRY_DELAY_MS * retries as u64,
+                    ))
+                    .await;
+                },
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Writes the commit at the next version, failing if it already exists.
+    async fn try_commit(
+        &mut self,
+        client: &GCSClient,
+        schema: &Type,
+        relative_path: &str,
+        file_size: i64,
+        num_records: i64,
+    ) -> Result<i64> {
+        let version = match self.next_version {
+            Some(version) => version,
+            None => self.get_latest_version(client).await? + 1,
+        };
+        let commit = self.build_commit(schema, relative_path, file_size, num_records)?;
+        let upload_request = UploadObjectRequest {
+            bucket: self.bucket_name.clone(),
+            // Only create the commit if nobody else has written this version yet
+            if_generation_match: Some(0),
+            ..Default::default()
+        };
+        let upload_type = UploadType::Simple(Media::new(
+            self.commit_path(version).to_string_lossy().into_owned(),
+        ));
+        match client
+            .upload_object(&upload_request, Body::from(commit), &upload_type)
+            .await
+        {
+            Ok(_) => {
+                info!(
+                    table_root = self.table_root.to_string_lossy().as_ref(),
+                    version, "[Delta Log] Committed parquet file {}", relative_path
+                );
+            },
+            // The upload may have created the commit even though the response was lost
+            Err(e) => {
+                if !self
+                    .is_own_commit(client, version, relative_path)
+                    .await
+                    .unwrap_or(false)
+                {
+                    return Err(e).context(format!(
+                        "Failed to commit version {} to delta log {:?}",
+                        version, self.table_root
+                    ));
+                }
+                info!(
+                    table_root = self.table_root.to_string_lossy().as_ref(),
+                    version, "[Delta Log] Parquet file {} was already committed", relative_path
+                );
+            },
+        }
+        self.next_version = Some(version + 1);
+        self.metadata_committed = true;
+        Ok(version)
+    }
+
+    /// Returns the latest committed version, or -1 if the table doesn't exist yet.
+    async fn get_latest_version(&self, client: &GCSClient) -> Result<i64> {
+        let prefix = format!("{}/", self.table_root.join(DELTA_LOG_DIR).to_string_lossy());
+        let mut latest_version = -1;
+        let mut page_token = None;
+        loop {
+            let response = client
+                .list_objects(&ListObjectsRequest {
+                    bucket: self.bucket_name.clone(),
+                    prefix: Some(prefix.clone()),
+                    page_token: page_token.clone(),
+                    ..Default::default()
+                })
+                .await
+                .context("Failed to list delta log")?;
+            for object in response.items.unwrap_or_default() {
+                if let Some(version) = parse_commit_version(&object.name) {
+                    latest_version = latest_version.max(version);
+                }
+            }
+            page_token = response.next_page_token;
+            if page_token.is_none() {
+                return Ok(latest_version);
+            }
+        }
+    }
+
+    /// Whether the commit at the version adds our file, i.e. whether we wrote it.
+    async fn is_own_commit(
+        &self,
+        client: &GCSClient,
+        version: i64,
+        relative_path: &str,
+    ) -> Result<bool> {
+        let commit = client
+            .download_object(
+                &GetObjectRequest {
+                    bucket: self.bucket_name.clone(),
+                    object: self.commit_path(version).to_string_lossy().into_owned(),
+                    ..Default::default()
+                },
+                &Range::default(),
+            )
+            .await
+            .context("Failed to download delta log commit")?;
+        commit_adds_file(&commit, relative_path)
+    }
+
+    fn commit_path(&self, version: i64) -> PathBuf {
+        self.table_root
+            .join(DELTA_LOG_DIR)
+            .join(format!("{:020}.json", version))
+    }
+
+    /// Delta commits are newline delimited json, one action per line.
+    fn build_commit(
+        &self,
+        schema: &Type,
+        relative_path: &str,
+        file_size: i64,
+        num_records: i64,
+    ) -> Result<Vec<u8>> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut actions = vec![json!({
+            "commitInfo": {
+                "timestamp": now,
+                "operation": "WRITE",
+                "operationParameters": { "mode": "Append" },
+                "engineInfo": ENGINE_INFO,
+            }
+        })];
+        if !self.metadata_committed {
+            let delta_schema = delta_schema(schema)?;
+            actions.push(json!({ "protocol": delta_protocol(&delta_schema) }));
+            actions.push(json!({
+                "metaData": {
+                    "id": self.table_id(),
+                    "format": { "provider": "parquet", "options": {} },
+                    "schemaString": serde_json::to_string(&delta_schema)?,
+                    "partitionColumns": [],
+                    "configuration": {},
+                    "createdTime": now,
+                }
+            }));
+        }
+        actions.push(json!({
+            "add": {
+                "path": relative_path,
+                "partitionValues": {},
+                "size": file_size,
+                "modificationTime": now,
+                "dataChange": true,
+                "stats": serde_json::to_string(&json!({ "numRecords": num_records }))?,
+            }
+        }));
+
+        let mut commit = vec![];
+        for action in actions {
+            serde_json::to_writer(&mut commit, &action)?;
+            commit.push(b'\n');
+        }
+        Ok(commit)
+    }
+
+    /// The table id has to stay the same across commits, so derive it from the table root.
+    fn table_id(&self) -> String {
+        let hash = hash_str(&self.table_root.to_string_lossy());
+        format!(
+            "{}-{}-{}-{}-{}",
+            &hash[0..8],
+            &hash[8..12],
+            &hash[12..16],
+            &hash[16..20],
+            &hash[20..32]
+        )
+    }
+}
+
+fn parse_commit_version(object_name: &str) -> Option<i64> {
+    let file_name = Path::new(object_name).file_name()?.to_str()?;
+    file_name.strip_suffix(".json")?.parse::<i64>().ok()
+}
+
+/// Whether one of the `add` actions of the newline delimited commit is for the file.
+fn commit_adds_file(commit: &[u8], relative_path: &str) -> Result<bool> {
+    for line in commit.split(|byte| *byte == b'\n') {
+        if line.is_empty() {
+            continue;
+        }
+        let action: Value = serde_json::from_slice(line).context("Invalid delta log action")?;
+        if action["add"]["path"].as_str() == Some(relative_path) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// timestamp_ntz columns can only be read by engines supporting the timestampNtz table feature.
+fn delta_protocol(delta_schema: &Value) -> Value {
+    let has_timestamp_ntz = delta_schema["fields"]
+        .as_array()
+        .map(|fields| {
+            fields
+                .iter()
+                .any(|field| field["type"].as_str() == Some("timestamp_ntz"))
+        })
+        .unwrap_or(false);
+    if has_timestamp_ntz {
+        json!({
+            "minReaderVersion": TABLE_FEATURES_READER_VERSION,
+            "minWriterVersion": TABLE_FEATURES_WRITER_VERSION,
+            "readerFeatures": [TIMESTAMP_NTZ_FEATURE],
+            "writerFeatures": [TIMESTAMP_NTZ_FEATURE],
+        })
+    } else {
+        json!({
+            "minReaderVersion": MIN_READER_VERSION,
+            "minWriterVersion": MIN_WRITER_VERSION,
+        })
+    }
+}
+
+/// Converts the parquet schema of a table into a Delta Lake struct type.
+pub fn delta_schema(schema: &Type) -> Result<Value> {
+    let fields = schema
+        .get_fields()
+        .iter()
+        .map(|field| {
+            Ok(json!({
+                "name": field.name(),
+                "type": delta_type(field)?,
+                "nullable": field.get_basic_info().repetition() != Repetition::REQUIRED,
+                "metadata": {},
+            }))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(json!({ "type": "struct", "fields": fields }))
+}
+
+fn delta_type(field: &Type) -> Result<&'static str> {
+    anyhow::ensure!(
+        field.is_primitive(),
+        "Nested column {} isn't supported by the delta log writer",
+        field.name()
+    );
+    let basic_info = field.get_basic_info();
+    let delta_type = match (field.get_physical_type(), basic_info.logical_type()) {
+        (PhysicalType::BOOLEAN, _) => "boolean",
+        (PhysicalType::INT32, _) => "integer",
+        (
+            PhysicalType::INT64,
+            Some(LogicalType::Timestamp {
+                is_adjusted_to_u_t_c,
+                unit: TimeUnit::MILLIS(_) | TimeUnit::MICROS(_),
+            }),
+        ) => {
+            if is_adjusted_to_u_t_c {
+                "timestamp"
+            } else {
+                "timestamp_ntz"
+            }
+        },
+        // parquet_derive only sets the converted type for chrono timestamps, which are UTC
+        (PhysicalType::INT64, _)
+            if matches!(
+                basic_info.converted_type(),
+                ConvertedType::TIMESTAMP_MILLIS | ConvertedType::TIMESTAMP_MICROS
+            ) =>
+        {
+            "timestamp"
+        },
+        (PhysicalType::INT64, _) => "long",
+        (PhysicalType::FLOAT, _) => "float",
+        (PhysicalType::DOUBLE, _) => "double",
+        (PhysicalType::BYTE_ARRAY, Some(LogicalType::String | LogicalType::Json)) => "string",
+        (PhysicalType::BYTE_ARRAY, _) if basic_info.converted_type() == ConvertedType::UTF8 => {
+            "string"
+        },
+        (PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY, _) => "binary",
+        (physical_type, _) => anyhow::bail!(
+            "Column {} has unsupported type {:?}",
+            field.name(),
+            physical_type
+        ),
+    };
+    Ok(delta_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet::schema::parser::parse_message_type;
+
+    #[test]
+    fn test_delta_schema() {
+        let schema = parse_message_type(
+            "message transactions {
+                REQUIRED INT64 txn_version;
+                REQUIRED BOOLEAN success;
+                OPTIONAL BYTE_ARRAY payload (UTF8);
+                REQUIRED INT64 block_timestamp (TIMESTAMP(MILLIS,false));
+                REQUIRED INT64 inserted_at (TIMESTAMP_MILLIS);
+                REQUIRED INT64 updated_at (TIMESTAMP(MICROS,true));
+            }",
+        )
+        .unwrap();
+        let delta_schema = delta_schema(&schema).unwrap();
+        let fields = delta_schema["fields"].as_array().unwrap();
+        assert_eq!(fields.len(), 6);
+        assert_eq!(fields[0]["type"], "long");
+        assert_eq!(fields[0]["nullable"], false);
+        assert_eq!(fields[1]["type"], "boolean");
+        assert_eq!(fields[2]["type"], "string");
+        assert_eq!(fields[2]["nullable"], true);
+        assert_eq!(fields[3]["type"], "timestamp_ntz");
+        assert_eq!(fields[4]["type"], "timestamp");
+        assert_eq!(fields[5]["type"], "timestamp");
+        let protocol = delta_protocol(&delta_schema);
+        assert_eq!(protocol["minReaderVersion"], TABLE_FEATURES_READER_VERSION);
+        assert_eq!(protocol["readerFeatures"], json!([TIMESTAMP_NTZ_FEATURE]));
+    }
+
+    #[test]
+    fn test_commit_adds_file() {
+        let commit =
+            b"{\"commitInfo\":{\"operation\":\"WRITE\"}}\n{\"add\":{\"path\":\"a.parquet\"}}\n";
+        assert!(commit_adds_file(commit, "a.parquet").unwrap());
+        assert!(!commit_adds_file(commit, "b.parquet").unwrap());
+    }
+
+    #[test]
+    fn test_parse_commit_version() {
+        assert_eq!(
+            parse_commit_version("root/transactions/_delta_log/00000000000000000012.json"),
+            Some(12)
+        );
+        assert_eq!(
+            parse_commit_version("root/transactions/_delta_log/_last_checkpoint"),
+            None
+        );
+    }
+}
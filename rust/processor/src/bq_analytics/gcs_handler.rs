@@ -3,7 +3,10 @@ use anyhow::Result;
 use chrono::{Datelike, Timelike};
 use google_cloud_storage::{
     client::Client as GCSClient,
-    http::objects::upload::{Media, UploadObjectRequest, UploadType},
+    http::objects::{
+        delete::DeleteObjectRequest,
+        upload::{Media, UploadObjectRequest, UploadType},
+    },
 };
 use hyper::Body;
 use std::path::{Path, PathBuf};
@@ -18,7 +21,7 @@ pub async fn upload_parquet_to_gcs(
     table_name: &str,
     bucket_name: &str,
     bucket_root: &Path,
-) -> Result<(), ParquetProcessorError> {
+) -> Result<PathBuf, ParquetProcessorError> {
     if buffer.is_empty() {
         error!("The file is empty and has no data to upload.",);
         return Err(ParquetProcessorError::Other(
@@ -66,7 +69,7 @@ pub async fn upload_parquet_to_gcs(
         match upload_result {
             Ok(Ok(result)) => {
                 info!("File uploaded successfully to GCS: {}", result.name);
-                return Ok(());
+                return Ok(object_name);
             },
             Ok(Err(e)) => {
                 error!("Failed to upload file to GCS: {}", e);
@@ -89,6 +92,23 @@ pub async fn upload_parquet_to_gcs(
     }
 }
 
+/// Deletes an uploaded file that couldn't be committed, a failure is only logged since the
+/// file isn't referenced by anything.
+pub async fn delete_parquet_from_gcs(client: &GCSClient, bucket_name: &str, file_path: &Path) {
+    let delete_request = DeleteObjectRequest {
+        bucket: bucket_name.to_string(),
+        object: file_path.to_string_lossy().into_owned(),
+        ..Default::default()
+    };
+    match client.delete_object(&delete_request).await {
+        Ok(_) => info!("Deleted uncommitted file from GCS: {:?}", file_path),
+        Err(e) => error!(
+            "Failed to delete uncommitted file {:?} from GCS: {}",
+            file_path, e
+        ),
+    }
+}
+
 fn generate_parquet_file_path(
     gcs_bucket_root: &Path,
     table: &str,
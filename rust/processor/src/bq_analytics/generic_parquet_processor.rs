@@ -1,22 +1,38 @@
 use super::ParquetProcessingResult;
 use crate::{
-    bq_analytics::gcs_handler::upload_parquet_to_gcs,
+    bq_analytics::{
+        delta_log::{DeltaLogWriter, ParquetTableFormat},
+        gcs_handler::{delete_parquet_from_gcs, upload_parquet_to_gcs},
+    },
+    db::common::models::{
+        parquet_table_status::ParquetTableStatus,
+        parquet_upload_manifest::{
+            contiguous_version_from_ranges, merge_version_ranges, ParquetUploadManifest,
+        },
+    },
     gap_detectors::ProcessingResult,
+    schema::{parquet_table_status, parquet_upload_manifests},
     utils::{
         counters::{PARQUET_HANDLER_BUFFER_SIZE, PARQUET_STRUCT_SIZE},
+        database::{execute_with_better_error, ArcDbPool},
         util::naive_datetime_to_timestamp,
     },
 };
 use ahash::AHashMap;
 use allocative::Allocative;
 use anyhow::{Context, Result};
+use diesel::{pg::upsert::excluded, ExpressionMethods};
 use google_cloud_storage::client::Client as GCSClient;
 use parquet::{
     file::{properties::WriterProperties, writer::SerializedFileWriter},
     record::RecordWriter,
     schema::types::Type,
 };
-use std::{path::PathBuf, sync::Arc, time::Instant};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
 use tokio::time::Duration;
 use tracing::{debug, error, info};
 
@@ -24,10 +40,16 @@ use tracing::{debug, error, info};
 pub struct ParquetDataGeneric<ParquetType> {
     pub data: Vec<ParquetType>,
     pub transaction_version_to_struct_count: AHashMap<i64, i64>,
+    // Version range of the batch the structs were parsed from. The range is covered by the
+    // table even if the batch didn't produce any structs for it.
+    pub start_version: i64,
+    pub end_version: i64,
 }
 
 pub trait NamedTable {
     const TABLE_NAME: &'static str;
+    /// Bumped on every change to the parquet schema of the table, see `parquet_schema`.
+    const SCHEMA_VERSION: u32;
 }
 
 pub trait HasVersion {
@@ -56,29 +78,38 @@ where
 
 pub struct ParquetHandler<ParquetType>
 where
-    ParquetType: NamedTable + NamedTable + HasVersion + HasParquetSchema + 'static + Allocative,
+    ParquetType: NamedTable + HasVersion + HasParquetSchema + 'static + Allocative,
     for<'a> &'a [ParquetType]: RecordWriter<ParquetType>,
 {
     pub schema: Arc<Type>,
+    pub writer_properties: Arc<WriterProperties>,
     pub writer: SerializedFileWriter<Vec<u8>>,
     pub buffer: Vec<ParquetType>,
     pub buffer_size_bytes: usize,
 
     pub transaction_version_to_struct_count: AHashMap<i64, i64>,
+    // Version ranges of the batches in the buffer, recorded in the upload manifests
+    pub buffered_version_ranges: Vec<(i64, i64)>,
+    pub processor_name: String,
+    pub db_pool: ArcDbPool,
+    // Versions before the starting version of the table are skipped, they're already uploaded
+    pub starting_version: i64,
+    // Next version of the table checkpoint, shared by all the handlers of the table
+    pub next_checkpoint_version: i64,
     pub bucket_name: String,
     pub bucket_root: String,
     pub gap_detector_sender: kanal::AsyncSender<ProcessingResult>,
     pub upload_interval: Duration,
     pub max_buffer_size: usize,
     pub last_upload_time: Instant,
+    pub delta_log: Option<DeltaLogWriter>,
 }
-fn create_new_writer(schema: Arc<Type>) -> Result<SerializedFileWriter<Vec<u8>>> {
-    let props = WriterProperties::builder()
-        .set_compression(parquet::basic::Compression::LZ4)
-        .build();
-    let props_arc = Arc::new(props);
-
-    SerializedFileWriter::new(Vec::new(), schema, props_arc).context("Failed to create new writer")
+fn create_new_writer(
+    schema: Arc<Type>,
+    writer_properties: Arc<WriterProperties>,
+) -> Result<SerializedFileWriter<Vec<u8>>> {
+    SerializedFileWriter::new(Vec::new(), schema, writer_properties)
+        .context("Failed to create new writer")
 }
 
 impl<ParquetType> ParquetHandler<ParquetType>
@@ -87,7 +118,7 @@ where
     for<'a> &'a [ParquetType]: RecordWriter<ParquetType>,
 {
     fn create_new_writer(&self) -> Result<SerializedFileWriter<Vec<u8>>> {
-        create_new_writer(self.schema.clone())
+        create_new_writer(self.schema.clone(), self.writer_properties.clone())
     }
 
     fn close_writer(&mut self) -> Result<SerializedFileWriter<Vec<u8>>> {
@@ -97,28 +128,48 @@ where
     }
 
     pub fn new(
+        processor_name: String,
+        db_pool: ArcDbPool,
+        starting_version: u64,
         bucket_name: String,
         bucket_root: String,
         gap_detector_sender: kanal::AsyncSender<ProcessingResult>,
         schema: Arc<Type>,
+        writer_properties: Arc<WriterProperties>,
         upload_interval: Duration,
         max_buffer_size: usize,
+        table_format: ParquetTableFormat,
     ) -> Result<Self> {
         // had to append unique id to avoid concurrent write issues
-        let writer = create_new_writer(schema.clone())?;
+        let writer = create_new_writer(schema.clone(), writer_properties.clone())?;
+        let delta_log = match table_format {
+            ParquetTableFormat::Parquet => None,
+            ParquetTableFormat::DeltaLake => Some(DeltaLogWriter::new(
+                bucket_name.clone(),
+                &PathBuf::from(&bucket_root),
+                ParquetType::TABLE_NAME,
+            )),
+        };
 
         Ok(Self {
             writer,
             buffer: Vec::new(),
             buffer_size_bytes: 0,
             transaction_version_to_struct_count: AHashMap::new(),
+            buffered_version_ranges: Vec::new(),
+            processor_name,
+            db_pool,
+            starting_version: starting_version as i64,
+            next_checkpoint_version: starting_version as i64,
             bucket_name,
             bucket_root,
             gap_detector_sender,
             schema,
+            writer_properties,
             upload_interval,
             max_buffer_size,
             last_upload_time: Instant::now(),
+            delta_log,
         })
     }
 
@@ -127,38 +178,62 @@ where
         gcs_client: &GCSClient,
         changes: ParquetDataGeneric<ParquetType>,
     ) -> Result<()> {
-        let parquet_structs = changes.data;
-        self.transaction_version_to_struct_count
-            .extend(changes.transaction_version_to_struct_count);
+        let ParquetDataGeneric {
+            data: parquet_structs,
+            mut transaction_version_to_struct_count,
+            start_version,
+            end_version,
+        } = changes;
+        if end_version >= self.starting_version {
+            self.buffered_version_ranges
+                .push((start_version.max(self.starting_version), end_version));
+        }
 
+        let mut skipped_structs = vec![];
         for parquet_struct in parquet_structs {
+            if parquet_struct.version() < self.starting_version {
+                skipped_structs.push(parquet_struct);
+                continue;
+            }
             let size_of_struct = allocative::size_of_unique(&parquet_struct);
             PARQUET_STRUCT_SIZE
                 .with_label_values(&[ParquetType::TABLE_NAME])
                 .set(size_of_struct as i64);
             self.buffer_size_bytes += size_of_struct;
             self.buffer.push(parquet_struct);
+        }
+        let skipped_struct_count =
+            process_struct_count_map(&skipped_structs, &mut transaction_version_to_struct_count);
+        self.transaction_version_to_struct_count.extend(
+            transaction_version_to_struct_count
+                .into_iter()
+                .filter(|(version, _)| *version >= self.starting_version),
+        );
+        if !skipped_struct_count.is_empty() {
+            self.send_skipped_struct_count(skipped_struct_count).await;
+        }
 
-            if self.buffer_size_bytes >= self.max_buffer_size {
-                info!("Max buffer size reached, uploading to GCS.");
-                if let Err(e) = self.upload_buffer(gcs_client).await {
-                    error!("Failed to upload buffer: {}", e);
-                    return Err(e);
-                }
-                self.last_upload_time = Instant::now();
+        // Only upload on batch boundaries so that every file fully covers the batches in it,
+        // the buffer can exceed max_buffer_size by up to one batch.
+        if self.buffer_size_bytes >= self.max_buffer_size {
+            info!("Max buffer size reached, uploading to GCS.");
+            if let Err(e) = self.upload_buffer(gcs_client).await {
+                error!("Failed to upload buffer: {}", e);
+                return Err(e);
             }
+            self.last_upload_time = Instant::now();
+        }
 
-            if self.last_upload_time.elapsed() >= self.upload_interval {
-                info!(
-                    "Time has elapsed more than {} since last upload.",
-                    self.upload_interval.as_secs()
-                );
-                if let Err(e) = self.upload_buffer(gcs_client).await {
-                    error!("Failed to upload buffer: {}", e);
-                    return Err(e);
-                }
-                self.last_upload_time = Instant::now();
+        if self.last_upload_time.elapsed() >= self.upload_interval {
+            info!(
+                "Time has elapsed more than {} since last upload.",
+                self.upload_interval.as_secs()
+            );
+            if let Err(e) = self.upload_buffer(gcs_client).await {
+                error!("Failed to upload buffer: {}", e);
+                return Err(e);
             }
+            self.last_upload_time = Instant::now();
         }
 
         PARQUET_HANDLER_BUFFER_SIZE
@@ -169,6 +244,16 @@ where
 
     async fn upload_buffer(&mut self, gcs_client: &GCSClient) -> Result<()> {
         if self.buffer.is_empty() {
+            // Nothing to upload, the batches are still covered by the table so that its
+            // checkpoint moves forward
+            if !self.buffered_version_ranges.is_empty() {
+                self.write_upload_manifests(None, 0, 0)
+                    .await
+                    .context("Failed to write parquet upload manifest")?;
+                self.update_table_checkpoint()
+                    .await
+                    .context("Failed to update parquet table checkpoint")?;
+            }
             return Ok(());
         }
         let start_version = self
@@ -187,6 +272,7 @@ where
             process_struct_count_map(&self.buffer, &mut self.transaction_version_to_struct_count);
 
         let struct_buffer = std::mem::take(&mut self.buffer);
+        let num_records = struct_buffer.len() as i64;
 
         let mut row_group_writer = self
             .writer
@@ -212,16 +298,35 @@ where
             end_version = end_version,
             "Max buffer size reached, uploading to GCS."
         );
-        let bucket_root = PathBuf::from(&self.bucket_root);
+        let file_size = upload_buffer.len() as i64;
+        let file_path = match self
+            .upload_and_commit(gcs_client, upload_buffer, file_size, num_records)
+            .await
+        {
+            Ok(file_path) => file_path,
+            Err(e) => {
+                // Nothing was committed, so the structs are kept in the buffer and written to
+                // a new file on the next upload
+                error!(
+                    table_name = ParquetType::TABLE_NAME,
+                    start_version = start_version,
+                    end_version = end_version,
+                    "Failed to upload parquet file, retrying with the next upload: {:?}",
+                    e
+                );
+                self.buffer = struct_buffer;
+                self.transaction_version_to_struct_count
+                    .extend(txn_version_to_struct_count);
+                return Ok(());
+            },
+        };
 
-        upload_parquet_to_gcs(
-            gcs_client,
-            upload_buffer,
-            ParquetType::TABLE_NAME,
-            &self.bucket_name,
-            &bucket_root,
-        )
-        .await?;
+        self.write_upload_manifests(Some(file_path.as_path()), num_records, file_size)
+            .await
+            .context("Failed to write parquet upload manifest")?;
+        self.update_table_checkpoint()
+            .await
+            .context("Failed to update parquet table checkpoint")?;
 
         self.buffer_size_bytes = 0;
 
@@ -241,6 +346,163 @@ where
 
         Ok(())
     }
+
+    /// Uploads the file and commits it to the table log, if there's one. A file that can't be
+    /// committed is deleted, so that a failed upload leaves nothing behind.
+    async fn upload_and_commit(
+        &mut self,
+        gcs_client: &GCSClient,
+        upload_buffer: Vec<u8>,
+        file_size: i64,
+        num_records: i64,
+    ) -> Result<PathBuf> {
+        let file_path = upload_parquet_to_gcs(
+            gcs_client,
+            upload_buffer,
+            ParquetType::TABLE_NAME,
+            &self.bucket_name,
+            &PathBuf::from(&self.bucket_root),
+        )
+        .await?;
+
+        // The file only becomes visible to table readers once it's committed to the log
+        if let Some(delta_log) = self.delta_log.as_mut() {
+            if let Err(e) = delta_log
+                .commit(gcs_client, &self.schema, &file_path, file_size, num_records)
+                .await
+            {
+                delete_parquet_from_gcs(gcs_client, &self.bucket_name, &file_path).await;
+                return Err(e).context("Failed to commit parquet file to delta log");
+            }
+        }
+        Ok(file_path)
+    }
+
+    /// The structs before the starting version of the table are already uploaded, but the gap
+    /// detector starts at the lowest starting version of all the tables and still expects them.
+    async fn send_skipped_struct_count(&self, txn_version_to_struct_count: AHashMap<i64, i64>) {
+        let (start_version, end_version) = txn_version_to_struct_count
+            .keys()
+            .fold((i64::MAX, i64::MIN), |(start, end), version| {
+                (start.min(*version), end.max(*version))
+            });
+        self.gap_detector_sender
+            .send(ProcessingResult::ParquetProcessingResult(
+                ParquetProcessingResult {
+                    start_version,
+                    end_version,
+                    last_transaction_timestamp: None,
+                    txn_version_to_struct_count,
+                },
+            ))
+            .await
+            .expect("[Parser] Failed to send versions to gap detector");
+    }
+
+    /// Records the version ranges of the batches in the uploaded file, or without a file when
+    /// the batches since the last upload didn't produce any structs.
+    async fn write_upload_manifests(
+        &mut self,
+        file_path: Option<&Path>,
+        num_records: i64,
+        file_size: i64,
+    ) -> Result<()> {
+        let version_ranges =
+            merge_version_ranges(std::mem::take(&mut self.buffered_version_ranges));
+        let manifests = version_ranges
+            .into_iter()
+            .map(|(start_version, end_version)| ParquetUploadManifest {
+                processor: self.processor_name.clone(),
+                table_name: ParquetType::TABLE_NAME.to_string(),
+                start_version,
+                end_version,
+                file_path: file_path.map(|path| path.to_string_lossy().into_owned()),
+                num_records,
+                file_size,
+            })
+            .collect::<Vec<_>>();
+        execute_with_better_error(
+            self.db_pool.clone(),
+            diesel::insert_into(parquet_upload_manifests::table)
+                .values(manifests)
+                .on_conflict((
+                    parquet_upload_manifests::processor,
+                    parquet_upload_manifests::table_name,
+                    parquet_upload_manifests::start_version,
+                    parquet_upload_manifests::end_version,
+                ))
+                .do_update()
+                .set((
+                    parquet_upload_manifests::file_path
+                        .eq(excluded(parquet_upload_manifests::file_path)),
+                    parquet_upload_manifests::num_records
+                        .eq(excluded(parquet_upload_manifests::num_records)),
+                    parquet_upload_manifests::file_size
+                        .eq(excluded(parquet_upload_manifests::file_size)),
+                    parquet_upload_manifests::inserted_at
+                        .eq(excluded(parquet_upload_manifests::inserted_at)),
+                )),
+            None,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Moves the table checkpoint forward over the contiguous ranges uploaded by all the
+    /// handlers of the table. The manifests behind the checkpoint are deleted, except the one
+    /// ending at it that the resume version falls back to.
+    async fn update_table_checkpoint(&mut self) -> Result<()> {
+        let ranges = {
+            let mut conn = self.db_pool.get().await?;
+            ParquetUploadManifest::get_uploaded_ranges(
+                &self.processor_name,
+                ParquetType::TABLE_NAME,
+                self.next_checkpoint_version,
+                &mut conn,
+            )
+            .await?
+        };
+        let next_checkpoint_version =
+            contiguous_version_from_ranges(&ranges, self.next_checkpoint_version);
+        if next_checkpoint_version == self.next_checkpoint_version {
+            return Ok(());
+        }
+        self.next_checkpoint_version = next_checkpoint_version;
+
+        let status = ParquetTableStatus {
+            processor: self.processor_name.clone(),
+            table_name: ParquetType::TABLE_NAME.to_string(),
+            last_success_version: next_checkpoint_version - 1,
+        };
+        execute_with_better_error(
+            self.db_pool.clone(),
+            diesel::insert_into(parquet_table_status::table)
+                .values(&status)
+                .on_conflict((
+                    parquet_table_status::processor,
+                    parquet_table_status::table_name,
+                ))
+                .do_update()
+                .set((
+                    parquet_table_status::last_success_version
+                        .eq(excluded(parquet_table_status::last_success_version)),
+                    parquet_table_status::last_updated
+                        .eq(excluded(parquet_table_status::last_updated)),
+                )),
+            Some(" WHERE parquet_table_status.last_success_version <= EXCLUDED.last_success_version "),
+        )
+        .await?;
+
+        let mut conn = self.db_pool.get().await?;
+        ParquetUploadManifest::delete_before(
+            &self.processor_name,
+            ParquetType::TABLE_NAME,
+            next_checkpoint_version - 1,
+            &mut conn,
+        )
+        .await?;
+        Ok(())
+    }
 }
 
 fn process_struct_count_map<ParquetType: NamedTable + HasVersion>(
@@ -1,12 +1,21 @@
+pub mod delta_log;
 pub mod gcs_handler;
 pub mod generic_parquet_processor;
+pub mod parquet_schema;
+pub mod parquet_writer_config;
 
 use crate::{
-    bq_analytics::generic_parquet_processor::{
-        GetTimeStamp, HasParquetSchema, HasVersion, NamedTable, ParquetDataGeneric,
-        ParquetHandler as GenericParquetHandler,
+    bq_analytics::{
+        delta_log::ParquetTableFormat,
+        generic_parquet_processor::{
+            GetTimeStamp, HasParquetSchema, HasVersion, NamedTable, ParquetDataGeneric,
+            ParquetHandler as GenericParquetHandler,
+        },
+        parquet_schema::validate_parquet_schema,
+        parquet_writer_config::{get_writer_properties, ParquetWriterConfig},
     },
     gap_detectors::ProcessingResult,
+    utils::database::ArcDbPool,
     worker::PROCESSOR_SERVICE_TYPE,
 };
 use ahash::AHashMap;
@@ -85,6 +94,8 @@ impl From<parquet::errors::ParquetError> for ParquetProcessorError {
 }
 
 pub fn create_parquet_handler_loop<ParquetType>(
+    db_pool: ArcDbPool,
+    parquet_table_start_versions: &AHashMap<String, u64>,
     new_gap_detector_sender: AsyncSender<ProcessingResult>,
     processor_name: &str,
     bucket_name: String,
@@ -92,6 +103,8 @@ pub fn create_parquet_handler_loop<ParquetType>(
     parquet_handler_response_channel_size: usize,
     max_buffer_size: usize,
     upload_interval: Duration,
+    per_table_writer_configs: &AHashMap<String, ParquetWriterConfig>,
+    table_format: ParquetTableFormat,
 ) -> AsyncSender<ParquetDataGeneric<ParquetType>>
 where
     ParquetType: GetTimeStamp
@@ -104,6 +117,15 @@ where
         + Allocative,
     for<'a> &'a [ParquetType]: RecordWriter<ParquetType>,
 {
+    // Refuse to write files whose schema doesn't match the versioned golden schema
+    validate_parquet_schema::<ParquetType>().unwrap_or_else(|e| {
+        panic!(
+            "[Parquet Handler] Invalid schema for table {}: {:?}",
+            ParquetType::TABLE_NAME,
+            e
+        )
+    });
+
     let processor_name = processor_name.to_owned();
     let (parquet_sender, parquet_receiver) = kanal::bounded_async::<ParquetDataGeneric<ParquetType>>(
         parquet_handler_response_channel_size,
@@ -116,12 +138,26 @@ where
     );
 
     let mut parquet_manager = GenericParquetHandler::new(
+        processor_name.clone(),
+        db_pool,
+        parquet_table_start_versions
+            .get(ParquetType::TABLE_NAME)
+            .copied()
+            .unwrap_or_default(),
         bucket_name.clone(),
         bucket_root.clone(),
         new_gap_detector_sender.clone(),
         ParquetType::schema(),
+        // The writer configs are validated when the processor config is loaded
+        get_writer_properties(
+            ParquetType::TABLE_NAME,
+            ParquetType::SCHEMA_VERSION,
+            per_table_writer_configs,
+        )
+        .expect("Invalid parquet writer config"),
         upload_interval,
         max_buffer_size,
+        table_format,
     )
     .expect("Failed to create parquet manager");
 
@@ -0,0 +1,625 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Versioned parquet schemas. The schema of every parquet table is derived from its struct,
+//! so adding or changing a field silently changes the output files. Each table declares a
+//! `SCHEMA_VERSION` which is written to the file metadata, and the schema of every released
+//! version is kept in `schemas/<table>/v<version>.json`. At startup and in tests the schema has
+//! to match the golden schema of its version and be compatible with the previous version, so
+//! a breaking change can't be hidden by bumping the version and regenerating the golden schema.
+
+use crate::bq_analytics::generic_parquet_processor::{HasParquetSchema, NamedTable};
+use anyhow::{Context, Result};
+use parquet::{
+    basic::{ConvertedType, LogicalType, Repetition, TimeUnit, Type as PhysicalType},
+    schema::types::Type,
+};
+use serde::{Deserialize, Serialize};
+
+/// Key of the schema version in the parquet file metadata
+pub const SCHEMA_VERSION_METADATA_KEY: &str = "aptos_schema_version";
+
+/// Golden schemas of every released version, keyed by table name and version. Add the file of
+/// the new version, generated with `ParquetSchemaSnapshot::to_golden`, whenever the schema
+/// version of a table is bumped, and keep the files of the previous versions.
+const GOLDEN_SCHEMAS: &[(&str, u32, &str)] = &[
+    (
+        "coin_supply",
+        1,
+        include_str!("schemas/coin_supply/v1.json"),
+    ),
+    (
+        "fungible_asset_balances",
+        1,
+        include_str!("schemas/fungible_asset_balances/v1.json"),
+    ),
+    (
+        "move_modules",
+        1,
+        include_str!("schemas/move_modules/v1.json"),
+    ),
+    (
+        "move_resources",
+        1,
+        include_str!("schemas/move_resources/v1.json"),
+    ),
+    (
+        "table_items",
+        1,
+        include_str!("schemas/table_items/v1.json"),
+    ),
+    (
+        "table_metadatas",
+        1,
+        include_str!("schemas/table_metadatas/v1.json"),
+    ),
+    (
+        "transactions",
+        1,
+        include_str!("schemas/transactions/v1.json"),
+    ),
+    (
+        "write_set_changes",
+        1,
+        include_str!("schemas/write_set_changes/v1.json"),
+    ),
+];
+
+// The types below mirror the parquet types with the names of the parquet format, so that the
+// golden schemas don't depend on how the parquet crate formats its types.
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ParquetPhysicalType {
+    Boolean,
+    Int32,
+    Int64,
+    Int96,
+    Float,
+    Double,
+    ByteArray,
+    FixedLenByteArray,
+    // Nested column
+    Group,
+}
+
+impl From<PhysicalType> for ParquetPhysicalType {
+    fn from(physical_type: PhysicalType) -> Self {
+        match physical_type {
+            PhysicalType::BOOLEAN => Self::Boolean,
+            PhysicalType::INT32 => Self::Int32,
+            PhysicalType::INT64 => Self::Int64,
+            PhysicalType::INT96 => Self::Int96,
+            PhysicalType::FLOAT => Self::Float,
+            PhysicalType::DOUBLE => Self::Double,
+            PhysicalType::BYTE_ARRAY => Self::ByteArray,
+            PhysicalType::FIXED_LEN_BYTE_ARRAY => Self::FixedLenByteArray,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ParquetTimeUnit {
+    Millis,
+    Micros,
+    Nanos,
+}
+
+impl From<&TimeUnit> for ParquetTimeUnit {
+    fn from(unit: &TimeUnit) -> Self {
+        match unit {
+            TimeUnit::MILLIS(_) => Self::Millis,
+            TimeUnit::MICROS(_) => Self::Micros,
+            TimeUnit::NANOS(_) => Self::Nanos,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ParquetLogicalType {
+    String,
+    Map,
+    List,
+    Enum,
+    Decimal {
+        scale: i32,
+        precision: i32,
+    },
+    Date,
+    Time {
+        is_adjusted_to_utc: bool,
+        unit: ParquetTimeUnit,
+    },
+    Timestamp {
+        is_adjusted_to_utc: bool,
+        unit: ParquetTimeUnit,
+    },
+    Integer {
+        bit_width: i8,
+        is_signed: bool,
+    },
+    Unknown,
+    Json,
+    Bson,
+    Uuid,
+    Float16,
+}
+
+impl From<&LogicalType> for ParquetLogicalType {
+    fn from(logical_type: &LogicalType) -> Self {
+        match logical_type {
+            LogicalType::String => Self::String,
+            LogicalType::Map => Self::Map,
+            LogicalType::List => Self::List,
+            LogicalType::Enum => Self::Enum,
+            LogicalType::Decimal { scale, precision } => Self::Decimal {
+                scale: *scale,
+                precision: *precision,
+            },
+            LogicalType::Date => Self::Date,
+            LogicalType::Time {
+                is_adjusted_to_u_t_c,
+                unit,
+            } => Self::Time {
+                is_adjusted_to_utc: *is_adjusted_to_u_t_c,
+                unit: unit.into(),
+            },
+            LogicalType::Timestamp {
+                is_adjusted_to_u_t_c,
+                unit,
+            } => Self::Timestamp {
+                is_adjusted_to_utc: *is_adjusted_to_u_t_c,
+                unit: unit.into(),
+            },
+            LogicalType::Integer {
+                bit_width,
+                is_signed,
+            } => Self::Integer {
+                bit_width: *bit_width,
+                is_signed: *is_signed,
+            },
+            LogicalType::Unknown => Self::Unknown,
+            LogicalType::Json => Self::Json,
+            LogicalType::Bson => Self::Bson,
+            LogicalType::Uuid => Self::Uuid,
+            LogicalType::Float16 => Self::Float16,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ParquetConvertedType {
+    Utf8,
+    Map,
+    MapKeyValue,
+    List,
+    Enum,
+    Decimal,
+    Date,
+    TimeMillis,
+    TimeMicros,
+    TimestampMillis,
+    TimestampMicros,
+    #[serde(rename = "UINT_8")]
+    Uint8,
+    #[serde(rename = "UINT_16")]
+    Uint16,
+    #[serde(rename = "UINT_32")]
+    Uint32,
+    #[serde(rename = "UINT_64")]
+    Uint64,
+    #[serde(rename = "INT_8")]
+    Int8,
+    #[serde(rename = "INT_16")]
+    Int16,
+    #[serde(rename = "INT_32")]
+    Int32,
+    #[serde(rename = "INT_64")]
+    Int64,
+    Json,
+    Bson,
+    Interval,
+}
+
+impl ParquetConvertedType {
+    fn from_converted_type(converted_type: ConvertedType) -> Option<Self> {
+        let converted_type = match converted_type {
+            ConvertedType::NONE => return None,
+            ConvertedType::UTF8 => Self::Utf8,
+            ConvertedType::MAP => Self::Map,
+            ConvertedType::MAP_KEY_VALUE => Self::MapKeyValue,
+            ConvertedType::LIST => Self::List,
+            ConvertedType::ENUM => Self::Enum,
+            ConvertedType::DECIMAL => Self::Decimal,
+            ConvertedType::DATE => Self::Date,
+            ConvertedType::TIME_MILLIS => Self::TimeMillis,
+            ConvertedType::TIME_MICROS => Self::TimeMicros,
+            ConvertedType::TIMESTAMP_MILLIS => Self::TimestampMillis,
+            ConvertedType::TIMESTAMP_MICROS => Self::TimestampMicros,
+            ConvertedType::UINT_8 => Self::Uint8,
+            ConvertedType::UINT_16 => Self::Uint16,
+            ConvertedType::UINT_32 => Self::Uint32,
+            ConvertedType::UINT_64 => Self::Uint64,
+            ConvertedType::INT_8 => Self::Int8,
+            ConvertedType::INT_16 => Self::Int16,
+            ConvertedType::INT_32 => Self::Int32,
+            ConvertedType::INT_64 => Self::Int64,
+            ConvertedType::JSON => Self::Json,
+            ConvertedType::BSON => Self::Bson,
+            ConvertedType::INTERVAL => Self::Interval,
+        };
+        Some(converted_type)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ParquetRepetition {
+    Required,
+    Optional,
+    Repeated,
+}
+
+impl From<Repetition> for ParquetRepetition {
+    fn from(repetition: Repetition) -> Self {
+        match repetition {
+            Repetition::REQUIRED => Self::Required,
+            Repetition::OPTIONAL => Self::Optional,
+            Repetition::REPEATED => Self::Repeated,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ParquetColumn {
+    pub name: String,
+    pub physical_type: ParquetPhysicalType,
+    // How readers interpret the physical type, e.g. a BYTE_ARRAY as a string or an INT64 as a
+    // timestamp. parquet_derive only sets the converted type for some types, so keep both.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logical_type: Option<ParquetLogicalType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub converted_type: Option<ParquetConvertedType>,
+    pub repetition: ParquetRepetition,
+}
+
+impl ParquetColumn {
+    fn has_same_type(&self, other: &Self) -> bool {
+        self.physical_type == other.physical_type
+            && self.logical_type == other.logical_type
+            && self.converted_type == other.converted_type
+    }
+
+    fn type_string(&self) -> String {
+        serde_json::json!({
+            "physical_type": self.physical_type,
+            "logical_type": self.logical_type,
+            "converted_type": self.converted_type,
+        })
+        .to_string()
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ParquetSchemaSnapshot {
+    pub table_name: String,
+    pub schema_version: u32,
+    pub columns: Vec<ParquetColumn>,
+}
+
+impl ParquetSchemaSnapshot {
+    pub fn from_schema(table_name: &str, schema_version: u32, schema: &Type) -> Self {
+        let columns = schema
+            .get_fields()
+            .iter()
+            .map(|field| {
+                let basic_info = field.get_basic_info();
+                ParquetColumn {
+                    name: field.name().to_string(),
+                    physical_type: if field.is_primitive() {
+                        field.get_physical_type().into()
+                    } else {
+                        ParquetPhysicalType::Group
+                    },
+                    logical_type: basic_info.logical_type().as_ref().map(Into::into),
+                    converted_type: ParquetConvertedType::from_converted_type(
+                        basic_info.converted_type(),
+                    ),
+                    repetition: basic_info.repetition().into(),
+                }
+            })
+            .collect();
+        Self {
+            table_name: table_name.to_string(),
+            schema_version,
+            columns,
+        }
+    }
+
+    pub fn from_table<ParquetType: NamedTable + HasParquetSchema>() -> Self {
+        Self::from_schema(
+            ParquetType::TABLE_NAME,
+            ParquetType::SCHEMA_VERSION,
+            &ParquetType::schema(),
+        )
+    }
+
+    /// Golden schemas of every released version of the table, ordered by version
+    pub fn goldens(table_name: &str) -> Result<Vec<Self>> {
+        let mut goldens = GOLDEN_SCHEMAS
+            .iter()
+            .filter(|(name, ..)| *name == table_name)
+            .map(|(_, version, golden)| {
+                let golden: Self = serde_json::from_str(golden).with_context(|| {
+                    format!(
+                        "Failed to parse golden schema {} of {}",
+                        version, table_name
+                    )
+                })?;
+                anyhow::ensure!(
+                    golden.table_name == table_name && golden.schema_version == *version,
+                    "Golden schema {} of {} is for version {} of {}",
+                    version,
+                    table_name,
+                    golden.schema_version,
+                    golden.table_name
+                );
+                Ok(golden)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        goldens.sort_by_key(|golden| golden.schema_version);
+        Ok(goldens)
+    }
+
+    pub fn to_golden(&self) -> String {
+        format!("{}\n", serde_json::to_string_pretty(self).unwrap())
+    }
+
+    /// Returns the changes that would break readers of files written with the `previous`
+    /// schema: removed columns, changed types and columns that are now required.
+    pub fn breaking_changes(&self, previous: &Self) -> Vec<String> {
+        let mut changes = vec![];
+        for previous_column in previous.columns.iter() {
+            match self.columns.iter().find(|c| c.name == previous_column.name) {
+                None => changes.push(format!("column {} was removed", previous_column.name)),
+                Some(column) => {
+                    if !column.has_same_type(previous_column) {
+                        changes.push(format!(
+                            "column {} changed type from {} to {}",
+                            column.name,
+                            previous_column.type_string(),
+                            column.type_string()
+                        ));
+                    }
+                    if column.repetition != previous_column.repetition
+                        && column.repetition == ParquetRepetition::Required
+                    {
+                        changes.push(format!("column {} became required", column.name));
+                    }
+                },
+            }
+        }
+        changes
+    }
+
+    /// Checks the schema against the golden schemas of the table, ordered by version:
+    /// - the schema must match the golden schema of its version, so any change (e.g. an added
+    ///   column) must come with a schema version bump and a new golden schema
+    /// - breaking changes from the previous released version always fail
+    pub fn check_compatibility(&self, goldens: &[Self]) -> Result<()> {
+        let latest_version = goldens
+            .last()
+            .map(|golden| golden.schema_version)
+            .with_context(|| format!("No golden schema found for table {}", self.table_name))?;
+        anyhow::ensure!(
+            self.schema_version >= latest_version,
+            "Schema version {} of table {} is older than the golden schema version {}",
+            self.schema_version,
+            self.table_name,
+            latest_version
+        );
+        let golden = goldens
+            .iter()
+            .find(|golden| golden.schema_version == self.schema_version)
+            .with_context(|| {
+                format!(
+                    "No golden schema for version {} of table {}, add schemas/{}/v{}.json",
+                    self.schema_version, self.table_name, self.table_name, self.schema_version
+                )
+            })?;
+        anyhow::ensure!(
+            self.columns == golden.columns,
+            "Parquet schema of table {} changed without bumping the schema version {}",
+            self.table_name,
+            self.schema_version
+        );
+        if let Some(previous) = goldens
+            .iter()
+            .rev()
+            .find(|golden| golden.schema_version < self.schema_version)
+        {
+            let breaking_changes = self.breaking_changes(previous);
+            anyhow::ensure!(
+                breaking_changes.is_empty(),
+                "Breaking parquet schema changes in table {} since version {}: {}",
+                self.table_name,
+                previous.schema_version,
+                breaking_changes.join(", ")
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Startup check for a parquet table, see `ParquetSchemaSnapshot::check_compatibility`.
+pub fn validate_parquet_schema<ParquetType: NamedTable + HasParquetSchema>() -> Result<()> {
+    let snapshot = ParquetSchemaSnapshot::from_table::<ParquetType>();
+    snapshot.check_compatibility(&ParquetSchemaSnapshot::goldens(ParquetType::TABLE_NAME)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::common::models::{
+        default_models::{
+            parquet_move_modules::MoveModule,
+            parquet_move_resources::MoveResource,
+            parquet_move_tables::{TableItem, TableMetadata},
+            parquet_transactions::Transaction,
+            parquet_write_set_changes::WriteSetChange,
+        },
+        fungible_asset_models::{
+            parquet_coin_supply::CoinSupply,
+            parquet_v2_fungible_asset_balances::FungibleAssetBalance,
+        },
+    };
+
+    fn assert_matches_golden<ParquetType: NamedTable + HasParquetSchema>() {
+        let snapshot = ParquetSchemaSnapshot::from_table::<ParquetType>();
+        let goldens = ParquetSchemaSnapshot::goldens(ParquetType::TABLE_NAME).unwrap();
+        assert!(
+            goldens.contains(&snapshot),
+            "Golden schema of version {} of {} is missing or out of date, write it to schemas/{}/v{}.json:\n{}",
+            ParquetType::SCHEMA_VERSION,
+            ParquetType::TABLE_NAME,
+            ParquetType::TABLE_NAME,
+            ParquetType::SCHEMA_VERSION,
+            snapshot.to_golden()
+        );
+        snapshot.check_compatibility(&goldens).unwrap();
+    }
+
+    /// When a parquet struct changes, bump its `SCHEMA_VERSION` and add a golden schema for it.
+    #[test]
+    fn test_parquet_schemas_match_golden() {
+        assert_matches_golden::<CoinSupply>();
+        assert_matches_golden::<FungibleAssetBalance>();
+        assert_matches_golden::<MoveModule>();
+        assert_matches_golden::<MoveResource>();
+        assert_matches_golden::<TableItem>();
+        assert_matches_golden::<TableMetadata>();
+        assert_matches_golden::<Transaction>();
+        assert_matches_golden::<WriteSetChange>();
+    }
+
+    fn column(
+        name: &str,
+        physical_type: ParquetPhysicalType,
+        repetition: ParquetRepetition,
+    ) -> ParquetColumn {
+        ParquetColumn {
+            name: name.to_string(),
+            physical_type,
+            logical_type: None,
+            converted_type: None,
+            repetition,
+        }
+    }
+
+    fn snapshot(schema_version: u32, columns: Vec<ParquetColumn>) -> ParquetSchemaSnapshot {
+        ParquetSchemaSnapshot {
+            table_name: "test".to_string(),
+            schema_version,
+            columns,
+        }
+    }
+
+    #[test]
+    fn test_check_compatibility() {
+        use ParquetPhysicalType::*;
+        use ParquetRepetition::*;
+
+        let v1 = snapshot(1, vec![
+            column("txn_version", Int64, Required),
+            column("payload", ByteArray, Optional),
+        ]);
+        assert!(v1.check_compatibility(std::slice::from_ref(&v1)).is_ok());
+
+        // Added column without a version bump
+        let mut added = v1.clone();
+        added.columns.push(column("gas_used", Int64, Required));
+        assert!(added
+            .check_compatibility(std::slice::from_ref(&v1))
+            .is_err());
+        // or without a golden schema for the new version
+        added.schema_version = 2;
+        assert!(added
+            .check_compatibility(std::slice::from_ref(&v1))
+            .is_err());
+        assert!(added
+            .check_compatibility(&[v1.clone(), added.clone()])
+            .is_ok());
+
+        // Breaking changes fail even with a version bump and a new golden schema
+        let removed = snapshot(2, vec![column("txn_version", Int64, Required)]);
+        assert!(removed
+            .check_compatibility(&[v1.clone(), removed.clone()])
+            .is_err());
+        let retyped = snapshot(2, vec![
+            column("txn_version", ByteArray, Required),
+            column("payload", ByteArray, Optional),
+        ]);
+        assert!(retyped
+            .check_compatibility(&[v1.clone(), retyped.clone()])
+            .is_err());
+        let required = snapshot(2, vec![
+            column("txn_version", Int64, Required),
+            column("payload", ByteArray, Required),
+        ]);
+        assert_eq!(required.breaking_changes(&v1), vec![
+            "column payload became required".to_string()
+        ]);
+        let mut timestamp = v1.clone();
+        timestamp.schema_version = 2;
+        timestamp.columns[0].converted_type = Some(ParquetConvertedType::TimestampMillis);
+        assert_eq!(timestamp.breaking_changes(&v1).len(), 1);
+        assert!(timestamp
+            .check_compatibility(&[v1.clone(), timestamp.clone()])
+            .is_err());
+
+        // Only the schema of the latest version can be written
+        assert!(v1.check_compatibility(&[v1.clone(), added]).is_err());
+    }
+
+    #[test]
+    fn test_golden_format() {
+        let schema = parquet::schema::parser::parse_message_type(
+            "message test {
+                REQUIRED BYTE_ARRAY txn_type (STRING);
+                REQUIRED INT64 gas_used (INTEGER(64,false));
+                REQUIRED INT64 block_timestamp (TIMESTAMP_MILLIS);
+            }",
+        )
+        .unwrap();
+        let snapshot = ParquetSchemaSnapshot::from_schema("test", 1, &schema);
+        let golden: serde_json::Value = serde_json::from_str(&snapshot.to_golden()).unwrap();
+        assert_eq!(
+            golden["columns"],
+            serde_json::json!([
+                {
+                    "name": "txn_type",
+                    "physical_type": "BYTE_ARRAY",
+                    "logical_type": { "type": "STRING" },
+                    "converted_type": "UTF8",
+                    "repetition": "REQUIRED",
+                },
+                {
+                    "name": "gas_used",
+                    "physical_type": "INT64",
+                    "logical_type": { "type": "INTEGER", "bit_width": 64, "is_signed": false },
+                    "converted_type": "UINT_64",
+                    "repetition": "REQUIRED",
+                },
+                {
+                    "name": "block_timestamp",
+                    "physical_type": "INT64",
+                    "converted_type": "TIMESTAMP_MILLIS",
+                    "repetition": "REQUIRED",
+                },
+            ])
+        );
+    }
+}
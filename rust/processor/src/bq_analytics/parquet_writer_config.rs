@@ -0,0 +1,228 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::bq_analytics::parquet_schema::SCHEMA_VERSION_METADATA_KEY;
+use ahash::AHashMap;
+use anyhow::{Context, Result};
+use parquet::{
+    basic::Compression,
+    file::{
+        metadata::KeyValue,
+        properties::{EnabledStatistics, WriterProperties},
+    },
+    schema::types::ColumnPath,
+};
+use serde::{Deserialize, Serialize};
+use std::{str::FromStr, sync::Arc};
+
+pub const DEFAULT_COMPRESSION: &str = "LZ4";
+pub const DEFAULT_STATISTICS: &str = "page";
+
+/// Writer properties for the parquet files of a single table. Scan heavy tables usually
+/// want large row groups and a strong codec (e.g. `ZSTD(9)`), while point lookup tables
+/// benefit from bloom filters on the columns that are filtered on (e.g. addresses).
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct ParquetWriterConfig {
+    /// Compression codec, e.g. `UNCOMPRESSED`, `SNAPPY`, `LZ4`, `LZ4_RAW`, `ZSTD(3)` or `GZIP(6)`.
+    pub compression: String,
+    /// Maximum number of rows in a row group. Uses the parquet default if not set.
+    pub max_row_group_size: Option<usize>,
+    /// Whether dictionary encoding is enabled for all columns.
+    pub dictionary_enabled: bool,
+    /// Column level statistics to write: `none`, `chunk` or `page`.
+    pub statistics: String,
+    /// Columns to write bloom filters for.
+    pub bloom_filter_columns: Vec<String>,
+    /// False positive probability of the bloom filters. Uses the parquet default if not set.
+    pub bloom_filter_fpp: Option<f64>,
+}
+
+impl Default for ParquetWriterConfig {
+    fn default() -> Self {
+        Self {
+            compression: DEFAULT_COMPRESSION.to_string(),
+            max_row_group_size: None,
+            dictionary_enabled: true,
+            statistics: DEFAULT_STATISTICS.to_string(),
+            bloom_filter_columns: vec![],
+            bloom_filter_fpp: None,
+        }
+    }
+}
+
+impl ParquetWriterConfig {
+    /// Checks every property, including the ones that only apply with bloom filter columns.
+    pub fn validate(&self) -> Result<()> {
+        self.compression()?;
+        self.statistics()?;
+        if let Some(max_row_group_size) = self.max_row_group_size {
+            anyhow::ensure!(
+                max_row_group_size > 0,
+                "max_row_group_size must be positive"
+            );
+        }
+        if let Some(fpp) = self.bloom_filter_fpp {
+            anyhow::ensure!(
+                fpp > 0.0 && fpp < 1.0,
+                "bloom_filter_fpp must be between 0 and 1"
+            );
+        }
+        for column in self.bloom_filter_columns.iter() {
+            anyhow::ensure!(!column.is_empty(), "bloom_filter_columns can't be empty");
+        }
+        Ok(())
+    }
+
+    fn compression(&self) -> Result<Compression> {
+        Compression::from_str(&self.compression)
+            .with_context(|| format!("Invalid parquet compression {}", self.compression))
+    }
+
+    fn statistics(&self) -> Result<EnabledStatistics> {
+        EnabledStatistics::from_str(&self.statistics)
+            .map_err(|e| anyhow::anyhow!(e))
+            .with_context(|| format!("Invalid parquet statistics level {}", self.statistics))
+    }
+
+    pub fn writer_properties(&self, schema_version: u32) -> Result<WriterProperties> {
+        self.validate()?;
+        let mut builder = WriterProperties::builder()
+            .set_compression(self.compression()?)
+            .set_dictionary_enabled(self.dictionary_enabled)
+            .set_statistics_enabled(self.statistics()?)
+            .set_key_value_metadata(Some(vec![KeyValue::new(
+                SCHEMA_VERSION_METADATA_KEY.to_string(),
+                schema_version.to_string(),
+            )]));
+        if let Some(max_row_group_size) = self.max_row_group_size {
+            builder = builder.set_max_row_group_size(max_row_group_size);
+        }
+        for column in self.bloom_filter_columns.iter() {
+            let column_path = ColumnPath::from(column.as_str());
+            builder = builder.set_column_bloom_filter_enabled(column_path.clone(), true);
+            if let Some(fpp) = self.bloom_filter_fpp {
+                builder = builder.set_column_bloom_filter_fpp(column_path, fpp);
+            }
+        }
+        Ok(builder.build())
+    }
+}
+
+/// Validates the writer configs of a parquet processor when its config is loaded, so that the
+/// parquet handlers don't fail on them later. Every table has to be one of the processor's.
+pub fn validate_writer_configs(
+    per_table_writer_configs: &AHashMap<String, ParquetWriterConfig>,
+    table_names: &[&str],
+) -> Result<()> {
+    for (table_name, config) in per_table_writer_configs.iter() {
+        anyhow::ensure!(
+            table_names.contains(&table_name.as_str()),
+            "Unknown table {} in per_table_writer_configs, expected one of {:?}",
+            table_name,
+            table_names
+        );
+        config
+            .validate()
+            .with_context(|| format!("Invalid writer config for table {}", table_name))?;
+    }
+    Ok(())
+}
+
+/// Returns the writer properties for a table, falling back to the default writer config if
+/// the table isn't configured.
+pub fn get_writer_properties(
+    table_name: &str,
+    schema_version: u32,
+    per_table_writer_configs: &AHashMap<String, ParquetWriterConfig>,
+) -> Result<Arc<WriterProperties>> {
+    let config = per_table_writer_configs
+        .get(table_name)
+        .cloned()
+        .unwrap_or_default();
+    let writer_properties = config
+        .writer_properties(schema_version)
+        .with_context(|| format!("Invalid writer config for table {}", table_name))?;
+    Ok(Arc::new(writer_properties))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_writer_config() {
+        let props = ParquetWriterConfig::default().writer_properties(2).unwrap();
+        let column = ColumnPath::from("txn_version");
+        assert_eq!(props.key_value_metadata().unwrap(), &vec![KeyValue::new(
+            SCHEMA_VERSION_METADATA_KEY.to_string(),
+            "2".to_string()
+        )]);
+        assert_eq!(props.compression(&column), Compression::LZ4);
+        assert!(props.dictionary_enabled(&column));
+        assert_eq!(props.statistics_enabled(&column), EnabledStatistics::Page);
+        assert!(props.bloom_filter_properties(&column).is_none());
+    }
+
+    #[test]
+    fn test_custom_writer_config() {
+        let config: ParquetWriterConfig = serde_json::from_value(serde_json::json!({
+            "compression": "ZSTD(9)",
+            "max_row_group_size": 4096,
+            "dictionary_enabled": false,
+            "statistics": "chunk",
+            "bloom_filter_columns": ["address"],
+            "bloom_filter_fpp": 0.01,
+        }))
+        .unwrap();
+        let props = config.writer_properties(1).unwrap();
+        let address = ColumnPath::from("address");
+        let other = ColumnPath::from("txn_version");
+        assert!(matches!(props.compression(&other), Compression::ZSTD(_)));
+        assert_eq!(props.max_row_group_size(), 4096);
+        assert!(!props.dictionary_enabled(&other));
+        assert_eq!(props.statistics_enabled(&other), EnabledStatistics::Chunk);
+        assert_eq!(props.bloom_filter_properties(&address).unwrap().fpp, 0.01);
+        assert!(props.bloom_filter_properties(&other).is_none());
+    }
+
+    #[test]
+    fn test_invalid_writer_config() {
+        let config = ParquetWriterConfig {
+            compression: "ZSTD(100)".to_string(),
+            ..Default::default()
+        };
+        assert!(config.writer_properties(1).is_err());
+
+        let config = ParquetWriterConfig {
+            statistics: "everything".to_string(),
+            ..Default::default()
+        };
+        assert!(config.writer_properties(1).is_err());
+
+        // Invalid even without bloom filter columns
+        let config = ParquetWriterConfig {
+            bloom_filter_fpp: Some(1.5),
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_writer_configs() {
+        let mut configs = AHashMap::new();
+        configs.insert("transactions".to_string(), ParquetWriterConfig::default());
+        assert!(validate_writer_configs(&configs, &["transactions", "move_resources"]).is_ok());
+
+        configs.insert("transaction".to_string(), ParquetWriterConfig::default());
+        assert!(validate_writer_configs(&configs, &["transactions", "move_resources"]).is_err());
+
+        let mut configs = AHashMap::new();
+        configs.insert("transactions".to_string(), ParquetWriterConfig {
+            compression: "ZSTD(100)".to_string(),
+            ..Default::default()
+        });
+        assert!(validate_writer_configs(&configs, &["transactions"]).is_err());
+    }
+}
@@ -3,7 +3,7 @@
 
 use crate::{
     gap_detectors::DEFAULT_GAP_DETECTION_BATCH_SIZE, processors::ProcessorConfig,
-    transaction_filter::TransactionFilter, worker::Worker,
+    transaction_filter::TransactionFilter, utils::dead_letter::DeadLetterConfig, worker::Worker,
 };
 use ahash::AHashMap;
 use anyhow::{Context, Result};
@@ -55,6 +55,36 @@ pub struct IndexerGrpcProcessorConfig {
     // String vector for deprecated tables to skip db writes
     #[serde(default)]
     pub deprecated_tables: HashSet<String>,
+    // Re-run the unresolved ranges of the processor_gaps table and exit
+    #[serde(default)]
+    pub replay_gaps: bool,
+    // Skip the transactions that fail to process instead of failing, see `DeadLetterConfig`
+    #[serde(default)]
+    pub dead_letter_config: Option<DeadLetterConfig>,
+    // Seconds between records of the processor status history, 0 disables it
+    #[serde(
+        default = "IndexerGrpcProcessorConfig::default_processor_status_history_interval_secs"
+    )]
+    pub processor_status_history_interval_secs: u64,
+    // Postgres schema to create the tables in, one per network to index several networks in the
+    // same database. Defaults to the search path of the connection string.
+    #[serde(default)]
+    pub db_schema: Option<String>,
+    #[serde(default)]
+    pub chain_id_guard: ChainIdGuard,
+}
+
+/// What the chain id from GRPC is checked against on startup
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChainIdGuard {
+    /// The whole database (or schema) holds a single chain, stored in ledger_infos. The chain of
+    /// the processor is checked as well.
+    #[default]
+    Database,
+    /// Only the chain of the processor is checked, so processors of different networks can share
+    /// a database as long as they don't write the same tables.
+    Processor,
 }
 
 impl IndexerGrpcProcessorConfig {
@@ -76,6 +106,11 @@ impl IndexerGrpcProcessorConfig {
         100_000
     }
 
+    /// Record the processor status history every minute by default
+    pub const fn default_processor_status_history_interval_secs() -> u64 {
+        60
+    }
+
     /// Default timeout for grpc response item in seconds. Defaults to 60 seconds.
     pub const fn default_grpc_response_item_timeout_in_secs() -> u64 {
         60
@@ -103,6 +138,11 @@ impl RunnableConfig for IndexerGrpcProcessorConfig {
             self.transaction_filter.clone(),
             self.grpc_response_item_timeout_in_secs,
             self.deprecated_tables.clone(),
+            self.replay_gaps,
+            self.dead_letter_config.clone(),
+            self.processor_status_history_interval_secs,
+            self.db_schema.clone(),
+            self.chain_id_guard,
         )
         .await
         .context("Failed to build worker")?;
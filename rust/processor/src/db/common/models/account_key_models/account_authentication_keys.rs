@@ -0,0 +1,45 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use super::account_key_utils::KeyRotation;
+use crate::schema::account_authentication_keys;
+use aptos_protos::transaction::v1::Event;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+/// History of the authentication key rotations of each account
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(transaction_version, event_index))]
+#[diesel(table_name = account_authentication_keys)]
+pub struct AccountAuthenticationKey {
+    pub transaction_version: i64,
+    pub event_index: i64,
+    pub account_address: String,
+    pub old_authentication_key: String,
+    pub new_authentication_key: String,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl AccountAuthenticationKey {
+    pub fn from_event(
+        event: &Event,
+        txn_version: i64,
+        event_index: i64,
+        txn_timestamp: chrono::NaiveDateTime,
+    ) -> anyhow::Result<Option<Self>> {
+        Ok(
+            KeyRotation::from_event(event, txn_version)?.map(|rotation| Self {
+                transaction_version: txn_version,
+                event_index,
+                account_address: rotation.account_address,
+                old_authentication_key: rotation.old_authentication_key,
+                new_authentication_key: rotation.new_authentication_key,
+                transaction_timestamp: txn_timestamp,
+            }),
+        )
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::utils::util::standardize_address;
+use anyhow::{Context, Result};
+use aptos_protos::transaction::v1::{Event, WriteResource};
+use serde::{Deserialize, Serialize};
+
+const ACCOUNT_RESOURCE: &str = "0x1::account::Account";
+
+/// Only the fields of `0x1::account::Account` we need
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AccountResource {
+    pub authentication_key: String,
+}
+
+impl AccountResource {
+    pub fn from_write_resource(
+        write_resource: &WriteResource,
+        txn_version: i64,
+    ) -> Result<Option<Self>> {
+        if write_resource.type_str != ACCOUNT_RESOURCE {
+            return Ok(None);
+        }
+        serde_json::from_str(&write_resource.data)
+            .map(Some)
+            .context(format!(
+                "version {} failed! failed to parse type {}, data {:?}",
+                txn_version, write_resource.type_str, write_resource.data
+            ))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct KeyRotationEventV1 {
+    old_authentication_key: String,
+    new_authentication_key: String,
+}
+
+/// Module event replacing the event handle of the account
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct KeyRotationEventV2 {
+    account: String,
+    old_authentication_key: String,
+    new_authentication_key: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyRotation {
+    pub account_address: String,
+    pub old_authentication_key: String,
+    pub new_authentication_key: String,
+}
+
+impl KeyRotation {
+    pub fn from_event(event: &Event, txn_version: i64) -> Result<Option<Self>> {
+        let context = || {
+            format!(
+                "version {} failed! failed to parse type {}, data {:?}",
+                txn_version, event.type_str, event.data
+            )
+        };
+        let rotation = match event.type_str.as_str() {
+            "0x1::account::KeyRotationEvent" => {
+                let inner: KeyRotationEventV1 =
+                    serde_json::from_str(&event.data).with_context(context)?;
+                // The event handle is stored in the rotated account
+                let account_address = event
+                    .key
+                    .as_ref()
+                    .context("event must have a key")?
+                    .account_address
+                    .clone();
+                Self {
+                    account_address: standardize_address(&account_address),
+                    old_authentication_key: standardize_address(&inner.old_authentication_key),
+                    new_authentication_key: standardize_address(&inner.new_authentication_key),
+                }
+            },
+            "0x1::account::KeyRotation" => {
+                let inner: KeyRotationEventV2 =
+                    serde_json::from_str(&event.data).with_context(context)?;
+                Self {
+                    account_address: standardize_address(&inner.account),
+                    old_authentication_key: standardize_address(&inner.old_authentication_key),
+                    new_authentication_key: standardize_address(&inner.new_authentication_key),
+                }
+            },
+            _ => return Ok(None),
+        };
+        Ok(Some(rotation))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_protos::transaction::v1::EventKey;
+
+    #[test]
+    fn test_key_rotation_from_event() {
+        let v1 = Event {
+            key: Some(EventKey {
+                account_address: "0xa11ce".to_string(),
+                creation_number: 1,
+            }),
+            type_str: "0x1::account::KeyRotationEvent".to_string(),
+            data: r#"{"old_authentication_key":"0x0a11ce","new_authentication_key":"0xb0b"}"#
+                .to_string(),
+            ..Event::default()
+        };
+        let v2 = Event {
+            type_str: "0x1::account::KeyRotation".to_string(),
+            data: r#"{"account":"0xa11ce","old_authentication_key":"0x0a11ce","new_authentication_key":"0xb0b"}"#
+                .to_string(),
+            ..Event::default()
+        };
+        let expected = Some(KeyRotation {
+            account_address: standardize_address("0xa11ce"),
+            old_authentication_key: standardize_address("0xa11ce"),
+            new_authentication_key: standardize_address("0xb0b"),
+        });
+        assert_eq!(KeyRotation::from_event(&v1, 1).unwrap(), expected);
+        assert_eq!(KeyRotation::from_event(&v2, 1).unwrap(), expected);
+    }
+}
@@ -0,0 +1,43 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use super::account_key_utils::AccountResource;
+use crate::{schema::current_auth_key_to_address, utils::util::standardize_address};
+use aptos_protos::transaction::v1::WriteResource;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+/// Current authentication key of each account, indexed by key to find the accounts a key
+/// controls, including accounts whose key was rotated
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(account_address))]
+#[diesel(table_name = current_auth_key_to_address)]
+pub struct CurrentAuthKeyToAddress {
+    pub account_address: String,
+    pub authentication_key: String,
+    pub last_transaction_version: i64,
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl CurrentAuthKeyToAddress {
+    pub fn from_write_resource(
+        write_resource: &WriteResource,
+        txn_version: i64,
+        txn_timestamp: chrono::NaiveDateTime,
+    ) -> anyhow::Result<Option<Self>> {
+        Ok(
+            AccountResource::from_write_resource(write_resource, txn_version)?.map(|account| {
+                Self {
+                    account_address: standardize_address(&write_resource.address),
+                    authentication_key: standardize_address(&account.authentication_key),
+                    last_transaction_version: txn_version,
+                    last_transaction_timestamp: txn_timestamp,
+                }
+            }),
+        )
+    }
+}
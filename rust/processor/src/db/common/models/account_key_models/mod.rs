@@ -0,0 +1,6 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod account_authentication_keys;
+pub mod account_key_utils;
+pub mod current_auth_key_to_address;
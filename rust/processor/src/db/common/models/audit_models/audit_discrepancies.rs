@@ -0,0 +1,378 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+
+use crate::{schema::audit_discrepancies, utils::database::DbPoolConnection};
+use bigdecimal::BigDecimal;
+use diesel::{
+    sql_query,
+    sql_types::{BigInt, Nullable, Numeric, Text},
+    QueryableByName,
+};
+use diesel_async::RunQueryDsl;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+/// Replays the activities of the audited range on the balance of the store before the range,
+/// the latest one in fungible_asset_balances. A store without an earlier balance starts empty
+/// if the processor started at genesis, and can't be verified if it started mid-chain, the
+/// balance at its starting version being unknown. Gas is charged to the fee payer if there's
+/// one, and the storage refund is given back to the same account.
+const FUNGIBLE_ASSET_BALANCE_MISMATCHES_QUERY: &str = "
+SELECT b.storage_id, b.owner_address, b.amount, b.last_transaction_version,
+  COALESCE(p.amount, 0) + COALESCE(s.activity_amount, 0) + COALESCE(f.activity_amount, 0)
+    AS activity_amount
+FROM (
+  SELECT storage_id, owner_address, asset_type, amount, last_transaction_version
+  FROM current_fungible_asset_balances
+  WHERE last_transaction_version BETWEEN $1 AND $2
+  ORDER BY last_transaction_version
+  LIMIT $3
+) b
+CROSS JOIN (
+  SELECT MIN(transaction_version) AS starting_version FROM fungible_asset_balances
+) v
+LEFT JOIN LATERAL (
+  SELECT amount
+  FROM fungible_asset_balances
+  WHERE owner_address = b.owner_address AND asset_type = b.asset_type
+    AND storage_id = b.storage_id AND transaction_version < $1
+  ORDER BY transaction_version DESC, write_set_change_index DESC
+  LIMIT 1
+) p ON TRUE
+LEFT JOIN LATERAL (
+  SELECT SUM(CASE
+      WHEN is_gas_fee THEN storage_refund_amount - amount
+      WHEN type IN ('0x1::coin::DepositEvent', '0x1::fungible_asset::DepositEvent', '0x1::fungible_asset::Deposit') THEN amount
+      ELSE -amount
+    END) AS activity_amount
+  FROM fungible_asset_activities
+  WHERE storage_id = b.storage_id
+    AND transaction_version BETWEEN $1 AND b.last_transaction_version
+    AND (
+      (is_transaction_success AND type IN (
+        '0x1::coin::DepositEvent', '0x1::fungible_asset::DepositEvent', '0x1::fungible_asset::Deposit',
+        '0x1::coin::WithdrawEvent', '0x1::fungible_asset::WithdrawEvent', '0x1::fungible_asset::Withdraw'
+      ))
+      OR (is_gas_fee AND gas_fee_payer_address IS NULL)
+    )
+) s ON TRUE
+LEFT JOIN LATERAL (
+  SELECT SUM(storage_refund_amount - amount) AS activity_amount
+  FROM fungible_asset_activities
+  WHERE gas_fee_payer_address = b.owner_address AND is_gas_fee AND asset_type = b.asset_type
+    AND transaction_version BETWEEN $1 AND b.last_transaction_version
+) f ON TRUE
+WHERE (p.amount IS NOT NULL OR v.starting_version = 0)
+  AND b.amount <> COALESCE(p.amount, 0) + COALESCE(s.activity_amount, 0) + COALESCE(f.activity_amount, 0)
+";
+
+/// Non fungible v2 tokens are objects, so their owner must be the owner of the object.
+const TOKEN_OWNER_MISMATCHES_QUERY: &str = "
+SELECT o.token_data_id, o.owner_address, o.last_transaction_version,
+  c.owner_address AS object_owner_address
+FROM (
+  SELECT token_data_id, owner_address, last_transaction_version
+  FROM current_token_ownerships_v2
+  WHERE last_transaction_version BETWEEN $1 AND $2
+    AND token_standard = 'v2'
+    AND storage_id = token_data_id
+    AND amount > 0
+  ORDER BY last_transaction_version
+  LIMIT $3
+) o
+JOIN current_objects c ON c.object_address = o.token_data_id
+WHERE NOT c.is_deleted
+  AND c.owner_address <> o.owner_address
+";
+
+/// Balance of a coin store or of a fungible store (plus its concurrent balance, if it has one)
+/// from the latest resource written at or before the version of the balance. Deleted stores
+/// count as empty. Balances without any resource in move_resources can't be verified.
+const RESOURCE_BALANCE_LATERALS: &str = "
+LEFT JOIN LATERAL (
+  SELECT CASE WHEN is_deleted THEN 0 ELSE (data->'coin'->>'value')::NUMERIC END AS balance
+  FROM move_resources
+  WHERE address = b.owner_address AND module = 'coin' AND name = 'CoinStore'
+    AND type = '0x1::coin::CoinStore<' || b.coin_type || '>'
+    AND transaction_version <= b.coin_version
+  ORDER BY transaction_version DESC, write_set_change_index DESC
+  LIMIT 1
+) coin_store ON b.coin_type IS NOT NULL
+LEFT JOIN LATERAL (
+  SELECT CASE WHEN is_deleted THEN 0 ELSE (data->>'balance')::NUMERIC END AS balance
+  FROM move_resources
+  WHERE address = b.storage_id AND module = 'fungible_asset' AND name = 'FungibleStore'
+    AND transaction_version <= b.fungible_asset_version
+  ORDER BY transaction_version DESC, write_set_change_index DESC
+  LIMIT 1
+) fungible_store ON b.fungible_asset_version IS NOT NULL
+LEFT JOIN LATERAL (
+  SELECT CASE WHEN is_deleted THEN 0 ELSE (data->'balance'->>'value')::NUMERIC END AS balance
+  FROM move_resources
+  WHERE address = b.storage_id AND module = 'fungible_asset' AND name = 'ConcurrentFungibleBalance'
+    AND transaction_version <= b.fungible_asset_version
+  ORDER BY transaction_version DESC, write_set_change_index DESC
+  LIMIT 1
+) concurrent_balance ON b.fungible_asset_version IS NOT NULL
+";
+
+/// Columns of `ResourceBalanceMismatch` from the laterals above
+const RESOURCE_BALANCE_COLUMNS: &str = "
+SELECT b.storage_id, b.owner_address, b.amount, b.last_transaction_version,
+  CASE WHEN b.coin_type IS NOT NULL THEN coin_store.balance
+    ELSE fungible_store.balance + COALESCE(concurrent_balance.balance, 0)
+  END AS resource_amount
+";
+
+/// current_fungible_asset_balances, the asset type of coins is the coin type
+const FUNGIBLE_ASSET_BALANCES_SAMPLE: &str = "
+FROM (
+  SELECT storage_id, owner_address, amount, last_transaction_version,
+    CASE WHEN token_standard = 'v1' THEN asset_type END AS coin_type,
+    CASE WHEN token_standard = 'v1' THEN last_transaction_version END AS coin_version,
+    CASE WHEN token_standard = 'v2' THEN last_transaction_version END AS fungible_asset_version
+  FROM current_fungible_asset_balances
+  WHERE last_transaction_version BETWEEN $1 AND $2
+  ORDER BY last_transaction_version
+  LIMIT $3
+) b
+";
+
+/// current_unified_fungible_asset_balances_to_be_renamed, the coin and fungible store parts of
+/// a balance are verified separately, each at the version it was last updated
+const UNIFIED_FUNGIBLE_ASSET_BALANCES_SAMPLE: &str = "
+FROM (
+  (
+    SELECT storage_id, owner_address, amount_v1 AS amount,
+      last_transaction_version_v1 AS last_transaction_version,
+      asset_type_v1 AS coin_type,
+      last_transaction_version_v1 AS coin_version,
+      NULL::BIGINT AS fungible_asset_version
+    FROM current_unified_fungible_asset_balances_to_be_renamed
+    WHERE last_transaction_version_v1 BETWEEN $1 AND $2 AND asset_type_v1 IS NOT NULL
+    ORDER BY last_transaction_version_v1
+    LIMIT $3
+  )
+  UNION ALL
+  (
+    SELECT storage_id, owner_address, amount_v2 AS amount,
+      last_transaction_version_v2 AS last_transaction_version,
+      NULL AS coin_type,
+      NULL::BIGINT AS coin_version,
+      last_transaction_version_v2 AS fungible_asset_version
+    FROM current_unified_fungible_asset_balances_to_be_renamed
+    WHERE last_transaction_version_v2 BETWEEN $1 AND $2 AND asset_type_v2 IS NOT NULL
+    ORDER BY last_transaction_version_v2
+    LIMIT $3
+  )
+) b
+";
+
+#[derive(Clone, Debug, Deserialize, FieldCount, Insertable, Serialize)]
+#[diesel(table_name = audit_discrepancies)]
+pub struct AuditDiscrepancy {
+    pub check_name: String,
+    // Storage id of a balance, token data id of a token, etc.
+    pub entity_id: String,
+    pub transaction_version: i64,
+    pub address: String,
+    pub expected_value: String,
+    pub actual_value: String,
+    pub audit_start_version: i64,
+    pub audit_end_version: i64,
+}
+
+#[derive(Debug, QueryableByName)]
+pub struct FungibleAssetBalanceMismatch {
+    #[diesel(sql_type = Text)]
+    pub storage_id: String,
+    #[diesel(sql_type = Text)]
+    pub owner_address: String,
+    #[diesel(sql_type = Numeric)]
+    pub amount: BigDecimal,
+    #[diesel(sql_type = BigInt)]
+    pub last_transaction_version: i64,
+    #[diesel(sql_type = Numeric)]
+    pub activity_amount: BigDecimal,
+}
+
+#[derive(Debug, QueryableByName)]
+pub struct TokenOwnerMismatch {
+    #[diesel(sql_type = Text)]
+    pub token_data_id: String,
+    #[diesel(sql_type = Text)]
+    pub owner_address: String,
+    #[diesel(sql_type = BigInt)]
+    pub last_transaction_version: i64,
+    #[diesel(sql_type = Text)]
+    pub object_owner_address: String,
+}
+
+/// A balance that doesn't match the coin or fungible store resources
+#[derive(Debug, QueryableByName)]
+pub struct ResourceBalanceMismatch {
+    #[diesel(sql_type = Text)]
+    pub storage_id: String,
+    #[diesel(sql_type = Text)]
+    pub owner_address: String,
+    #[diesel(sql_type = Nullable<Numeric>)]
+    pub amount: Option<BigDecimal>,
+    #[diesel(sql_type = BigInt)]
+    pub last_transaction_version: i64,
+    #[diesel(sql_type = Numeric)]
+    pub resource_amount: BigDecimal,
+}
+
+impl FungibleAssetBalanceMismatch {
+    /// Checks up to `limit` balances last updated between the versions against their balance
+    /// before the versions plus the activities of their store since.
+    pub async fn get_by_version_range(
+        start_version: i64,
+        end_version: i64,
+        limit: i64,
+        conn: &mut DbPoolConnection<'_>,
+    ) -> diesel::QueryResult<Vec<Self>> {
+        sql_query(FUNGIBLE_ASSET_BALANCE_MISMATCHES_QUERY)
+            .bind::<BigInt, _>(start_version)
+            .bind::<BigInt, _>(end_version)
+            .bind::<BigInt, _>(limit)
+            .get_results(conn)
+            .await
+    }
+
+    pub fn to_discrepancy(
+        &self,
+        check_name: &str,
+        audit_start_version: i64,
+        audit_end_version: i64,
+    ) -> AuditDiscrepancy {
+        AuditDiscrepancy {
+            check_name: check_name.to_string(),
+            entity_id: self.storage_id.clone(),
+            transaction_version: self.last_transaction_version,
+            address: self.owner_address.clone(),
+            expected_value: self.activity_amount.to_string(),
+            actual_value: self.amount.to_string(),
+            audit_start_version,
+            audit_end_version,
+        }
+    }
+}
+
+impl TokenOwnerMismatch {
+    /// Checks up to `limit` token ownerships last updated between the versions against the
+    /// owner of the token object.
+    pub async fn get_by_version_range(
+        start_version: i64,
+        end_version: i64,
+        limit: i64,
+        conn: &mut DbPoolConnection<'_>,
+    ) -> diesel::QueryResult<Vec<Self>> {
+        sql_query(TOKEN_OWNER_MISMATCHES_QUERY)
+            .bind::<BigInt, _>(start_version)
+            .bind::<BigInt, _>(end_version)
+            .bind::<BigInt, _>(limit)
+            .get_results(conn)
+            .await
+    }
+
+    pub fn to_discrepancy(
+        &self,
+        check_name: &str,
+        audit_start_version: i64,
+        audit_end_version: i64,
+    ) -> AuditDiscrepancy {
+        AuditDiscrepancy {
+            check_name: check_name.to_string(),
+            entity_id: self.token_data_id.clone(),
+            transaction_version: self.last_transaction_version,
+            address: self.owner_address.clone(),
+            expected_value: self.object_owner_address.clone(),
+            actual_value: self.owner_address.clone(),
+            audit_start_version,
+            audit_end_version,
+        }
+    }
+}
+
+impl ResourceBalanceMismatch {
+    /// Verifies up to `limit` balances of current_fungible_asset_balances last updated between
+    /// the versions against move_resources.
+    pub async fn get_fungible_asset_balances_by_version_range(
+        start_version: i64,
+        end_version: i64,
+        limit: i64,
+        conn: &mut DbPoolConnection<'_>,
+    ) -> diesel::QueryResult<Vec<Self>> {
+        Self::get_by_version_range(
+            FUNGIBLE_ASSET_BALANCES_SAMPLE,
+            start_version,
+            end_version,
+            limit,
+            conn,
+        )
+        .await
+    }
+
+    /// Same as above for the coin and fungible store parts of the unified balances.
+    pub async fn get_unified_fungible_asset_balances_by_version_range(
+        start_version: i64,
+        end_version: i64,
+        limit: i64,
+        conn: &mut DbPoolConnection<'_>,
+    ) -> diesel::QueryResult<Vec<Self>> {
+        Self::get_by_version_range(
+            UNIFIED_FUNGIBLE_ASSET_BALANCES_SAMPLE,
+            start_version,
+            end_version,
+            limit,
+            conn,
+        )
+        .await
+    }
+
+    async fn get_by_version_range(
+        balances_sample: &str,
+        start_version: i64,
+        end_version: i64,
+        limit: i64,
+        conn: &mut DbPoolConnection<'_>,
+    ) -> diesel::QueryResult<Vec<Self>> {
+        // Only keep the balances that don't match their resources
+        sql_query(format!(
+            "SELECT * FROM ({}{}{}) m
+            WHERE m.resource_amount IS NOT NULL AND m.resource_amount IS DISTINCT FROM m.amount",
+            RESOURCE_BALANCE_COLUMNS, balances_sample, RESOURCE_BALANCE_LATERALS,
+        ))
+        .bind::<BigInt, _>(start_version)
+        .bind::<BigInt, _>(end_version)
+        .bind::<BigInt, _>(limit)
+        .get_results(conn)
+        .await
+    }
+
+    pub fn to_discrepancy(
+        &self,
+        check_name: &str,
+        audit_start_version: i64,
+        audit_end_version: i64,
+    ) -> AuditDiscrepancy {
+        AuditDiscrepancy {
+            check_name: check_name.to_string(),
+            entity_id: self.storage_id.clone(),
+            transaction_version: self.last_transaction_version,
+            address: self.owner_address.clone(),
+            expected_value: self.resource_amount.to_string(),
+            actual_value: self
+                .amount
+                .as_ref()
+                .map(|amount| amount.to_string())
+                .unwrap_or_default(),
+            audit_start_version,
+            audit_end_version,
+        }
+    }
+}
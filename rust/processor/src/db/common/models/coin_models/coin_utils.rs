@@ -190,6 +190,14 @@ impl CoinInfoType {
         }
     }
 
+    /// From the coin type itself, e.g. a type argument of another resource or event
+    pub fn from_coin_type(coin_type: &str) -> Self {
+        Self {
+            coin_type: coin_type.to_string(),
+            creator_address: coin_type.split("::").next().unwrap_or_default().to_string(),
+        }
+    }
+
     pub fn get_creator_address(&self) -> String {
         standardize_address(&self.creator_address)
     }
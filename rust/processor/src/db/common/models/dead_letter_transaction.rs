@@ -0,0 +1,30 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(clippy::extra_unused_lifetimes)]
+
+use crate::{schema::dead_letter_transactions, utils::database::DbPoolConnection};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+#[derive(Clone, Debug, Insertable)]
+#[diesel(table_name = dead_letter_transactions)]
+/// A transaction a processor skipped because it failed to process
+pub struct DeadLetterTransaction {
+    pub processor: String,
+    pub transaction_version: i64,
+    pub error: String,
+}
+
+impl DeadLetterTransaction {
+    pub async fn count_by_processor(
+        processor_name: &str,
+        conn: &mut DbPoolConnection<'_>,
+    ) -> diesel::QueryResult<i64> {
+        dead_letter_transactions::table
+            .filter(dead_letter_transactions::processor.eq(processor_name))
+            .count()
+            .get_result(conn)
+            .await
+    }
+}
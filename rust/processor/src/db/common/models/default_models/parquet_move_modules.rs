@@ -34,6 +34,7 @@ pub struct MoveModule {
 }
 
 impl NamedTable for MoveModule {
+    const SCHEMA_VERSION: u32 = 1;
     const TABLE_NAME: &'static str = "move_modules";
 }
 
@@ -36,6 +36,7 @@ pub struct MoveResource {
 }
 
 impl NamedTable for MoveResource {
+    const SCHEMA_VERSION: u32 = 1;
     const TABLE_NAME: &'static str = "move_resources";
 }
 
@@ -30,6 +30,7 @@ pub struct TableItem {
 }
 
 impl NamedTable for TableItem {
+    const SCHEMA_VERSION: u32 = 1;
     const TABLE_NAME: &'static str = "table_items";
 }
 
@@ -66,6 +67,7 @@ pub struct TableMetadata {
 }
 
 impl NamedTable for TableMetadata {
+    const SCHEMA_VERSION: u32 = 1;
     const TABLE_NAME: &'static str = "table_metadatas";
 }
 
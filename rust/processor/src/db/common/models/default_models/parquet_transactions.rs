@@ -52,6 +52,7 @@ pub struct Transaction {
 }
 
 impl NamedTable for Transaction {
+    const SCHEMA_VERSION: u32 = 1;
     const TABLE_NAME: &'static str = "transactions";
 }
 
@@ -37,6 +37,7 @@ pub struct WriteSetChange {
 }
 
 impl NamedTable for WriteSetChange {
+    const SCHEMA_VERSION: u32 = 1;
     const TABLE_NAME: &'static str = "write_set_changes";
 }
 
@@ -0,0 +1,34 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use crate::schema::dex_liquidity_events;
+use bigdecimal::BigDecimal;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(transaction_version, event_index))]
+#[diesel(table_name = dex_liquidity_events)]
+pub struct DexLiquidityEvent {
+    pub transaction_version: i64,
+    pub event_index: i64,
+    pub dex: String,
+    pub contract_address: String,
+    pub pool_id: String,
+    // sha256 of pool_id, which can be too long to be indexed
+    pub pool_id_hash: String,
+    pub sender: Option<String>,
+    // add_liquidity or remove_liquidity
+    pub event_type: String,
+    pub asset_x: String,
+    pub amount_x: BigDecimal,
+    pub asset_y: String,
+    pub amount_y: BigDecimal,
+    pub lp_token_amount: Option<BigDecimal>,
+    pub entry_function_id_str: Option<String>,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+}
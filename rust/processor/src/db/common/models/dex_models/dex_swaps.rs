@@ -0,0 +1,31 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use crate::schema::dex_swaps;
+use bigdecimal::BigDecimal;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(transaction_version, event_index))]
+#[diesel(table_name = dex_swaps)]
+pub struct DexSwap {
+    pub transaction_version: i64,
+    pub event_index: i64,
+    pub dex: String,
+    pub contract_address: String,
+    pub pool_id: String,
+    // sha256 of pool_id, which can be too long to be indexed
+    pub pool_id_hash: String,
+    pub sender: Option<String>,
+    pub asset_in: String,
+    pub amount_in: BigDecimal,
+    pub asset_out: String,
+    pub amount_out: BigDecimal,
+    pub entry_function_id_str: Option<String>,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+}
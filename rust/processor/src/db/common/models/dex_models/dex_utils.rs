@@ -0,0 +1,254 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    dex_liquidity_events::DexLiquidityEvent, dex_swaps::DexSwap,
+    liquidswap_adapter::LiquidswapV0Adapter,
+};
+use crate::{
+    db::common::models::coin_models::coin_utils::CoinInfoType,
+    utils::util::{
+        get_entry_function_from_user_request, hash_str, parse_timestamp, standardize_address,
+    },
+};
+use anyhow::Result;
+use aptos_protos::transaction::v1::{transaction::TxnData, Transaction};
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DexAdapterType {
+    LiquidswapV0,
+}
+
+impl DexAdapterType {
+    pub fn adapter(&self) -> &'static dyn DexAdapter {
+        match self {
+            DexAdapterType::LiquidswapV0 => &LiquidswapV0Adapter,
+        }
+    }
+}
+
+/// A dex contract to index and the adapter reading its events
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DexConfig {
+    // Name written to the dex column, e.g. "liquidswap"
+    pub name: String,
+    pub adapter: DexAdapterType,
+    pub contract_address: String,
+}
+
+impl DexConfig {
+    pub fn get_contract_address(&self) -> String {
+        standardize_address(&self.contract_address)
+    }
+}
+
+/// Maps the events of a dex to the normalized swaps and liquidity changes. Assets are given as the
+/// coin type for coins and the metadata address for fungible assets, see `resolve_asset_type`.
+pub trait DexAdapter: Send + Sync {
+    /// Returns None for events that aren't a swap or a liquidity change. The event type is
+    /// declared by `contract_address`.
+    fn parse_event(
+        &self,
+        contract_address: &str,
+        event_type: &str,
+        data: &str,
+        txn_version: i64,
+    ) -> Result<Option<DexEvent>>;
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NormalizedSwap {
+    pub pool_id: String,
+    pub asset_in: String,
+    pub amount_in: BigDecimal,
+    pub asset_out: String,
+    pub amount_out: BigDecimal,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NormalizedLiquidityChange {
+    pub pool_id: String,
+    pub asset_x: String,
+    pub amount_x: BigDecimal,
+    pub asset_y: String,
+    pub amount_y: BigDecimal,
+    pub lp_token_amount: Option<BigDecimal>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DexEvent {
+    Swap(NormalizedSwap),
+    AddLiquidity(NormalizedLiquidityChange),
+    RemoveLiquidity(NormalizedLiquidityChange),
+}
+
+/// `0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>` -> (`0x1::coin::CoinStore`,
+/// [`0x1::aptos_coin::AptosCoin`]). Only the top level type arguments are split.
+pub fn split_type_args(type_str: &str) -> (&str, Vec<&str>) {
+    let Some((outer, rest)) = type_str.split_once('<') else {
+        return (type_str, vec![]);
+    };
+    let inner = rest.strip_suffix('>').unwrap_or(rest);
+    let mut args = vec![];
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in inner.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            ',' if depth == 0 => {
+                args.push(inner[start..i].trim());
+                start = i + 1;
+            },
+            _ => {},
+        }
+    }
+    args.push(inner[start..].trim());
+    (outer, args)
+}
+
+/// Resolves an asset to the asset_type of fungible_asset_metadata the way the fungible asset
+/// processor does, so swaps can be joined with their symbol and decimals: through `CoinInfoType`
+/// for coins, and the standardized metadata address for fungible assets. Returns None for coin
+/// types too long to be in fungible_asset_metadata.
+pub fn resolve_asset_type(asset: &str) -> Option<String> {
+    if asset.contains("::") {
+        CoinInfoType::from_coin_type(asset).get_coin_type_below_max()
+    } else {
+        Some(standardize_address(asset))
+    }
+}
+
+/// Swaps and liquidity changes of the configured dexes in the transaction
+pub fn parse_transaction(
+    txn: &Transaction,
+    dexes: &[DexConfig],
+) -> Result<(Vec<DexSwap>, Vec<DexLiquidityEvent>)> {
+    let (mut swaps, mut liquidity_events) = (vec![], vec![]);
+    let Some(TxnData::User(user_txn)) = txn.txn_data.as_ref() else {
+        return Ok((swaps, liquidity_events));
+    };
+    let txn_version = txn.version as i64;
+    let txn_timestamp = parse_timestamp(txn.timestamp.as_ref().unwrap(), txn_version);
+    let user_request = user_txn.request.as_ref();
+    let sender = user_request.map(|request| standardize_address(&request.sender));
+    let entry_function_id_str = user_request.and_then(get_entry_function_from_user_request);
+
+    for (index, event) in user_txn.events.iter().enumerate() {
+        let event_address = event.type_str.split("::").next().unwrap_or_default();
+        let Some(dex) = dexes
+            .iter()
+            .find(|dex| standardize_address(event_address) == dex.get_contract_address())
+        else {
+            continue;
+        };
+        let Some(mut dex_event) = dex.adapter.adapter().parse_event(
+            &dex.get_contract_address(),
+            &event.type_str,
+            &event.data,
+            txn_version,
+        )?
+        else {
+            continue;
+        };
+        let assets = match &mut dex_event {
+            DexEvent::Swap(swap) => [&mut swap.asset_in, &mut swap.asset_out],
+            DexEvent::AddLiquidity(change) | DexEvent::RemoveLiquidity(change) => {
+                [&mut change.asset_x, &mut change.asset_y]
+            },
+        };
+        let mut unresolved = false;
+        for asset in assets {
+            match resolve_asset_type(asset) {
+                Some(asset_type) => *asset = asset_type,
+                None => unresolved = true,
+            }
+        }
+        if unresolved {
+            tracing::warn!(
+                transaction_version = txn_version,
+                event_index = index,
+                "Skipping dex event with an asset that isn't in fungible_asset_metadata"
+            );
+            continue;
+        }
+
+        let (event_type, change) = match dex_event {
+            DexEvent::Swap(swap) => {
+                swaps.push(DexSwap {
+                    transaction_version: txn_version,
+                    event_index: index as i64,
+                    dex: dex.name.clone(),
+                    contract_address: dex.get_contract_address(),
+                    pool_id_hash: hash_str(&swap.pool_id),
+                    pool_id: swap.pool_id,
+                    sender: sender.clone(),
+                    asset_in: swap.asset_in,
+                    amount_in: swap.amount_in,
+                    asset_out: swap.asset_out,
+                    amount_out: swap.amount_out,
+                    entry_function_id_str: entry_function_id_str.clone(),
+                    transaction_timestamp: txn_timestamp,
+                });
+                continue;
+            },
+            DexEvent::AddLiquidity(change) => ("add_liquidity", change),
+            DexEvent::RemoveLiquidity(change) => ("remove_liquidity", change),
+        };
+        liquidity_events.push(DexLiquidityEvent {
+            transaction_version: txn_version,
+            event_index: index as i64,
+            dex: dex.name.clone(),
+            contract_address: dex.get_contract_address(),
+            pool_id_hash: hash_str(&change.pool_id),
+            pool_id: change.pool_id,
+            sender: sender.clone(),
+            event_type: event_type.to_string(),
+            asset_x: change.asset_x,
+            amount_x: change.amount_x,
+            asset_y: change.asset_y,
+            amount_y: change.amount_y,
+            lp_token_amount: change.lp_token_amount,
+            entry_function_id_str: entry_function_id_str.clone(),
+            transaction_timestamp: txn_timestamp,
+        });
+    }
+    Ok((swaps, liquidity_events))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_asset_type() {
+        assert_eq!(
+            resolve_asset_type("0x1::aptos_coin::AptosCoin"),
+            Some("0x1::aptos_coin::AptosCoin".to_string())
+        );
+        assert_eq!(resolve_asset_type("0xa"), Some(standardize_address("0xa")));
+        let long_coin_type = format!("0x1::coin::Wrapped<{}>", "0x1::m::T".repeat(200));
+        assert_eq!(resolve_asset_type(&long_coin_type), None);
+    }
+
+    #[test]
+    fn test_split_type_args() {
+        assert_eq!(
+            split_type_args("0x1::aptos_coin::AptosCoin"),
+            ("0x1::aptos_coin::AptosCoin", vec![])
+        );
+        assert_eq!(
+            split_type_args(
+                "0xa::pool::SwapEvent<0x1::aptos_coin::AptosCoin, 0xb::lp::LP<0xc::x::X, 0xc::y::Y>, 0xa::curves::Stable>"
+            ),
+            ("0xa::pool::SwapEvent", vec![
+                "0x1::aptos_coin::AptosCoin",
+                "0xb::lp::LP<0xc::x::X, 0xc::y::Y>",
+                "0xa::curves::Stable",
+            ])
+        );
+    }
+}
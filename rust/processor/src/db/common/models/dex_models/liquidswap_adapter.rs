@@ -0,0 +1,279 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reference adapter for the v0 pools of Liquidswap, whose events are generic over the coin
+//! pair and the curve, e.g. `liquidity_pool::SwapEvent<X, Y, Curve>`
+
+use super::dex_utils::{
+    split_type_args, DexAdapter, DexEvent, NormalizedLiquidityChange, NormalizedSwap,
+};
+use crate::utils::util::{deserialize_from_string, standardize_address};
+use anyhow::{Context, Result};
+use bigdecimal::{BigDecimal, Zero};
+use serde::{Deserialize, Serialize};
+
+const MODULE: &str = "liquidity_pool";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct SwapEvent {
+    #[serde(deserialize_with = "deserialize_from_string")]
+    x_in: BigDecimal,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    x_out: BigDecimal,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    y_in: BigDecimal,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    y_out: BigDecimal,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct LiquidityAddedEvent {
+    #[serde(deserialize_with = "deserialize_from_string")]
+    added_x_val: BigDecimal,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    added_y_val: BigDecimal,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    lp_tokens_received: BigDecimal,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct LiquidityRemovedEvent {
+    #[serde(deserialize_with = "deserialize_from_string")]
+    returned_x_val: BigDecimal,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    returned_y_val: BigDecimal,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    lp_tokens_burned: BigDecimal,
+}
+
+pub struct LiquidswapV0Adapter;
+
+impl DexAdapter for LiquidswapV0Adapter {
+    fn parse_event(
+        &self,
+        contract_address: &str,
+        event_type: &str,
+        data: &str,
+        txn_version: i64,
+    ) -> Result<Option<DexEvent>> {
+        let (outer_type, type_args) = split_type_args(event_type);
+        let [address, module, name] = outer_type.split("::").collect::<Vec<_>>()[..] else {
+            return Ok(None);
+        };
+        let [x, y, curve] = type_args[..] else {
+            return Ok(None);
+        };
+        if standardize_address(address) != contract_address || module != MODULE {
+            return Ok(None);
+        }
+        // The pool is the resource holding the reserves of the pair for the curve
+        let pool_id = format!(
+            "{}::{}::LiquidityPool<{}, {}, {}>",
+            address, MODULE, x, y, curve
+        );
+        let context = || {
+            format!(
+                "version {} failed! failed to parse type {}, data {:?}",
+                txn_version, event_type, data
+            )
+        };
+
+        let dex_event = match name {
+            "SwapEvent" => {
+                let swap: SwapEvent = serde_json::from_str(data).with_context(context)?;
+                // A swap only goes one way, the fee stays in the pool
+                Some(DexEvent::Swap(
+                    if !swap.x_in.is_zero() {
+                        NormalizedSwap {
+                            pool_id,
+                            asset_in: x.to_string(),
+                            amount_in: swap.x_in,
+                            asset_out: y.to_string(),
+                            amount_out: swap.y_out,
+                        }
+                    } else {
+                        NormalizedSwap {
+                            pool_id,
+                            asset_in: y.to_string(),
+                            amount_in: swap.y_in,
+                            asset_out: x.to_string(),
+                            amount_out: swap.x_out,
+                        }
+                    },
+                ))
+            },
+            "LiquidityAddedEvent" => {
+                let added: LiquidityAddedEvent =
+                    serde_json::from_str(data).with_context(context)?;
+                Some(DexEvent::AddLiquidity(NormalizedLiquidityChange {
+                    pool_id,
+                    asset_x: x.to_string(),
+                    amount_x: added.added_x_val,
+                    asset_y: y.to_string(),
+                    amount_y: added.added_y_val,
+                    lp_token_amount: Some(added.lp_tokens_received),
+                }))
+            },
+            "LiquidityRemovedEvent" => {
+                let removed: LiquidityRemovedEvent =
+                    serde_json::from_str(data).with_context(context)?;
+                Some(DexEvent::RemoveLiquidity(NormalizedLiquidityChange {
+                    pool_id,
+                    asset_x: x.to_string(),
+                    amount_x: removed.returned_x_val,
+                    asset_y: y.to_string(),
+                    amount_y: removed.returned_y_val,
+                    lp_token_amount: Some(removed.lp_tokens_burned),
+                }))
+            },
+            _ => None,
+        };
+        Ok(dex_event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        db::common::models::dex_models::dex_utils::{parse_transaction, DexAdapterType, DexConfig},
+        utils::util::hash_str,
+    };
+    use aptos_protos::{
+        transaction::v1::{
+            transaction::{TransactionType, TxnData},
+            transaction_payload::Payload,
+            EntryFunctionPayload, Event, Transaction, TransactionPayload, UserTransaction,
+            UserTransactionRequest,
+        },
+        util::timestamp::Timestamp,
+    };
+
+    const LIQUIDSWAP: &str = "0x190d44266241744264b964a37b8f09863167a12d3e70cda39376cfb4e3561e12";
+    const USDC: &str =
+        "0xf22bede237a07e121b56d91a491eb7bcdfd1f5907926a9e58338f964a01b17fa::asset::USDC";
+
+    /// Swap of 1 APT to USDC through the router followed by a liquidity deposit, with an
+    /// unrelated coin event and a pool event that isn't a swap or a liquidity change
+    fn fixture_transaction() -> Transaction {
+        let pool_type_args = format!(
+            "<0x1::aptos_coin::AptosCoin, {}, {}::curves::Uncorrelated>",
+            USDC, LIQUIDSWAP
+        );
+        let event = |type_str: String, data: &str| Event {
+            type_str,
+            data: data.to_string(),
+            ..Event::default()
+        };
+        Transaction {
+            version: 123_456_789,
+            timestamp: Some(Timestamp {
+                seconds: 1_700_000_000,
+                nanos: 0,
+            }),
+            r#type: TransactionType::User as i32,
+            txn_data: Some(TxnData::User(UserTransaction {
+                request: Some(UserTransactionRequest {
+                    sender: "0xa11ce".to_string(),
+                    payload: Some(TransactionPayload {
+                        payload: Some(Payload::EntryFunctionPayload(EntryFunctionPayload {
+                            entry_function_id_str: format!("{}::scripts_v2::swap", LIQUIDSWAP),
+                            ..EntryFunctionPayload::default()
+                        })),
+                        ..TransactionPayload::default()
+                    }),
+                    ..UserTransactionRequest::default()
+                }),
+                events: vec![
+                    event(
+                        "0x1::coin::WithdrawEvent".to_string(),
+                        r#"{"amount":"100000000"}"#,
+                    ),
+                    event(
+                        format!(
+                            "{}::liquidity_pool::SwapEvent{}",
+                            LIQUIDSWAP, pool_type_args
+                        ),
+                        r#"{"x_in":"100000000","x_out":"0","y_in":"0","y_out":"7012345"}"#,
+                    ),
+                    event(
+                        format!(
+                            "{}::liquidity_pool::LiquidityAddedEvent{}",
+                            LIQUIDSWAP, pool_type_args
+                        ),
+                        r#"{"added_x_val":"50000000","added_y_val":"3500000","lp_tokens_received":"4183300"}"#,
+                    ),
+                    event(
+                        format!(
+                            "{}::liquidity_pool::OracleUpdatedEvent{}",
+                            LIQUIDSWAP, pool_type_args
+                        ),
+                        r#"{"last_price_x_cumulative":"1","last_price_y_cumulative":"2"}"#,
+                    ),
+                ],
+            })),
+            ..Transaction::default()
+        }
+    }
+
+    #[test]
+    fn test_liquidswap_fixture_transaction() {
+        let dexes = vec![DexConfig {
+            name: "liquidswap".to_string(),
+            adapter: DexAdapterType::LiquidswapV0,
+            contract_address: LIQUIDSWAP.to_string(),
+        }];
+        let (swaps, liquidity_events) = parse_transaction(&fixture_transaction(), &dexes).unwrap();
+        let pool_id = format!(
+            "{}::liquidity_pool::LiquidityPool<0x1::aptos_coin::AptosCoin, {}, {}::curves::Uncorrelated>",
+            LIQUIDSWAP, USDC, LIQUIDSWAP
+        );
+
+        assert_eq!(swaps.len(), 1);
+        let swap = &swaps[0];
+        assert_eq!(swap.event_index, 1);
+        assert_eq!(swap.dex, "liquidswap");
+        assert_eq!(swap.pool_id, pool_id);
+        assert_eq!(swap.pool_id_hash, hash_str(&pool_id));
+        assert_eq!(swap.sender, Some(standardize_address("0xa11ce")));
+        assert_eq!(swap.asset_in, "0x1::aptos_coin::AptosCoin");
+        assert_eq!(swap.amount_in, BigDecimal::from(100_000_000));
+        assert_eq!(swap.asset_out, USDC);
+        assert_eq!(swap.amount_out, BigDecimal::from(7_012_345));
+        assert_eq!(
+            swap.entry_function_id_str,
+            Some(format!("{}::scripts_v2::swap", LIQUIDSWAP))
+        );
+
+        assert_eq!(liquidity_events.len(), 1);
+        let added = &liquidity_events[0];
+        assert_eq!(added.event_index, 2);
+        assert_eq!(added.event_type, "add_liquidity");
+        assert_eq!(added.pool_id, pool_id);
+        assert_eq!(added.amount_x, BigDecimal::from(50_000_000));
+        assert_eq!(added.amount_y, BigDecimal::from(3_500_000));
+        assert_eq!(added.lp_token_amount, Some(BigDecimal::from(4_183_300)));
+    }
+
+    #[test]
+    fn test_swap_from_y_to_x() {
+        let event = LiquidswapV0Adapter
+            .parse_event(
+                &standardize_address(LIQUIDSWAP),
+                &format!(
+                    "{}::liquidity_pool::SwapEvent<0x1::aptos_coin::AptosCoin, {}, {}::curves::Stable>",
+                    LIQUIDSWAP, USDC, LIQUIDSWAP
+                ),
+                r#"{"x_in":"0","x_out":"99","y_in":"7","y_out":"0"}"#,
+                1,
+            )
+            .unwrap();
+        let Some(DexEvent::Swap(swap)) = event else {
+            panic!("Expected a swap, got {:?}", event);
+        };
+        assert_eq!(swap.asset_in, USDC);
+        assert_eq!(swap.amount_in, BigDecimal::from(7));
+        assert_eq!(swap.asset_out, "0x1::aptos_coin::AptosCoin");
+        assert_eq!(swap.amount_out, BigDecimal::from(99));
+    }
+}
@@ -0,0 +1,7 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod dex_liquidity_events;
+pub mod dex_swaps;
+pub mod dex_utils;
+pub mod liquidswap_adapter;
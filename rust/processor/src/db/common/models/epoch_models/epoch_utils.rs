@@ -0,0 +1,145 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::utils::util::deserialize_from_string;
+use anyhow::{Context, Result};
+use aptos_protos::transaction::v1::{Event, WriteResource};
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+
+const VALIDATOR_SET_RESOURCE: &str = "0x1::stake::ValidatorSet";
+const VALIDATOR_PERFORMANCE_RESOURCE: &str = "0x1::stake::ValidatorPerformance";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ValidatorConfig {
+    pub consensus_pubkey: String,
+    pub network_addresses: String,
+    pub fullnode_addresses: String,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub validator_index: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ValidatorInfo {
+    pub addr: String,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub voting_power: BigDecimal,
+    pub config: ValidatorConfig,
+}
+
+/// `0x1::stake::ValidatorSet`. At the start of an epoch the validators joining and leaving have
+/// been applied, so the active validators are the validator set of the epoch.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ValidatorSetResource {
+    pub active_validators: Vec<ValidatorInfo>,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub total_voting_power: BigDecimal,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct IndividualValidatorPerformance {
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub successful_proposals: i64,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub failed_proposals: i64,
+}
+
+/// `0x1::stake::ValidatorPerformance`, indexed by validator index. It's updated by every block and
+/// reset for the new validator set on reconfiguration.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ValidatorPerformanceResource {
+    pub validators: Vec<IndividualValidatorPerformance>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum EpochResource {
+    ValidatorSet(ValidatorSetResource),
+    ValidatorPerformance(ValidatorPerformanceResource),
+}
+
+impl EpochResource {
+    pub fn from_write_resource(
+        write_resource: &WriteResource,
+        txn_version: i64,
+    ) -> Result<Option<Self>> {
+        let data = write_resource.data.as_str();
+        match write_resource.type_str.as_str() {
+            VALIDATOR_SET_RESOURCE => {
+                serde_json::from_str(data).map(|inner| Some(Self::ValidatorSet(inner)))
+            },
+            VALIDATOR_PERFORMANCE_RESOURCE => {
+                serde_json::from_str(data).map(|inner| Some(Self::ValidatorPerformance(inner)))
+            },
+            _ => Ok(None),
+        }
+        .context(format!(
+            "version {} failed! failed to parse type {}, data {:?}",
+            txn_version, write_resource.type_str, data
+        ))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NewEpochEvent {
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub epoch: i64,
+}
+
+impl NewEpochEvent {
+    /// The epoch started by the transaction, which is the last one of the previous epoch
+    pub fn from_event(event: &Event, txn_version: i64) -> Result<Option<Self>> {
+        match event.type_str.as_str() {
+            // Event handle and module event
+            "0x1::reconfiguration::NewEpochEvent" | "0x1::reconfiguration::NewEpoch" => {
+                serde_json::from_str(&event.data).map(Some)
+            },
+            _ => Ok(None),
+        }
+        .context(format!(
+            "version {} failed! failed to parse type {}, data {:?}",
+            txn_version, event.type_str, event.data
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validator_set_from_write_resource() {
+        let write_resource = WriteResource {
+            type_str: VALIDATOR_SET_RESOURCE.to_string(),
+            data: r#"{
+                "active_validators": [{
+                    "addr": "0xa11ce",
+                    "config": {
+                        "consensus_pubkey": "0xb0b",
+                        "fullnode_addresses": "0x00",
+                        "network_addresses": "0x01",
+                        "validator_index": "3"
+                    },
+                    "voting_power": "1000000"
+                }],
+                "consensus_scheme": 0,
+                "pending_active": [],
+                "pending_inactive": [],
+                "total_joining_power": "0",
+                "total_voting_power": "1000000"
+            }"#
+            .to_string(),
+            ..WriteResource::default()
+        };
+        let Some(EpochResource::ValidatorSet(validator_set)) =
+            EpochResource::from_write_resource(&write_resource, 1).unwrap()
+        else {
+            panic!("Expected a validator set");
+        };
+        assert_eq!(validator_set.active_validators.len(), 1);
+        assert_eq!(validator_set.active_validators[0].config.validator_index, 3);
+        assert_eq!(
+            validator_set.total_voting_power,
+            BigDecimal::from(1_000_000)
+        );
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use super::epoch_utils::ValidatorPerformanceResource;
+use crate::schema::epoch_validator_performances;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+/// Proposals of each validator in an epoch, as of the last block processed. Validators are
+/// identified by their index in the validator set of the epoch, see epoch_validators.
+///
+/// The reconfiguration transaction resets the performance for the next epoch, so the proposal of
+/// the last block of an epoch isn't counted.
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(epoch, validator_index))]
+#[diesel(table_name = epoch_validator_performances)]
+pub struct EpochValidatorPerformance {
+    pub epoch: i64,
+    pub validator_index: i64,
+    pub successful_proposals: i64,
+    pub failed_proposals: i64,
+    pub last_transaction_version: i64,
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl EpochValidatorPerformance {
+    pub fn from_validator_performance(
+        validator_performance: &ValidatorPerformanceResource,
+        epoch: i64,
+        txn_version: i64,
+        txn_timestamp: chrono::NaiveDateTime,
+    ) -> Vec<Self> {
+        validator_performance
+            .validators
+            .iter()
+            .enumerate()
+            .map(|(index, performance)| Self {
+                epoch,
+                validator_index: index as i64,
+                successful_proposals: performance.successful_proposals,
+                failed_proposals: performance.failed_proposals,
+                last_transaction_version: txn_version,
+                last_transaction_timestamp: txn_timestamp,
+            })
+            .collect()
+    }
+}
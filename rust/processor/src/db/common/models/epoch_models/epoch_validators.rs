@@ -0,0 +1,53 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use super::epoch_utils::ValidatorSetResource;
+use crate::{schema::epoch_validators, utils::util::standardize_address};
+use bigdecimal::BigDecimal;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+/// Validator set of each epoch. The addresses are bcs encoded and kept hex encoded as on chain.
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(epoch, validator_address))]
+#[diesel(table_name = epoch_validators)]
+pub struct EpochValidator {
+    pub epoch: i64,
+    pub validator_address: String,
+    pub validator_index: i64,
+    pub voting_power: BigDecimal,
+    pub consensus_public_key: String,
+    pub network_addresses: String,
+    pub fullnode_addresses: String,
+    pub transaction_version: i64,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl EpochValidator {
+    pub fn from_validator_set(
+        validator_set: &ValidatorSetResource,
+        epoch: i64,
+        txn_version: i64,
+        txn_timestamp: chrono::NaiveDateTime,
+    ) -> Vec<Self> {
+        validator_set
+            .active_validators
+            .iter()
+            .map(|validator| Self {
+                epoch,
+                validator_address: standardize_address(&validator.addr),
+                validator_index: validator.config.validator_index,
+                voting_power: validator.voting_power.clone(),
+                consensus_public_key: validator.config.consensus_pubkey.clone(),
+                network_addresses: validator.config.network_addresses.clone(),
+                fullnode_addresses: validator.config.fullnode_addresses.clone(),
+                transaction_version: txn_version,
+                transaction_timestamp: txn_timestamp,
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use super::epoch_utils::ValidatorSetResource;
+use crate::schema::epochs;
+use bigdecimal::BigDecimal;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+/// Start of each epoch, from the reconfiguration transaction ending the previous one
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(epoch))]
+#[diesel(table_name = epochs)]
+pub struct Epoch {
+    pub epoch: i64,
+    pub transaction_version: i64,
+    pub block_height: i64,
+    pub num_validators: i64,
+    pub total_voting_power: BigDecimal,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl Epoch {
+    pub fn from_validator_set(
+        validator_set: &ValidatorSetResource,
+        epoch: i64,
+        txn_version: i64,
+        block_height: i64,
+        txn_timestamp: chrono::NaiveDateTime,
+    ) -> Self {
+        Self {
+            epoch,
+            transaction_version: txn_version,
+            block_height,
+            num_validators: validator_set.active_validators.len() as i64,
+            total_voting_power: validator_set.total_voting_power.clone(),
+            transaction_timestamp: txn_timestamp,
+        }
+    }
+}
@@ -0,0 +1,7 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod epoch_utils;
+pub mod epoch_validator_performances;
+pub mod epoch_validators;
+pub mod epochs;
@@ -0,0 +1,353 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tables declared in the config of the event tables processor, see `runtime_tables`.
+//! Identifiers are restricted to plain lowercase names.
+
+use crate::utils::{
+    database::is_valid_identifier,
+    runtime_tables::RuntimeTable,
+    util::{normalize_type_str, parse_timestamp, standardize_address},
+};
+use anyhow::{Context, Result};
+use aptos_protos::transaction::v1::{Event, Transaction};
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::str::FromStr;
+
+/// Columns every event table has, before the configured ones
+const FIXED_COLUMNS: [(&str, &str); 4] = [
+    ("transaction_version", "BIGINT NOT NULL"),
+    ("event_index", "BIGINT NOT NULL"),
+    ("account_address", "VARCHAR(66) NOT NULL"),
+    ("transaction_timestamp", "TIMESTAMP NOT NULL"),
+];
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventColumnType {
+    /// Standardized to the long form, e.g. 0x1 to 0x000...001
+    Address,
+    /// u64, u128 and u256, which move serializes as strings
+    Numeric,
+    BigInt,
+    Boolean,
+    Text,
+    Jsonb,
+}
+
+impl EventColumnType {
+    fn sql_type(&self) -> &'static str {
+        match self {
+            EventColumnType::Address => "VARCHAR(66)",
+            EventColumnType::Numeric => "NUMERIC",
+            EventColumnType::BigInt => "BIGINT",
+            EventColumnType::Boolean => "BOOLEAN",
+            EventColumnType::Text => "TEXT",
+            EventColumnType::Jsonb => "JSONB",
+        }
+    }
+
+    /// The type as formatted by postgres, to compare with the existing columns
+    fn formatted_sql_type(&self) -> &'static str {
+        match self {
+            EventColumnType::Address => "character varying(66)",
+            EventColumnType::Numeric => "numeric",
+            EventColumnType::BigInt => "bigint",
+            EventColumnType::Boolean => "boolean",
+            EventColumnType::Text => "text",
+            EventColumnType::Jsonb => "jsonb",
+        }
+    }
+
+    /// Converts the move value to the JSON value postgres reads into the column type
+    fn convert(&self, value: &Value) -> Result<Value> {
+        let converted = match (self, value) {
+            (_, Value::Null) => Value::Null,
+            (EventColumnType::Address, Value::String(address)) => {
+                Value::String(standardize_address(address))
+            },
+            // Kept as a string so u128 and u256 don't lose precision
+            (EventColumnType::Numeric, Value::String(number)) => {
+                Value::String(BigDecimal::from_str(number)?.to_string())
+            },
+            (EventColumnType::Numeric, Value::Number(number)) => Value::String(number.to_string()),
+            (EventColumnType::BigInt, Value::String(number)) => json!(i64::from_str(number)?),
+            (EventColumnType::BigInt, Value::Number(number)) if number.is_i64() => {
+                Value::Number(number.clone())
+            },
+            (EventColumnType::Boolean, Value::Bool(_)) => value.clone(),
+            (EventColumnType::Text, Value::String(_)) => value.clone(),
+            (EventColumnType::Text, _) => Value::String(value.to_string()),
+            (EventColumnType::Jsonb, _) => value.clone(),
+            _ => anyhow::bail!("Can't convert {} to {:?}", value, self),
+        };
+        Ok(converted)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EventColumnConfig {
+    pub name: String,
+    // Dot separated path in the event data, array elements are selected by index,
+    // e.g. `metadata.inner` or `coins.0.value`. The column is null if the path is missing.
+    pub path: String,
+    #[serde(rename = "type")]
+    pub column_type: EventColumnType,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EventTableConfig {
+    pub table_name: String,
+    // Move event type, e.g. `0xcafe::amm::SwapEvent`. The addresses, also those of the type
+    // arguments, can be in short or long form.
+    pub event_type: String,
+    pub columns: Vec<EventColumnConfig>,
+}
+
+impl EventTableConfig {
+    /// The event type to compare with the types normalized with `normalize_type_str`, computed
+    /// once instead of for every event
+    pub fn normalized_event_type(&self) -> String {
+        normalize_type_str(&self.event_type)
+    }
+
+    /// The row of the event as a JSON object keyed by column name
+    pub fn to_row(
+        &self,
+        event: &Event,
+        transaction: &Transaction,
+        event_index: i64,
+    ) -> Result<Value> {
+        let txn_version = transaction.version as i64;
+        let data: Value = serde_json::from_str(&event.data).context(format!(
+            "version {} failed! failed to parse event data {:?}",
+            txn_version, event.data
+        ))?;
+        let account_address = event
+            .key
+            .as_ref()
+            .map(|key| standardize_address(&key.account_address))
+            .unwrap_or_else(|| standardize_address("0x0"));
+        let transaction_timestamp =
+            parse_timestamp(transaction.timestamp.as_ref().unwrap(), txn_version);
+
+        let mut row = Map::new();
+        row.insert("transaction_version".to_string(), json!(txn_version));
+        row.insert("event_index".to_string(), json!(event_index));
+        row.insert("account_address".to_string(), json!(account_address));
+        row.insert(
+            "transaction_timestamp".to_string(),
+            json!(transaction_timestamp
+                .format("%Y-%m-%dT%H:%M:%S%.f")
+                .to_string()),
+        );
+        for column in &self.columns {
+            let value = get_path(&data, &column.path).unwrap_or(&Value::Null);
+            let value = column.column_type.convert(value).context(format!(
+                "version {} failed! failed to convert {} of table {}",
+                txn_version, column.path, self.table_name
+            ))?;
+            row.insert(column.name.clone(), value);
+        }
+        Ok(Value::Object(row))
+    }
+}
+
+impl RuntimeTable for EventTableConfig {
+    fn table_name(&self) -> String {
+        self.table_name.clone()
+    }
+
+    fn validate(&self) -> Result<()> {
+        anyhow::ensure!(
+            is_valid_identifier(&self.table_name),
+            "Invalid table name {}, only lowercase letters, digits and underscores are allowed",
+            self.table_name
+        );
+        for column in &self.columns {
+            anyhow::ensure!(
+                is_valid_identifier(&column.name),
+                "Invalid column name {} in table {}",
+                column.name,
+                self.table_name
+            );
+            anyhow::ensure!(
+                !FIXED_COLUMNS.iter().any(|(name, _)| *name == column.name)
+                    && column.name != "inserted_at",
+                "Column {} of table {} is reserved",
+                column.name,
+                self.table_name
+            );
+        }
+        Ok(())
+    }
+
+    fn column_types(&self) -> Vec<(&str, &'static str)> {
+        self.columns
+            .iter()
+            .map(|column| {
+                (
+                    column.name.as_str(),
+                    column.column_type.formatted_sql_type(),
+                )
+            })
+            .collect()
+    }
+
+    /// Creates the table and adds the columns added to the config since. Columns removed from
+    /// the config are left as they are.
+    fn ddl_statements(&self) -> Vec<String> {
+        let mut columns = FIXED_COLUMNS
+            .iter()
+            .map(|(name, sql_type)| format!("\"{}\" {}", name, sql_type))
+            .collect::<Vec<_>>();
+        columns.extend(
+            self.columns
+                .iter()
+                .map(|column| format!("\"{}\" {}", column.name, column.column_type.sql_type())),
+        );
+        columns.push("\"inserted_at\" TIMESTAMP NOT NULL DEFAULT NOW()".to_string());
+        columns.push("PRIMARY KEY (\"transaction_version\", \"event_index\")".to_string());
+
+        let mut statements = vec![format!(
+            "CREATE TABLE IF NOT EXISTS \"{}\" ({})",
+            self.table_name,
+            columns.join(", ")
+        )];
+        statements.extend(self.columns.iter().map(|column| {
+            format!(
+                "ALTER TABLE \"{}\" ADD COLUMN IF NOT EXISTS \"{}\" {}",
+                self.table_name,
+                column.name,
+                column.column_type.sql_type()
+            )
+        }));
+        statements
+    }
+
+    fn insert_statement(&self) -> String {
+        let column_names = FIXED_COLUMNS
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .chain(self.columns.iter().map(|column| column.name.clone()))
+            .map(|name| format!("\"{}\"", name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let record_columns = FIXED_COLUMNS
+            .iter()
+            .map(|(name, sql_type)| {
+                format!("\"{}\" {}", name, sql_type.trim_end_matches(" NOT NULL"))
+            })
+            .chain(
+                self.columns
+                    .iter()
+                    .map(|column| format!("\"{}\" {}", column.name, column.column_type.sql_type())),
+            )
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "INSERT INTO \"{table}\" ({columns}) SELECT {columns} FROM jsonb_to_recordset($1) AS r({record_columns}) \
+             ON CONFLICT (\"transaction_version\", \"event_index\") DO NOTHING",
+            table = self.table_name,
+            columns = column_names,
+            record_columns = record_columns,
+        )
+    }
+}
+
+fn get_path<'a>(data: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(data, |value, key| match value {
+        Value::Array(values) => values.get(key.parse::<usize>().ok()?),
+        _ => value.get(key),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> EventTableConfig {
+        serde_json::from_value(json!({
+            "table_name": "swaps",
+            "event_type": "0xcafe::amm::SwapEvent",
+            "columns": [
+                { "name": "sender", "path": "sender", "type": "address" },
+                { "name": "amount_in", "path": "amounts.0", "type": "numeric" },
+                { "name": "pool", "path": "pool.inner", "type": "address" },
+                { "name": "is_stable", "path": "is_stable", "type": "boolean" },
+            ],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_normalized_event_type() {
+        let table = table();
+        assert_eq!(
+            table.normalized_event_type(),
+            normalize_type_str(
+                "0x000000000000000000000000000000000000000000000000000000000000cafe::amm::SwapEvent"
+            )
+        );
+        assert_ne!(
+            table.normalized_event_type(),
+            normalize_type_str("0xcafe::amm::AddLiquidityEvent")
+        );
+        let mut generic = table;
+        generic.event_type = "0x1::coin::CoinDeposit<0x0001::aptos_coin::AptosCoin>".to_string();
+        assert_eq!(
+            generic.normalized_event_type(),
+            normalize_type_str("0x1::coin::CoinDeposit<0x1::aptos_coin::AptosCoin>")
+        );
+    }
+
+    #[test]
+    fn test_to_row() {
+        let table = table();
+        let transaction = Transaction {
+            version: 100,
+            timestamp: Some(aptos_protos::util::timestamp::Timestamp {
+                seconds: 1_700_000_000,
+                nanos: 0,
+            }),
+            ..Transaction::default()
+        };
+        let event = Event {
+            type_str: "0xcafe::amm::SwapEvent".to_string(),
+            data: r#"{"sender":"0x1","amounts":["340282366920938463463374607431768211455"],"pool":{"inner":"0x2"}}"#.to_string(),
+            ..Event::default()
+        };
+        let row = table.to_row(&event, &transaction, 3).unwrap();
+        assert_eq!(row["transaction_version"], json!(100));
+        assert_eq!(row["event_index"], json!(3));
+        assert_eq!(row["transaction_timestamp"], json!("2023-11-14T22:13:20"));
+        assert_eq!(row["sender"], json!(standardize_address("0x1")));
+        assert_eq!(
+            row["amount_in"],
+            json!("340282366920938463463374607431768211455")
+        );
+        assert_eq!(row["pool"], json!(standardize_address("0x2")));
+        assert_eq!(row["is_stable"], Value::Null);
+
+        let event = Event {
+            data: r#"{"sender":"0x1","amounts":["not a number"]}"#.to_string(),
+            ..event
+        };
+        assert!(table.to_row(&event, &transaction, 3).is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(table().validate().is_ok());
+        let mut invalid = table();
+        invalid.table_name = "swaps; DROP TABLE events".to_string();
+        assert!(invalid.validate().is_err());
+        let mut reserved = table();
+        reserved.columns[0].name = "event_index".to_string();
+        assert!(reserved.validate().is_err());
+    }
+}
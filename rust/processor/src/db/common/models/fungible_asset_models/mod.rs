@@ -3,6 +3,7 @@
 
 pub mod v2_fungible_asset_activities;
 pub mod v2_fungible_asset_balances;
+pub mod v2_fungible_asset_transfers;
 pub mod v2_fungible_asset_utils;
 pub mod v2_fungible_metadata;
 
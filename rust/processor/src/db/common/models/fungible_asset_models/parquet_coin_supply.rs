@@ -36,6 +36,7 @@ pub struct CoinSupply {
 }
 
 impl NamedTable for CoinSupply {
+    const SCHEMA_VERSION: u32 = 1;
     const TABLE_NAME: &'static str = "coin_supply";
 }
 
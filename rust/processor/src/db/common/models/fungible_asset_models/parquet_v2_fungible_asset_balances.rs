@@ -47,6 +47,7 @@ pub struct FungibleAssetBalance {
 }
 
 impl NamedTable for FungibleAssetBalance {
+    const SCHEMA_VERSION: u32 = 1;
     const TABLE_NAME: &'static str = "fungible_asset_balances";
 }
 
@@ -0,0 +1,269 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use super::v2_fungible_asset_activities::FungibleAssetActivity;
+use crate::schema::fungible_asset_transfers;
+use ahash::AHashMap;
+use bigdecimal::{BigDecimal, Zero};
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+const WITHDRAW_TYPES: [&str; 3] = [
+    "0x1::coin::WithdrawEvent",
+    "0x1::fungible_asset::WithdrawEvent",
+    "0x1::fungible_asset::Withdraw",
+];
+const DEPOSIT_TYPES: [&str; 3] = [
+    "0x1::coin::DepositEvent",
+    "0x1::fungible_asset::DepositEvent",
+    "0x1::fungible_asset::Deposit",
+];
+
+/// Amount moved from one store to another in a transaction, reconstructed by pairing the
+/// withdrawals and deposits of the same asset. A deposit without a matching withdrawal is a
+/// mint and has no from side, a withdrawal without a matching deposit is a burn and has no to side.
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(transaction_version, transfer_index))]
+#[diesel(table_name = fungible_asset_transfers)]
+pub struct FungibleAssetTransfer {
+    pub transaction_version: i64,
+    pub transfer_index: i64,
+    pub withdraw_event_index: Option<i64>,
+    pub from_address: Option<String>,
+    pub from_storage_id: Option<String>,
+    pub deposit_event_index: Option<i64>,
+    pub to_address: Option<String>,
+    pub to_storage_id: Option<String>,
+    pub asset_type: String,
+    pub amount: BigDecimal,
+    pub token_standard: String,
+    pub gas_fee_payer_address: Option<String>,
+    pub entry_function_id_str: Option<String>,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+}
+
+/// Part of an activity that isn't paired yet
+struct Unpaired<'a> {
+    activity: &'a FungibleAssetActivity,
+    remaining: BigDecimal,
+}
+
+impl FungibleAssetTransfer {
+    /// Pairs the withdrawals and deposits of each transaction, in event order. A withdrawal is
+    /// split over as many deposits as needed, e.g. a payment to several recipients, and a deposit
+    /// over as many withdrawals. Gas fees aren't transfers and are skipped, the fee payer of a
+    /// sponsored transaction is kept in `gas_fee_payer_address`.
+    ///
+    /// Assets are paired by asset type, so a coin withdrawal deposited as the paired fungible
+    /// asset shows up as a burn and a mint.
+    pub fn from_activities(activities: &[FungibleAssetActivity]) -> Vec<Self> {
+        let mut transfers = vec![];
+        let mut start = 0;
+        while start < activities.len() {
+            let txn_version = activities[start].transaction_version;
+            let end = activities[start..]
+                .iter()
+                .position(|activity| activity.transaction_version != txn_version)
+                .map_or(activities.len(), |len| start + len);
+            transfers.extend(Self::from_transaction_activities(&activities[start..end]));
+            start = end;
+        }
+        transfers
+    }
+
+    fn from_transaction_activities(activities: &[FungibleAssetActivity]) -> Vec<Self> {
+        let gas_fee_payer_address = activities
+            .iter()
+            .find(|activity| activity.is_gas_fee)
+            .and_then(|activity| activity.gas_fee_payer_address.clone());
+        let mut activities = activities
+            .iter()
+            .filter(|activity| !activity.is_gas_fee && activity.asset_type.is_some())
+            .filter(|activity| {
+                activity
+                    .amount
+                    .as_ref()
+                    .is_some_and(|amount| !amount.is_zero())
+            })
+            .collect::<Vec<_>>();
+        activities.sort_by_key(|activity| activity.event_index);
+
+        let mut transfers = vec![];
+        let new_transfer = |withdraw: Option<&FungibleAssetActivity>,
+                            deposit: Option<&FungibleAssetActivity>,
+                            amount: BigDecimal,
+                            transfer_index: usize| {
+            let activity = withdraw.or(deposit).unwrap();
+            Self {
+                transaction_version: activity.transaction_version,
+                transfer_index: transfer_index as i64,
+                withdraw_event_index: withdraw.map(|w| w.event_index),
+                from_address: withdraw.and_then(|w| w.owner_address.clone()),
+                from_storage_id: withdraw.map(|w| w.storage_id.clone()),
+                deposit_event_index: deposit.map(|d| d.event_index),
+                to_address: deposit.and_then(|d| d.owner_address.clone()),
+                to_storage_id: deposit.map(|d| d.storage_id.clone()),
+                asset_type: activity.asset_type.clone().unwrap(),
+                amount,
+                token_standard: activity.token_standard.clone(),
+                gas_fee_payer_address: gas_fee_payer_address.clone(),
+                entry_function_id_str: activity.entry_function_id_str.clone(),
+                transaction_timestamp: activity.transaction_timestamp,
+            }
+        };
+
+        // Per asset type, the withdrawals and deposits waiting for their counterpart
+        let mut withdrawals: AHashMap<&str, VecDeque<Unpaired<'_>>> = AHashMap::new();
+        let mut deposits: AHashMap<&str, VecDeque<Unpaired<'_>>> = AHashMap::new();
+        for activity in activities {
+            let is_withdraw = WITHDRAW_TYPES.contains(&activity.type_.as_str());
+            if !is_withdraw && !DEPOSIT_TYPES.contains(&activity.type_.as_str()) {
+                continue;
+            }
+            let asset_type = activity.asset_type.as_deref().unwrap();
+            let (pending, counterparts) = if is_withdraw {
+                (&mut withdrawals, &mut deposits)
+            } else {
+                (&mut deposits, &mut withdrawals)
+            };
+            let mut remaining = activity.amount.clone().unwrap();
+            let counterparts = counterparts.entry(asset_type).or_default();
+            while !remaining.is_zero() {
+                let Some(counterpart) = counterparts.front_mut() else {
+                    break;
+                };
+                let amount = remaining.clone().min(counterpart.remaining.clone());
+                remaining -= &amount;
+                counterpart.remaining -= &amount;
+                let (withdraw, deposit) = if is_withdraw {
+                    (activity, counterpart.activity)
+                } else {
+                    (counterpart.activity, activity)
+                };
+                if counterpart.remaining.is_zero() {
+                    counterparts.pop_front();
+                }
+                transfers.push(new_transfer(
+                    Some(withdraw),
+                    Some(deposit),
+                    amount,
+                    transfers.len(),
+                ));
+            }
+            if !remaining.is_zero() {
+                pending.entry(asset_type).or_default().push_back(Unpaired {
+                    activity,
+                    remaining,
+                });
+            }
+        }
+
+        // What's left are burns and mints
+        let mut unpaired = withdrawals
+            .into_values()
+            .flatten()
+            .chain(deposits.into_values().flatten())
+            .collect::<Vec<_>>();
+        unpaired.sort_by_key(|unpaired| unpaired.activity.event_index);
+        for Unpaired {
+            activity,
+            remaining,
+        } in unpaired
+        {
+            let transfer = if WITHDRAW_TYPES.contains(&activity.type_.as_str()) {
+                new_transfer(Some(activity), None, remaining, transfers.len())
+            } else {
+                new_transfer(None, Some(activity), remaining, transfers.len())
+            };
+            transfers.push(transfer);
+        }
+        transfers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn activity(
+        event_index: i64,
+        type_: &str,
+        owner_address: &str,
+        asset_type: &str,
+        amount: u64,
+    ) -> FungibleAssetActivity {
+        FungibleAssetActivity {
+            transaction_version: 1,
+            event_index,
+            owner_address: Some(owner_address.to_string()),
+            storage_id: format!("{}_store", owner_address),
+            asset_type: Some(asset_type.to_string()),
+            is_frozen: None,
+            amount: Some(BigDecimal::from(amount)),
+            type_: type_.to_string(),
+            is_gas_fee: false,
+            gas_fee_payer_address: None,
+            is_transaction_success: true,
+            entry_function_id_str: None,
+            block_height: 0,
+            token_standard: "v2".to_string(),
+            transaction_timestamp: chrono::NaiveDateTime::default(),
+            storage_refund_amount: BigDecimal::zero(),
+        }
+    }
+
+    fn summary(transfers: &[FungibleAssetTransfer]) -> Vec<(Option<&str>, Option<&str>, u64)> {
+        transfers
+            .iter()
+            .map(|t| {
+                (
+                    t.from_address.as_deref(),
+                    t.to_address.as_deref(),
+                    t.amount.to_string().parse().unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_multiple_recipients_and_sponsored_gas() {
+        let mut gas = activity(-1, "0x1::aptos_coin::GasFeeEvent", "alice", "apt", 5);
+        gas.is_gas_fee = true;
+        gas.gas_fee_payer_address = Some("sponsor".to_string());
+        let activities = vec![
+            gas,
+            activity(0, "0x1::fungible_asset::Withdraw", "alice", "usdc", 100),
+            activity(1, "0x1::fungible_asset::Deposit", "bob", "usdc", 30),
+            activity(2, "0x1::fungible_asset::Deposit", "carol", "usdc", 70),
+        ];
+        let transfers = FungibleAssetTransfer::from_activities(&activities);
+        assert_eq!(summary(&transfers), vec![
+            (Some("alice"), Some("bob"), 30),
+            (Some("alice"), Some("carol"), 70),
+        ]);
+        assert!(transfers
+            .iter()
+            .all(|t| t.gas_fee_payer_address.as_deref() == Some("sponsor")));
+        assert_eq!(transfers[1].transfer_index, 1);
+    }
+
+    #[test]
+    fn test_mints_burns_and_assets_paired_separately() {
+        let activities = vec![
+            activity(0, "0x1::coin::WithdrawEvent", "alice", "apt", 10),
+            activity(1, "0x1::fungible_asset::Deposit", "bob", "usdc", 20),
+            activity(2, "0x1::coin::DepositEvent", "carol", "apt", 4),
+        ];
+        let transfers = FungibleAssetTransfer::from_activities(&activities);
+        assert_eq!(summary(&transfers), vec![
+            (Some("alice"), Some("carol"), 4),
+            (Some("alice"), None, 6),
+            (None, Some("bob"), 20),
+        ]);
+    }
+}
@@ -20,8 +20,18 @@ use serde::{Deserialize, Serialize};
 const FUNGIBLE_ASSET_LENGTH: usize = 32;
 const FUNGIBLE_ASSET_SYMBOL: usize = 10;
 
+/// Breakdown of the gas charged to a transaction. The execution and io fees are in gas units
+/// while the storage fee is in octas, the total is in gas units and includes all of them.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct FeeStatement {
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub total_charge_gas_units: u64,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub execution_gas_units: u64,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub io_gas_units: u64,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub storage_fee_octas: u64,
     #[serde(deserialize_with = "deserialize_from_string")]
     pub storage_fee_refund_octas: u64,
 }
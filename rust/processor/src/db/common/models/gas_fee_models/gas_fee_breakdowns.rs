@@ -0,0 +1,97 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use crate::{
+    db::common::models::{
+        fungible_asset_models::v2_fungible_asset_utils::FeeStatement,
+        user_transactions_models::signatures::Signature,
+    },
+    schema::gas_fee_breakdowns,
+    utils::util::{
+        get_entry_function_from_user_request, parse_timestamp, standardize_address,
+        u64_to_bigdecimal,
+    },
+};
+use aptos_protos::transaction::v1::{transaction::TxnData, Transaction};
+use bigdecimal::BigDecimal;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+/// Gas charged to each user transaction. `gas_fee_octas` is what was charged before the storage
+/// refund, which is paid back to the payer. The breakdown is only available for transactions
+/// emitting a FeeStatement.
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(transaction_version))]
+#[diesel(table_name = gas_fee_breakdowns)]
+pub struct GasFeeBreakdown {
+    pub transaction_version: i64,
+    pub sender: String,
+    pub gas_fee_payer_address: Option<String>,
+    pub is_transaction_success: bool,
+    pub entry_function_id_str: Option<String>,
+    pub gas_unit_price: BigDecimal,
+    pub total_gas_units: i64,
+    pub execution_gas_units: Option<i64>,
+    pub io_gas_units: Option<i64>,
+    pub storage_fee_octas: Option<BigDecimal>,
+    pub storage_fee_refund_octas: Option<BigDecimal>,
+    pub gas_fee_octas: BigDecimal,
+    pub block_height: i64,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl GasFeeBreakdown {
+    pub fn from_transaction(transaction: &Transaction) -> Option<Self> {
+        let Some(TxnData::User(user_txn)) = transaction.txn_data.as_ref() else {
+            return None;
+        };
+        let txn_version = transaction.version as i64;
+        let user_request = user_txn
+            .request
+            .as_ref()
+            .expect("Sends is not present in user txn");
+        let txn_info = transaction
+            .info
+            .as_ref()
+            .expect("Transaction info doesn't exist!");
+        let fee_statement = user_txn.events.iter().find_map(|event| {
+            FeeStatement::from_event(event.type_str.as_str(), &event.data, txn_version)
+        });
+
+        Some(Self {
+            transaction_version: txn_version,
+            sender: standardize_address(&user_request.sender),
+            gas_fee_payer_address: user_request
+                .signature
+                .as_ref()
+                .and_then(|signature| Signature::get_fee_payer_address(signature, txn_version)),
+            is_transaction_success: txn_info.success,
+            entry_function_id_str: get_entry_function_from_user_request(user_request),
+            gas_unit_price: u64_to_bigdecimal(user_request.gas_unit_price),
+            total_gas_units: txn_info.gas_used as i64,
+            execution_gas_units: fee_statement
+                .as_ref()
+                .map(|fee_statement| fee_statement.execution_gas_units as i64),
+            io_gas_units: fee_statement
+                .as_ref()
+                .map(|fee_statement| fee_statement.io_gas_units as i64),
+            storage_fee_octas: fee_statement
+                .as_ref()
+                .map(|fee_statement| u64_to_bigdecimal(fee_statement.storage_fee_octas)),
+            storage_fee_refund_octas: fee_statement
+                .as_ref()
+                .map(|fee_statement| u64_to_bigdecimal(fee_statement.storage_fee_refund_octas)),
+            gas_fee_octas: u64_to_bigdecimal(txn_info.gas_used)
+                * u64_to_bigdecimal(user_request.gas_unit_price),
+            block_height: transaction.block_height as i64,
+            transaction_timestamp: parse_timestamp(
+                transaction.timestamp.as_ref().unwrap(),
+                txn_version,
+            ),
+        })
+    }
+}
@@ -0,0 +1,5 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod gas_fee_breakdowns;
+pub mod sender_daily_gas_fees;
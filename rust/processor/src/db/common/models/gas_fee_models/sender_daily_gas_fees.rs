@@ -0,0 +1,54 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+/// Inserts the breakdowns, bound as a JSONB array to $1, and adds the transactions that weren't
+/// already in gas_fee_breakdowns to the daily totals of their sender. Both are done in a single
+/// statement so a reprocessed transaction is never counted twice, and each batch only reads its
+/// own transactions instead of all the transactions of the day.
+///
+/// Sponsored transactions are counted for the sender, their fee payer is in gas_fee_breakdowns.
+pub const INSERT_GAS_FEE_BREAKDOWNS_AND_DAILY_TOTALS: &str = "
+    WITH new_breakdowns AS (
+        INSERT INTO gas_fee_breakdowns (
+            transaction_version, sender, gas_fee_payer_address, is_transaction_success,
+            entry_function_id_str, gas_unit_price, total_gas_units, execution_gas_units,
+            io_gas_units, storage_fee_octas, storage_fee_refund_octas, gas_fee_octas,
+            block_height, transaction_timestamp
+        )
+        SELECT
+            transaction_version, sender, gas_fee_payer_address, is_transaction_success,
+            entry_function_id_str, gas_unit_price, total_gas_units, execution_gas_units,
+            io_gas_units, storage_fee_octas, storage_fee_refund_octas, gas_fee_octas,
+            block_height, transaction_timestamp
+        FROM jsonb_populate_recordset(NULL::gas_fee_breakdowns, $1)
+        ON CONFLICT (transaction_version) DO NOTHING
+        RETURNING sender, transaction_timestamp, total_gas_units, gas_fee_octas,
+            storage_fee_refund_octas, transaction_version
+    )
+    INSERT INTO sender_daily_gas_fees (
+        sender, date, num_transactions, total_gas_units, gas_fee_octas,
+        storage_fee_refund_octas, last_transaction_version
+    )
+    SELECT
+        sender,
+        transaction_timestamp::date,
+        COUNT(*),
+        SUM(total_gas_units),
+        SUM(gas_fee_octas),
+        COALESCE(SUM(storage_fee_refund_octas), 0),
+        MAX(transaction_version)
+    FROM new_breakdowns
+    GROUP BY sender, transaction_timestamp::date
+    -- Concurrent batches lock the rows in the same order
+    ORDER BY sender, transaction_timestamp::date
+    ON CONFLICT (sender, date) DO UPDATE SET
+        num_transactions = sender_daily_gas_fees.num_transactions + excluded.num_transactions,
+        total_gas_units = sender_daily_gas_fees.total_gas_units + excluded.total_gas_units,
+        gas_fee_octas = sender_daily_gas_fees.gas_fee_octas + excluded.gas_fee_octas,
+        storage_fee_refund_octas =
+            sender_daily_gas_fees.storage_fee_refund_octas + excluded.storage_fee_refund_octas,
+        last_transaction_version = GREATEST(
+            sender_daily_gas_fees.last_transaction_version, excluded.last_transaction_version
+        ),
+        inserted_at = NOW()
+";
@@ -0,0 +1,139 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use super::governance_utils::ProposalResource;
+use crate::{
+    schema::{current_governance_proposals, governance_proposals},
+    utils::util::{parse_timestamp_secs, standardize_address},
+};
+use ahash::AHashMap;
+use aptos_protos::transaction::v1::WriteTableItem;
+use bigdecimal::BigDecimal;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+/// Proposal id to stake pool, from the proposals created in a transaction
+pub type ProposalStakePools = AHashMap<i64, String>;
+/// Proposal id to whether it was resolved early, from the proposals resolved in a transaction
+pub type ProposalEarlyResolutions = AHashMap<i64, bool>;
+
+/// State of a governance proposal after each transaction changing it, i.e. its creation, each
+/// vote and its resolution
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(transaction_version, proposal_id))]
+#[diesel(table_name = governance_proposals)]
+pub struct GovernanceProposal {
+    pub transaction_version: i64,
+    pub proposal_id: i64,
+    pub proposer_address: String,
+    // Only known from the creation of the proposal
+    pub stake_pool_address: Option<String>,
+    pub execution_hash: String,
+    pub metadata: serde_json::Value,
+    pub min_vote_threshold: BigDecimal,
+    pub early_resolution_vote_threshold: Option<BigDecimal>,
+    pub yes_votes: BigDecimal,
+    pub no_votes: BigDecimal,
+    pub is_resolved: bool,
+    // Only known from the resolution of the proposal
+    pub resolved_early: Option<bool>,
+    pub creation_timestamp: chrono::NaiveDateTime,
+    pub expiration_timestamp: chrono::NaiveDateTime,
+    pub resolution_timestamp: Option<chrono::NaiveDateTime>,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(proposal_id))]
+#[diesel(table_name = current_governance_proposals)]
+pub struct CurrentGovernanceProposal {
+    pub proposal_id: i64,
+    pub proposer_address: String,
+    pub stake_pool_address: Option<String>,
+    pub execution_hash: String,
+    pub metadata: serde_json::Value,
+    pub min_vote_threshold: BigDecimal,
+    pub early_resolution_vote_threshold: Option<BigDecimal>,
+    pub yes_votes: BigDecimal,
+    pub no_votes: BigDecimal,
+    pub is_resolved: bool,
+    pub resolved_early: Option<bool>,
+    pub creation_timestamp: chrono::NaiveDateTime,
+    pub expiration_timestamp: chrono::NaiveDateTime,
+    pub resolution_timestamp: Option<chrono::NaiveDateTime>,
+    pub last_transaction_version: i64,
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl GovernanceProposal {
+    pub fn from_write_table_item(
+        write_table_item: &WriteTableItem,
+        txn_version: i64,
+        txn_timestamp: chrono::NaiveDateTime,
+        stake_pools: &ProposalStakePools,
+        early_resolutions: &ProposalEarlyResolutions,
+    ) -> anyhow::Result<Option<(Self, CurrentGovernanceProposal)>> {
+        let Some((proposal_id, proposal)) =
+            ProposalResource::from_write_table_item(write_table_item, txn_version)?
+        else {
+            return Ok(None);
+        };
+        let stake_pool_address = stake_pools.get(&proposal_id).cloned();
+        let resolved_early = if proposal.is_resolved {
+            early_resolutions.get(&proposal_id).copied()
+        } else {
+            None
+        };
+        let creation_timestamp = parse_timestamp_secs(proposal.creation_time_secs, txn_version);
+        let expiration_timestamp = parse_timestamp_secs(proposal.expiration_secs, txn_version);
+        let resolution_timestamp = proposal
+            .is_resolved
+            .then(|| parse_timestamp_secs(proposal.resolution_time_secs, txn_version));
+        let metadata = proposal.metadata.to_json();
+        let early_resolution_vote_threshold =
+            proposal.early_resolution_vote_threshold.get_big_decimal();
+
+        Ok(Some((
+            Self {
+                transaction_version: txn_version,
+                proposal_id,
+                proposer_address: standardize_address(&proposal.proposer),
+                stake_pool_address: stake_pool_address.clone(),
+                execution_hash: proposal.execution_hash.clone(),
+                metadata: metadata.clone(),
+                min_vote_threshold: proposal.min_vote_threshold.clone(),
+                early_resolution_vote_threshold: early_resolution_vote_threshold.clone(),
+                yes_votes: proposal.yes_votes.clone(),
+                no_votes: proposal.no_votes.clone(),
+                is_resolved: proposal.is_resolved,
+                resolved_early,
+                creation_timestamp,
+                expiration_timestamp,
+                resolution_timestamp,
+                transaction_timestamp: txn_timestamp,
+            },
+            CurrentGovernanceProposal {
+                proposal_id,
+                proposer_address: standardize_address(&proposal.proposer),
+                stake_pool_address,
+                execution_hash: proposal.execution_hash,
+                metadata,
+                min_vote_threshold: proposal.min_vote_threshold,
+                early_resolution_vote_threshold,
+                yes_votes: proposal.yes_votes,
+                no_votes: proposal.no_votes,
+                is_resolved: proposal.is_resolved,
+                resolved_early,
+                creation_timestamp,
+                expiration_timestamp,
+                resolution_timestamp,
+                last_transaction_version: txn_version,
+                last_transaction_timestamp: txn_timestamp,
+            },
+        )))
+    }
+}
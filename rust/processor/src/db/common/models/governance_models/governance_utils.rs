@@ -0,0 +1,206 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::utils::util::{deserialize_from_string, hex_to_raw_bytes};
+use anyhow::{Context, Result};
+use aptos_protos::transaction::v1::WriteTableItem;
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Serialize};
+
+/// Proposals of `0x1::aptos_governance` are stored in the voting forum of 0x1, in a table keyed
+/// by proposal id. Only aptos_governance can create a GovernanceProposal.
+const GOVERNANCE_PROPOSAL_TYPE: &str =
+    "0x1::voting::Proposal<0x1::governance_proposal::GovernanceProposal>";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct BigDecimalWrapper(#[serde(deserialize_with = "deserialize_from_string")] pub BigDecimal);
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OptionalBigDecimal {
+    vec: Vec<BigDecimalWrapper>,
+}
+
+impl OptionalBigDecimal {
+    pub fn get_big_decimal(&self) -> Option<BigDecimal> {
+        self.vec.first().map(|inner| inner.0.clone())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct SimpleMapEntry {
+    key: String,
+    value: String,
+}
+
+/// `SimpleMap<String, vector<u8>>` of the proposal metadata, e.g. `metadata_location` and
+/// `metadata_hash`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProposalMetadata {
+    data: Vec<SimpleMapEntry>,
+}
+
+impl ProposalMetadata {
+    /// JSON object of the metadata, with the values decoded as utf8 when they are text and kept
+    /// hex encoded otherwise, e.g. the bcs encoded bool of `IS_MULTI_STEP_PROPOSAL_KEY`
+    pub fn to_json(&self) -> serde_json::Value {
+        self.data
+            .iter()
+            .map(|entry| {
+                let value = hex_to_raw_bytes(&entry.value)
+                    .ok()
+                    .and_then(|bytes| String::from_utf8(bytes).ok())
+                    .filter(|value| !value.chars().any(char::is_control))
+                    .unwrap_or_else(|| entry.value.clone());
+                (entry.key.clone(), serde_json::Value::String(value))
+            })
+            .collect::<serde_json::Map<_, _>>()
+            .into()
+    }
+}
+
+/// `0x1::voting::Proposal`, without the execution content
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProposalResource {
+    pub proposer: String,
+    pub metadata: ProposalMetadata,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub creation_time_secs: u64,
+    pub execution_hash: String,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub min_vote_threshold: BigDecimal,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub expiration_secs: u64,
+    pub early_resolution_vote_threshold: OptionalBigDecimal,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub yes_votes: BigDecimal,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub no_votes: BigDecimal,
+    pub is_resolved: bool,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub resolution_time_secs: u64,
+}
+
+impl ProposalResource {
+    /// Proposal id and proposal of a governance proposal table item
+    pub fn from_write_table_item(
+        write_table_item: &WriteTableItem,
+        txn_version: i64,
+    ) -> Result<Option<(i64, Self)>> {
+        let Some(table_item_data) = write_table_item.data.as_ref() else {
+            return Ok(None);
+        };
+        if table_item_data.value_type != GOVERNANCE_PROPOSAL_TYPE {
+            return Ok(None);
+        }
+        let context = || {
+            format!(
+                "version {} failed! failed to parse type {}, key {:?}, data {:?}",
+                txn_version, table_item_data.value_type, table_item_data.key, table_item_data.value
+            )
+        };
+        // The u64 key is a JSON string
+        let proposal_id = table_item_data
+            .key
+            .trim_matches('"')
+            .parse::<i64>()
+            .with_context(context)?;
+        let proposal = serde_json::from_str(&table_item_data.value).with_context(context)?;
+        Ok(Some((proposal_id, proposal)))
+    }
+}
+
+/// Creation of a proposal by `0x1::aptos_governance`, with the stake pool backing it
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreateProposalEvent {
+    pub proposer: String,
+    pub stake_pool: String,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub proposal_id: i64,
+}
+
+/// Resolution of a proposal by `0x1::voting`. Voting forums other than governance emit it too,
+/// so it's only meaningful for a governance proposal resolved in the same transaction.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ResolveProposalEvent {
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub proposal_id: i64,
+    pub resolved_early: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum GovernanceEvent {
+    CreateProposalEvent(CreateProposalEvent),
+    ResolveProposalEvent(ResolveProposalEvent),
+}
+
+impl GovernanceEvent {
+    pub fn from_event(data_type: &str, data: &str, txn_version: i64) -> Result<Option<Self>> {
+        match data_type {
+            // Event handle and module event
+            "0x1::aptos_governance::CreateProposalEvent"
+            | "0x1::aptos_governance::CreateProposal" => {
+                serde_json::from_str(data).map(|inner| Some(Self::CreateProposalEvent(inner)))
+            },
+            "0x1::voting::ResolveProposal" => {
+                serde_json::from_str(data).map(|inner| Some(Self::ResolveProposalEvent(inner)))
+            },
+            _ => Ok(None),
+        }
+        .context(format!(
+            "version {} failed! failed to parse type {}, data {:?}",
+            txn_version, data_type, data
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_protos::transaction::v1::WriteTableData;
+
+    #[test]
+    fn test_proposal_from_write_table_item() {
+        let write_table_item = WriteTableItem {
+            data: Some(WriteTableData {
+                key: r#""42""#.to_string(),
+                key_type: "u64".to_string(),
+                value: r#"{
+                    "creation_time_secs": "1700000000",
+                    "early_resolution_vote_threshold": {"vec": ["5000"]},
+                    "execution_content": {"vec": []},
+                    "execution_hash": "0xabcd",
+                    "expiration_secs": "1700604800",
+                    "is_resolved": false,
+                    "metadata": {"data": [
+                        {"key": "metadata_location", "value": "0x68747470733a2f2f61"},
+                        {"key": "IS_MULTI_STEP_PROPOSAL_KEY", "value": "0x00"}
+                    ]},
+                    "min_vote_threshold": "4000",
+                    "no_votes": "10",
+                    "proposer": "0xa11ce",
+                    "resolution_time_secs": "0",
+                    "yes_votes": "3000"
+                }"#
+                .to_string(),
+                value_type: GOVERNANCE_PROPOSAL_TYPE.to_string(),
+            }),
+            ..WriteTableItem::default()
+        };
+        let (proposal_id, proposal) = ProposalResource::from_write_table_item(&write_table_item, 1)
+            .unwrap()
+            .unwrap();
+        assert_eq!(proposal_id, 42);
+        assert_eq!(proposal.yes_votes, BigDecimal::from(3000));
+        assert_eq!(
+            proposal.early_resolution_vote_threshold.get_big_decimal(),
+            Some(BigDecimal::from(5000))
+        );
+        assert_eq!(
+            proposal.metadata.to_json(),
+            serde_json::json!({
+                "metadata_location": "https://a",
+                "IS_MULTI_STEP_PROPOSAL_KEY": "0x00",
+            })
+        );
+    }
+}
@@ -22,4 +22,13 @@ impl LedgerInfo {
             .await
             .optional()
     }
+
+    /// Every chain of the database. There is a single one unless processors of different networks
+    /// wrote to it before the chain id guard existed.
+    pub async fn get_chain_ids(conn: &mut DbPoolConnection<'_>) -> diesel::QueryResult<Vec<i64>> {
+        ledger_infos::table
+            .select(ledger_infos::chain_id)
+            .load::<i64>(conn)
+            .await
+    }
 }
@@ -1,16 +1,31 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod account_key_models;
 pub mod account_transaction_models;
 pub mod ans_models;
+pub mod audit_models;
 pub mod coin_models;
+pub mod dead_letter_transaction;
 pub mod default_models;
+pub mod dex_models;
+pub mod epoch_models;
 pub mod events_models;
 pub mod fungible_asset_models;
+pub mod gas_fee_models;
+pub mod governance_models;
 pub mod ledger_info;
+pub mod multisig_models;
+pub mod nft_marketplace_models;
 pub mod object_models;
+pub mod parquet_table_status;
+pub mod parquet_upload_manifest;
+pub mod processor_chain_id;
+pub mod processor_gap;
 pub mod processor_status;
+pub mod processor_status_history;
 pub mod property_map;
+pub mod resource_snapshots;
 pub mod stake_models;
 pub mod token_models;
 pub mod token_v2_models;
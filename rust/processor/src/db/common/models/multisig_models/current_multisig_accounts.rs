@@ -0,0 +1,49 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use super::multisig_utils::MultisigAccountResource;
+use crate::{schema::current_multisig_accounts, utils::util::standardize_address};
+use aptos_protos::transaction::v1::WriteResource;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+/// Current owners and threshold of each multisig account. `owners` is a JSON array of addresses
+/// so that the multisig accounts of an owner can be found with `owners ? '<address>'`.
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(multisig_address))]
+#[diesel(table_name = current_multisig_accounts)]
+pub struct CurrentMultisigAccount {
+    pub multisig_address: String,
+    pub owners: serde_json::Value,
+    pub num_signatures_required: i64,
+    pub last_executed_sequence_number: i64,
+    pub next_sequence_number: i64,
+    pub last_transaction_version: i64,
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl CurrentMultisigAccount {
+    pub fn from_write_resource(
+        write_resource: &WriteResource,
+        txn_version: i64,
+        txn_timestamp: chrono::NaiveDateTime,
+    ) -> anyhow::Result<Option<Self>> {
+        Ok(
+            MultisigAccountResource::from_write_resource(write_resource, txn_version)?.map(
+                |account| Self {
+                    multisig_address: standardize_address(&write_resource.address),
+                    owners: serde_json::json!(account.get_owners()),
+                    num_signatures_required: account.num_signatures_required,
+                    last_executed_sequence_number: account.last_executed_sequence_number,
+                    next_sequence_number: account.next_sequence_number,
+                    last_transaction_version: txn_version,
+                    last_transaction_timestamp: txn_timestamp,
+                },
+            ),
+        )
+    }
+}
@@ -0,0 +1,7 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod current_multisig_accounts;
+pub mod multisig_activities;
+pub mod multisig_transactions;
+pub mod multisig_utils;
@@ -0,0 +1,141 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use super::{current_multisig_accounts::CurrentMultisigAccount, multisig_utils::MultisigEvent};
+use crate::{schema::multisig_activities, utils::util::standardize_address};
+use aptos_protos::transaction::v1::Event;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+/// There is no event for the creation of a multisig account
+pub const CREATE_ACCOUNT_EVENT_INDEX: i64 = -1;
+
+/// History of a multisig account: its creation, owner and threshold changes, and the proposed
+/// transactions with their votes and execution. Only the columns relevant to the activity type
+/// are set:
+/// - `account_address` is the creator of the account or the proposal, the voter or the executor
+/// - `owners` are the owners at creation, or the owners added or removed
+/// - `num_signatures_required` is the threshold at creation or after the update
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(transaction_version, event_index))]
+#[diesel(table_name = multisig_activities)]
+pub struct MultisigActivity {
+    pub transaction_version: i64,
+    pub event_index: i64,
+    pub multisig_address: String,
+    pub activity_type: String,
+    pub sequence_number: Option<i64>,
+    pub account_address: Option<String>,
+    pub approved: Option<bool>,
+    pub owners: Option<serde_json::Value>,
+    pub num_signatures_required: Option<i64>,
+    pub num_approvals: Option<i64>,
+    pub num_rejections: Option<i64>,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl MultisigActivity {
+    pub fn from_multisig_event(
+        multisig_event: &MultisigEvent,
+        event: &Event,
+        txn_version: i64,
+        event_index: i64,
+        txn_timestamp: chrono::NaiveDateTime,
+    ) -> anyhow::Result<Self> {
+        let base = Self {
+            transaction_version: txn_version,
+            event_index,
+            multisig_address: multisig_event.get_multisig_address(event)?,
+            activity_type: String::new(),
+            sequence_number: None,
+            account_address: None,
+            approved: None,
+            owners: None,
+            num_signatures_required: None,
+            num_approvals: None,
+            num_rejections: None,
+            transaction_timestamp: txn_timestamp,
+        };
+        let owners = |owners: &[String]| {
+            let owners = owners
+                .iter()
+                .map(|owner| standardize_address(owner))
+                .collect::<Vec<_>>();
+            Some(serde_json::json!(owners))
+        };
+        Ok(match multisig_event {
+            MultisigEvent::AddOwners(inner) => Self {
+                activity_type: "add_owners".to_string(),
+                owners: owners(&inner.owners_added),
+                ..base
+            },
+            MultisigEvent::RemoveOwners(inner) => Self {
+                activity_type: "remove_owners".to_string(),
+                owners: owners(&inner.owners_removed),
+                ..base
+            },
+            MultisigEvent::UpdateSignaturesRequired(inner) => Self {
+                activity_type: "update_signatures_required".to_string(),
+                num_signatures_required: Some(inner.new_num_signatures_required),
+                ..base
+            },
+            MultisigEvent::CreateTransaction(inner) => Self {
+                activity_type: "create_transaction".to_string(),
+                sequence_number: Some(inner.sequence_number),
+                account_address: Some(standardize_address(&inner.creator)),
+                ..base
+            },
+            MultisigEvent::Vote(inner) => Self {
+                activity_type: "vote".to_string(),
+                sequence_number: Some(inner.sequence_number),
+                account_address: Some(standardize_address(&inner.owner)),
+                approved: Some(inner.approved),
+                ..base
+            },
+            MultisigEvent::ExecuteRejectedTransaction(inner) => Self {
+                activity_type: "execute_rejected_transaction".to_string(),
+                sequence_number: Some(inner.sequence_number),
+                account_address: Some(standardize_address(&inner.executor)),
+                num_rejections: Some(inner.num_rejections),
+                ..base
+            },
+            MultisigEvent::TransactionExecutionSucceeded(inner)
+            | MultisigEvent::TransactionExecutionFailed(inner) => Self {
+                activity_type: if matches!(
+                    multisig_event,
+                    MultisigEvent::TransactionExecutionSucceeded(_)
+                ) {
+                    "execute_transaction".to_string()
+                } else {
+                    "execute_transaction_failed".to_string()
+                },
+                sequence_number: Some(inner.sequence_number),
+                account_address: Some(standardize_address(&inner.executor)),
+                num_approvals: Some(inner.num_approvals),
+                ..base
+            },
+        })
+    }
+
+    /// Creation of the account, from the account written by a creation entry function
+    pub fn from_created_account(account: &CurrentMultisigAccount, creator_address: &str) -> Self {
+        Self {
+            transaction_version: account.last_transaction_version,
+            event_index: CREATE_ACCOUNT_EVENT_INDEX,
+            multisig_address: account.multisig_address.clone(),
+            activity_type: "create_account".to_string(),
+            sequence_number: None,
+            account_address: Some(standardize_address(creator_address)),
+            approved: None,
+            owners: Some(account.owners.clone()),
+            num_signatures_required: Some(account.num_signatures_required),
+            num_approvals: None,
+            num_rejections: None,
+            transaction_timestamp: account.last_transaction_timestamp,
+        }
+    }
+}
@@ -0,0 +1,50 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+#![allow(clippy::unused_unit)]
+
+use super::multisig_utils::MultisigEvent;
+use crate::{schema::multisig_transactions, utils::util::standardize_address};
+use aptos_protos::transaction::v1::Event;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+/// Transactions proposed to a multisig account. Votes and the outcome of the proposal are in
+/// multisig_activities, with the same sequence number.
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(multisig_address, sequence_number))]
+#[diesel(table_name = multisig_transactions)]
+pub struct MultisigTransaction {
+    pub multisig_address: String,
+    pub sequence_number: i64,
+    pub creator_address: String,
+    // Hex encoded payload, null if only the hash was proposed
+    pub payload: Option<String>,
+    pub payload_hash: Option<String>,
+    pub transaction_version: i64,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl MultisigTransaction {
+    pub fn from_multisig_event(
+        multisig_event: &MultisigEvent,
+        event: &Event,
+        txn_version: i64,
+        txn_timestamp: chrono::NaiveDateTime,
+    ) -> anyhow::Result<Option<Self>> {
+        let MultisigEvent::CreateTransaction(inner) = multisig_event else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            multisig_address: multisig_event.get_multisig_address(event)?,
+            sequence_number: inner.sequence_number,
+            creator_address: standardize_address(&inner.creator),
+            payload: inner.transaction.get_payload(),
+            payload_hash: inner.transaction.get_payload_hash(),
+            transaction_version: txn_version,
+            transaction_timestamp: txn_timestamp,
+        }))
+    }
+}
@@ -0,0 +1,285 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::utils::util::{deserialize_from_string, standardize_address};
+use anyhow::{Context, Result};
+use aptos_protos::transaction::v1::{Event, WriteResource};
+use serde::{Deserialize, Serialize};
+
+const MULTISIG_ACCOUNT_MODULE: &str = "0x1::multisig_account::";
+const MULTISIG_ACCOUNT_RESOURCE: &str = "0x1::multisig_account::MultisigAccount";
+
+/// Only the fields of `0x1::multisig_account::MultisigAccount` we need
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MultisigAccountResource {
+    pub owners: Vec<String>,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub num_signatures_required: i64,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub last_executed_sequence_number: i64,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub next_sequence_number: i64,
+}
+
+impl MultisigAccountResource {
+    pub fn from_write_resource(
+        write_resource: &WriteResource,
+        txn_version: i64,
+    ) -> Result<Option<Self>> {
+        if write_resource.type_str != MULTISIG_ACCOUNT_RESOURCE {
+            return Ok(None);
+        }
+        serde_json::from_str(&write_resource.data)
+            .map(Some)
+            .context(format!(
+                "version {} failed! failed to parse type {}, data {:?}",
+                txn_version, write_resource.type_str, write_resource.data
+            ))
+    }
+
+    pub fn get_owners(&self) -> Vec<String> {
+        self.owners
+            .iter()
+            .map(|owner| standardize_address(owner))
+            .collect()
+    }
+}
+
+/// Whether the entry function creates a multisig account, either a new one or from an existing
+/// account. `create_transaction` and `create_transaction_with_hash` propose a transaction.
+pub fn is_multisig_account_creation(entry_function_id_str: &str) -> bool {
+    entry_function_id_str
+        .strip_prefix(MULTISIG_ACCOUNT_MODULE)
+        .is_some_and(|function| function == "create" || function.starts_with("create_with_"))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct OptionalBytes {
+    vec: Vec<String>,
+}
+
+/// A proposed transaction. The payload is stored in full unless only its hash was proposed, in
+/// which case the payload is provided on execution.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MultisigTransactionResource {
+    payload: OptionalBytes,
+    payload_hash: OptionalBytes,
+    pub creator: String,
+}
+
+impl MultisigTransactionResource {
+    pub fn get_payload(&self) -> Option<String> {
+        self.payload.vec.first().cloned()
+    }
+
+    pub fn get_payload_hash(&self) -> Option<String> {
+        self.payload_hash.vec.first().cloned()
+    }
+}
+
+// The events below are emitted both through the event handles of the multisig account, named with
+// an `Event` suffix, and as module events, which also have the `multisig_account` field.
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AddOwners {
+    #[serde(default)]
+    pub multisig_account: Option<String>,
+    pub owners_added: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RemoveOwners {
+    #[serde(default)]
+    pub multisig_account: Option<String>,
+    pub owners_removed: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UpdateSignaturesRequired {
+    #[serde(default)]
+    pub multisig_account: Option<String>,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub old_num_signatures_required: i64,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub new_num_signatures_required: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreateTransaction {
+    #[serde(default)]
+    pub multisig_account: Option<String>,
+    pub creator: String,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub sequence_number: i64,
+    pub transaction: MultisigTransactionResource,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Vote {
+    #[serde(default)]
+    pub multisig_account: Option<String>,
+    pub owner: String,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub sequence_number: i64,
+    pub approved: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ExecuteRejectedTransaction {
+    #[serde(default)]
+    pub multisig_account: Option<String>,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub sequence_number: i64,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub num_rejections: i64,
+    pub executor: String,
+}
+
+/// Both a successful and a failed execution, the payload and the error aren't kept
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TransactionExecution {
+    #[serde(default)]
+    pub multisig_account: Option<String>,
+    pub executor: String,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub sequence_number: i64,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub num_approvals: i64,
+}
+
+#[derive(Debug, Clone)]
+pub enum MultisigEvent {
+    AddOwners(AddOwners),
+    RemoveOwners(RemoveOwners),
+    UpdateSignaturesRequired(UpdateSignaturesRequired),
+    CreateTransaction(CreateTransaction),
+    Vote(Vote),
+    ExecuteRejectedTransaction(ExecuteRejectedTransaction),
+    TransactionExecutionSucceeded(TransactionExecution),
+    TransactionExecutionFailed(TransactionExecution),
+}
+
+impl MultisigEvent {
+    pub fn from_event(event: &Event, txn_version: i64) -> Result<Option<Self>> {
+        let Some(name) = event.type_str.strip_prefix(MULTISIG_ACCOUNT_MODULE) else {
+            return Ok(None);
+        };
+        let name = name.strip_suffix("Event").unwrap_or(name);
+        let data = event.data.as_str();
+        let context = || {
+            format!(
+                "version {} failed! failed to parse type {}, data {:?}",
+                txn_version, event.type_str, data
+            )
+        };
+        let multisig_event = match name {
+            "AddOwners" => Self::AddOwners(serde_json::from_str(data).with_context(context)?),
+            "RemoveOwners" => Self::RemoveOwners(serde_json::from_str(data).with_context(context)?),
+            "UpdateSignaturesRequired" => {
+                Self::UpdateSignaturesRequired(serde_json::from_str(data).with_context(context)?)
+            },
+            "CreateTransaction" => {
+                Self::CreateTransaction(serde_json::from_str(data).with_context(context)?)
+            },
+            "Vote" => Self::Vote(serde_json::from_str(data).with_context(context)?),
+            "ExecuteRejectedTransaction" => {
+                Self::ExecuteRejectedTransaction(serde_json::from_str(data).with_context(context)?)
+            },
+            "TransactionExecutionSucceeded" => Self::TransactionExecutionSucceeded(
+                serde_json::from_str(data).with_context(context)?,
+            ),
+            "TransactionExecutionFailed" => {
+                Self::TransactionExecutionFailed(serde_json::from_str(data).with_context(context)?)
+            },
+            _ => return Ok(None),
+        };
+        Ok(Some(multisig_event))
+    }
+
+    /// The multisig account is in the module events, and is the account holding the event handle
+    /// otherwise
+    pub fn get_multisig_address(&self, event: &Event) -> Result<String> {
+        let multisig_account = match self {
+            Self::AddOwners(inner) => &inner.multisig_account,
+            Self::RemoveOwners(inner) => &inner.multisig_account,
+            Self::UpdateSignaturesRequired(inner) => &inner.multisig_account,
+            Self::CreateTransaction(inner) => &inner.multisig_account,
+            Self::Vote(inner) => &inner.multisig_account,
+            Self::ExecuteRejectedTransaction(inner) => &inner.multisig_account,
+            Self::TransactionExecutionSucceeded(inner)
+            | Self::TransactionExecutionFailed(inner) => &inner.multisig_account,
+        };
+        let address = match multisig_account {
+            Some(address) => address,
+            None => {
+                &event
+                    .key
+                    .as_ref()
+                    .context("event must have a key")?
+                    .account_address
+            },
+        };
+        Ok(standardize_address(address))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_protos::transaction::v1::EventKey;
+
+    #[test]
+    fn test_handle_and_module_events() {
+        let handle_event = Event {
+            key: Some(EventKey {
+                account_address: "0x5a1e".to_string(),
+                creation_number: 4,
+            }),
+            type_str: "0x1::multisig_account::VoteEvent".to_string(),
+            data: r#"{"owner":"0xa11ce","sequence_number":"3","approved":true}"#.to_string(),
+            ..Event::default()
+        };
+        let module_event = Event {
+            type_str: "0x1::multisig_account::Vote".to_string(),
+            data: r#"{"multisig_account":"0x5a1e","owner":"0xa11ce","sequence_number":"3","approved":true}"#
+                .to_string(),
+            ..Event::default()
+        };
+        for event in [handle_event, module_event] {
+            let multisig_event = MultisigEvent::from_event(&event, 1).unwrap().unwrap();
+            assert_eq!(
+                multisig_event.get_multisig_address(&event).unwrap(),
+                standardize_address("0x5a1e")
+            );
+            let MultisigEvent::Vote(vote) = multisig_event else {
+                panic!("Expected a vote, got {:?}", multisig_event);
+            };
+            assert_eq!(vote.sequence_number, 3);
+            assert!(vote.approved);
+        }
+        let metadata_updated = Event {
+            type_str: "0x1::multisig_account::MetadataUpdatedEvent".to_string(),
+            data: "{}".to_string(),
+            ..Event::default()
+        };
+        assert!(MultisigEvent::from_event(&metadata_updated, 1)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_is_multisig_account_creation() {
+        assert!(is_multisig_account_creation(
+            "0x1::multisig_account::create_with_owners"
+        ));
+        assert!(is_multisig_account_creation(
+            "0x1::multisig_account::create"
+        ));
+        assert!(!is_multisig_account_creation(
+            "0x1::multisig_account::create_transaction"
+        ));
+        assert!(!is_multisig_account_creation(
+            "0x1::aptos_account::create_account"
+        ));
+    }
+}
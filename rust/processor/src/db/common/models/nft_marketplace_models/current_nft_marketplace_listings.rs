@@ -0,0 +1,29 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+
+use crate::schema::current_nft_marketplace_listings;
+use bigdecimal::BigDecimal;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(listing_id))]
+#[diesel(table_name = current_nft_marketplace_listings)]
+pub struct CurrentNftMarketplaceListing {
+    pub listing_id: String,
+    pub token_data_id: String,
+    pub collection_id: String,
+    pub marketplace: String,
+    pub contract_address: String,
+    pub seller: String,
+    pub price: BigDecimal,
+    pub token_amount: BigDecimal,
+    pub token_standard: String,
+    pub listing_type: String,
+    pub is_deleted: bool,
+    pub last_transaction_version: i64,
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+}
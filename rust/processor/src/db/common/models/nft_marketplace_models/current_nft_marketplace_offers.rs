@@ -0,0 +1,49 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+
+use crate::schema::{
+    current_nft_marketplace_collection_offers, current_nft_marketplace_token_offers,
+};
+use bigdecimal::BigDecimal;
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(offer_id))]
+#[diesel(table_name = current_nft_marketplace_token_offers)]
+pub struct CurrentNftMarketplaceTokenOffer {
+    pub offer_id: String,
+    pub token_data_id: String,
+    pub collection_id: String,
+    pub marketplace: String,
+    pub contract_address: String,
+    pub buyer: String,
+    pub price: BigDecimal,
+    // Only known when the offer resource is written in the same transaction, i.e. on placement
+    pub expiration_time: Option<BigDecimal>,
+    pub token_standard: String,
+    pub is_deleted: bool,
+    pub last_transaction_version: i64,
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(collection_offer_id))]
+#[diesel(table_name = current_nft_marketplace_collection_offers)]
+pub struct CurrentNftMarketplaceCollectionOffer {
+    pub collection_offer_id: String,
+    pub collection_id: String,
+    pub marketplace: String,
+    pub contract_address: String,
+    pub buyer: String,
+    pub item_price: BigDecimal,
+    pub remaining_token_amount: BigDecimal,
+    pub expiration_time: Option<BigDecimal>,
+    pub token_standard: String,
+    pub is_deleted: bool,
+    pub last_transaction_version: i64,
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+}
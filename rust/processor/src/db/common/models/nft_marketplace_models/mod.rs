@@ -0,0 +1,7 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod current_nft_marketplace_listings;
+pub mod current_nft_marketplace_offers;
+pub mod nft_marketplace_activities;
+pub mod nft_marketplace_utils;
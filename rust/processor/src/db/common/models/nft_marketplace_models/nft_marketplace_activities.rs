@@ -0,0 +1,432 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+
+use super::{
+    current_nft_marketplace_listings::CurrentNftMarketplaceListing,
+    current_nft_marketplace_offers::{
+        CurrentNftMarketplaceCollectionOffer, CurrentNftMarketplaceTokenOffer,
+    },
+    nft_marketplace_utils::{
+        CollectionIdentifiers, ListingEvent, MarketplaceConfig, MarketplaceEvent, OfferResource,
+        TokenIdentifiers, TokenOfferEvent,
+    },
+};
+use crate::{
+    schema::nft_marketplace_activities,
+    utils::util::{get_entry_function_from_user_request, parse_timestamp, standardize_address},
+};
+use ahash::AHashMap;
+use aptos_protos::transaction::v1::{transaction::TxnData, write_set_change::Change, Transaction};
+use bigdecimal::{BigDecimal, One, Zero};
+use field_count::FieldCount;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Deserialize, FieldCount, Identifiable, Insertable, Serialize)]
+#[diesel(primary_key(transaction_version, event_index))]
+#[diesel(table_name = nft_marketplace_activities)]
+pub struct NftMarketplaceActivity {
+    pub transaction_version: i64,
+    pub event_index: i64,
+    pub marketplace: String,
+    pub contract_address: String,
+    pub event_type: String,
+    pub offer_or_listing_id: String,
+    pub collection_id: String,
+    // Null for collection offers placed or canceled
+    pub token_data_id: Option<String>,
+    pub creator_address: String,
+    pub collection_name: String,
+    pub token_name: Option<String>,
+    pub property_version: Option<BigDecimal>,
+    pub price: BigDecimal,
+    pub token_amount: BigDecimal,
+    pub token_standard: String,
+    pub seller: Option<String>,
+    pub buyer: Option<String>,
+    pub entry_function_id_str: Option<String>,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+}
+
+pub type CurrentNftMarketplaceListingPK = String;
+pub type CurrentNftMarketplaceTokenOfferPK = String;
+pub type CurrentNftMarketplaceCollectionOfferPK = String;
+
+#[derive(Default)]
+pub struct NftMarketplaceChanges {
+    pub activities: Vec<NftMarketplaceActivity>,
+    pub current_listings: AHashMap<CurrentNftMarketplaceListingPK, CurrentNftMarketplaceListing>,
+    pub current_token_offers:
+        AHashMap<CurrentNftMarketplaceTokenOfferPK, CurrentNftMarketplaceTokenOffer>,
+    pub current_collection_offers:
+        AHashMap<CurrentNftMarketplaceCollectionOfferPK, CurrentNftMarketplaceCollectionOffer>,
+}
+
+impl NftMarketplaceActivity {
+    fn from_token(
+        context: &EventContext,
+        event_index: i64,
+        event: &MarketplaceEvent,
+        token: &TokenIdentifiers,
+        offer_or_listing_id: &str,
+        price: &BigDecimal,
+        seller: Option<&String>,
+        buyer: Option<&String>,
+    ) -> Self {
+        Self {
+            transaction_version: context.txn_version,
+            event_index,
+            marketplace: context.marketplace.name.clone(),
+            contract_address: context.contract_address.to_string(),
+            event_type: event.get_event_type().to_string(),
+            offer_or_listing_id: standardize_address(offer_or_listing_id),
+            collection_id: token.collection_id.clone(),
+            token_data_id: Some(token.token_data_id.clone()),
+            creator_address: token.creator_address.clone(),
+            collection_name: token.collection_name.clone(),
+            token_name: Some(token.token_name.clone()),
+            property_version: token.property_version.clone(),
+            price: price.clone(),
+            token_amount: BigDecimal::one(),
+            token_standard: token.token_standard.clone(),
+            seller: seller.map(|address| standardize_address(address)),
+            buyer: buyer.map(|address| standardize_address(address)),
+            entry_function_id_str: context.entry_function_id_str.clone(),
+            transaction_timestamp: context.txn_timestamp,
+        }
+    }
+
+    fn from_collection(
+        context: &EventContext,
+        event_index: i64,
+        event: &MarketplaceEvent,
+        collection: &CollectionIdentifiers,
+        offer_id: &str,
+        price: &BigDecimal,
+        token_amount: &BigDecimal,
+        buyer: &str,
+    ) -> Self {
+        Self {
+            transaction_version: context.txn_version,
+            event_index,
+            marketplace: context.marketplace.name.clone(),
+            contract_address: context.contract_address.to_string(),
+            event_type: event.get_event_type().to_string(),
+            offer_or_listing_id: standardize_address(offer_id),
+            collection_id: collection.collection_id.clone(),
+            token_data_id: None,
+            creator_address: collection.creator_address.clone(),
+            collection_name: collection.collection_name.clone(),
+            token_name: None,
+            property_version: None,
+            price: price.clone(),
+            token_amount: token_amount.clone(),
+            token_standard: collection.token_standard.clone(),
+            seller: None,
+            buyer: Some(standardize_address(buyer)),
+            entry_function_id_str: context.entry_function_id_str.clone(),
+            transaction_timestamp: context.txn_timestamp,
+        }
+    }
+
+    /// Parses the events of the configured marketplaces into activities and the latest state of
+    /// the listings and offers they touch. Listings and offers are objects, so the offer
+    /// resources written by the transaction are used for the fields the events don't have.
+    pub fn from_transaction(
+        transaction: &Transaction,
+        marketplaces: &[MarketplaceConfig],
+        changes: &mut NftMarketplaceChanges,
+    ) -> anyhow::Result<()> {
+        let user_txn = match transaction.txn_data.as_ref() {
+            Some(TxnData::User(user_txn)) => user_txn,
+            _ => return Ok(()),
+        };
+        let txn_version = transaction.version as i64;
+        let txn_timestamp = parse_timestamp(transaction.timestamp.as_ref().unwrap(), txn_version);
+        let entry_function_id_str = user_txn
+            .request
+            .as_ref()
+            .and_then(get_entry_function_from_user_request);
+
+        for marketplace in marketplaces {
+            let contract_address = marketplace.get_contract_address();
+            let context = EventContext {
+                marketplace,
+                contract_address: &contract_address,
+                txn_version,
+                txn_timestamp,
+                entry_function_id_str: &entry_function_id_str,
+            };
+            let mut offer_resources = AHashMap::new();
+            for wsc in transaction.info.as_ref().unwrap().changes.iter() {
+                if let Some(Change::WriteResource(write_resource)) = wsc.change.as_ref() {
+                    if let Some(offer) = OfferResource::from_write_resource(
+                        marketplace,
+                        write_resource,
+                        txn_version,
+                    )? {
+                        offer_resources.insert(standardize_address(&write_resource.address), offer);
+                    }
+                }
+            }
+
+            for (index, event) in user_txn.events.iter().enumerate() {
+                let marketplace_event = match MarketplaceEvent::from_event(
+                    marketplace,
+                    event.type_str.as_str(),
+                    &event.data,
+                    txn_version,
+                )? {
+                    Some(marketplace_event) => marketplace_event,
+                    None => continue,
+                };
+                let event_index = index as i64;
+
+                let activity = match &marketplace_event {
+                    MarketplaceEvent::ListingPlaced(inner)
+                    | MarketplaceEvent::ListingCanceled(inner)
+                    | MarketplaceEvent::ListingFilled(inner) => {
+                        let token = inner.token_metadata.get_identifiers();
+                        let is_deleted =
+                            !matches!(marketplace_event, MarketplaceEvent::ListingPlaced(_));
+                        let listing = context.listing(inner, &token, is_deleted);
+                        changes
+                            .current_listings
+                            .insert(listing.listing_id.clone(), listing);
+                        Self::from_token(
+                            &context,
+                            event_index,
+                            &marketplace_event,
+                            &token,
+                            &inner.listing,
+                            &inner.price,
+                            Some(&inner.seller),
+                            inner.purchaser.as_ref(),
+                        )
+                    },
+                    MarketplaceEvent::TokenOfferPlaced(inner)
+                    | MarketplaceEvent::TokenOfferCanceled(inner)
+                    | MarketplaceEvent::TokenOfferFilled(inner) => {
+                        let token = inner.token_metadata.get_identifiers();
+                        let is_deleted =
+                            !matches!(marketplace_event, MarketplaceEvent::TokenOfferPlaced(_));
+                        let offer_id = standardize_address(&inner.token_offer);
+                        changes.merge_token_offer(context.token_offer(
+                            inner,
+                            &token,
+                            offer_resources.get(&offer_id),
+                            is_deleted,
+                        ));
+                        Self::from_token(
+                            &context,
+                            event_index,
+                            &marketplace_event,
+                            &token,
+                            &inner.token_offer,
+                            &inner.price,
+                            inner.seller.as_ref(),
+                            Some(&inner.purchaser),
+                        )
+                    },
+                    MarketplaceEvent::CollectionOfferPlaced(inner) => {
+                        let collection = inner.collection_metadata.get_identifiers();
+                        let offer_id = standardize_address(&inner.collection_offer);
+                        let offer = context.collection_offer(
+                            &offer_id,
+                            &collection,
+                            &inner.purchaser,
+                            &inner.price,
+                            inner.token_amount.clone(),
+                            offer_resources.get(&offer_id),
+                        );
+                        changes.merge_collection_offer(offer);
+                        Self::from_collection(
+                            &context,
+                            event_index,
+                            &marketplace_event,
+                            &collection,
+                            &inner.collection_offer,
+                            &inner.price,
+                            &inner.token_amount,
+                            &inner.purchaser,
+                        )
+                    },
+                    MarketplaceEvent::CollectionOfferCanceled(inner) => {
+                        let collection = inner.collection_metadata.get_identifiers();
+                        let offer_id = standardize_address(&inner.collection_offer);
+                        let mut offer = context.collection_offer(
+                            &offer_id,
+                            &collection,
+                            &inner.purchaser,
+                            &inner.price,
+                            inner.remaining_token_amount.clone(),
+                            None,
+                        );
+                        offer.is_deleted = true;
+                        changes.merge_collection_offer(offer);
+                        Self::from_collection(
+                            &context,
+                            event_index,
+                            &marketplace_event,
+                            &collection,
+                            &inner.collection_offer,
+                            &inner.price,
+                            &inner.remaining_token_amount,
+                            &inner.purchaser,
+                        )
+                    },
+                    MarketplaceEvent::CollectionOfferFilled(inner) => {
+                        let token = inner.token_metadata.get_identifiers();
+                        let offer_id = standardize_address(&inner.collection_offer);
+                        // The offer resource is deleted with the last fill
+                        let offer_resource = offer_resources.get(&offer_id);
+                        let remaining_token_amount = offer_resource
+                            .and_then(|offer| offer.remaining.clone())
+                            .unwrap_or_else(BigDecimal::zero);
+                        let collection = CollectionIdentifiers {
+                            collection_id: token.collection_id.clone(),
+                            creator_address: token.creator_address.clone(),
+                            collection_name: token.collection_name.clone(),
+                            token_standard: token.token_standard.clone(),
+                        };
+                        let offer = context.collection_offer(
+                            &offer_id,
+                            &collection,
+                            &inner.purchaser,
+                            &inner.price,
+                            remaining_token_amount,
+                            offer_resource,
+                        );
+                        changes.merge_collection_offer(offer);
+                        Self::from_token(
+                            &context,
+                            event_index,
+                            &marketplace_event,
+                            &token,
+                            &inner.collection_offer,
+                            &inner.price,
+                            Some(&inner.seller),
+                            Some(&inner.purchaser),
+                        )
+                    },
+                };
+                changes.activities.push(activity);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl NftMarketplaceChanges {
+    /// Keeps the expiration time of an earlier change of the same batch, the events don't have it
+    fn merge_token_offer(&mut self, mut offer: CurrentNftMarketplaceTokenOffer) {
+        if let Some(previous) = self.current_token_offers.get(&offer.offer_id) {
+            if offer.expiration_time.is_none() {
+                offer.expiration_time = previous.expiration_time.clone();
+            }
+        }
+        self.current_token_offers
+            .insert(offer.offer_id.clone(), offer);
+    }
+
+    fn merge_collection_offer(&mut self, mut offer: CurrentNftMarketplaceCollectionOffer) {
+        if let Some(previous) = self
+            .current_collection_offers
+            .get(&offer.collection_offer_id)
+        {
+            if offer.expiration_time.is_none() {
+                offer.expiration_time = previous.expiration_time.clone();
+            }
+        }
+        self.current_collection_offers
+            .insert(offer.collection_offer_id.clone(), offer);
+    }
+}
+
+/// Fields shared by the activity and the current listing or offer of a marketplace event
+struct EventContext<'a> {
+    marketplace: &'a MarketplaceConfig,
+    contract_address: &'a str,
+    txn_version: i64,
+    txn_timestamp: chrono::NaiveDateTime,
+    entry_function_id_str: &'a Option<String>,
+}
+
+impl EventContext<'_> {
+    fn listing(
+        &self,
+        event: &ListingEvent,
+        token: &TokenIdentifiers,
+        is_deleted: bool,
+    ) -> CurrentNftMarketplaceListing {
+        CurrentNftMarketplaceListing {
+            listing_id: standardize_address(&event.listing),
+            token_data_id: token.token_data_id.clone(),
+            collection_id: token.collection_id.clone(),
+            marketplace: self.marketplace.name.clone(),
+            contract_address: self.contract_address.to_string(),
+            seller: standardize_address(&event.seller),
+            price: event.price.clone(),
+            token_amount: if is_deleted {
+                BigDecimal::zero()
+            } else {
+                BigDecimal::one()
+            },
+            token_standard: token.token_standard.clone(),
+            listing_type: event.listing_type.clone(),
+            is_deleted,
+            last_transaction_version: self.txn_version,
+            last_transaction_timestamp: self.txn_timestamp,
+        }
+    }
+
+    fn token_offer(
+        &self,
+        event: &TokenOfferEvent,
+        token: &TokenIdentifiers,
+        offer_resource: Option<&OfferResource>,
+        is_deleted: bool,
+    ) -> CurrentNftMarketplaceTokenOffer {
+        CurrentNftMarketplaceTokenOffer {
+            offer_id: standardize_address(&event.token_offer),
+            token_data_id: token.token_data_id.clone(),
+            collection_id: token.collection_id.clone(),
+            marketplace: self.marketplace.name.clone(),
+            contract_address: self.contract_address.to_string(),
+            buyer: standardize_address(&event.purchaser),
+            price: event.price.clone(),
+            expiration_time: offer_resource.map(|offer| offer.expiration_time.clone()),
+            token_standard: token.token_standard.clone(),
+            is_deleted,
+            last_transaction_version: self.txn_version,
+            last_transaction_timestamp: self.txn_timestamp,
+        }
+    }
+
+    fn collection_offer(
+        &self,
+        offer_id: &str,
+        collection: &CollectionIdentifiers,
+        buyer: &str,
+        item_price: &BigDecimal,
+        remaining_token_amount: BigDecimal,
+        offer_resource: Option<&OfferResource>,
+    ) -> CurrentNftMarketplaceCollectionOffer {
+        CurrentNftMarketplaceCollectionOffer {
+            collection_offer_id: offer_id.to_string(),
+            collection_id: collection.collection_id.clone(),
+            marketplace: self.marketplace.name.clone(),
+            contract_address: self.contract_address.to_string(),
+            buyer: standardize_address(buyer),
+            item_price: item_price.clone(),
+            is_deleted: remaining_token_amount.is_zero(),
+            remaining_token_amount,
+            expiration_time: offer_resource.map(|offer| offer.expiration_time.clone()),
+            token_standard: collection.token_standard.clone(),
+            last_transaction_version: self.txn_version,
+            last_transaction_timestamp: self.txn_timestamp,
+        }
+    }
+}
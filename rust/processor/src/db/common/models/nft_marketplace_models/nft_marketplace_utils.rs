@@ -0,0 +1,396 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+
+use crate::{
+    db::common::models::{
+        token_models::token_utils::{CollectionDataIdType, TokenDataIdType, NAME_LENGTH},
+        token_v2_models::v2_token_utils::{ResourceReference, TokenStandard},
+    },
+    utils::util::{deserialize_from_string, standardize_address, truncate_str},
+};
+use anyhow::{Context, Result};
+use aptos_protos::transaction::v1::WriteResource;
+use bigdecimal::BigDecimal;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// A marketplace contract to index, deployed from the aptos marketplace example
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MarketplaceConfig {
+    // Name written to the marketplace column, e.g. "example_marketplace"
+    pub name: String,
+    pub contract_address: String,
+}
+
+impl MarketplaceConfig {
+    /// Whether the move type is declared by the marketplace contract
+    pub fn is_contract_type(&self, move_type: &str) -> bool {
+        move_type
+            .split("::")
+            .next()
+            .is_some_and(|address| standardize_address(address) == self.get_contract_address())
+    }
+
+    pub fn get_contract_address(&self) -> String {
+        standardize_address(&self.contract_address)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct MoveOption<T> {
+    vec: Vec<T>,
+}
+
+impl<T: Clone> MoveOption<T> {
+    fn get(&self) -> Option<T> {
+        self.vec.first().cloned()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct U64Wrapper(#[serde(deserialize_with = "deserialize_from_string")] BigDecimal);
+
+fn deserialize_optional_u64<'de, D>(deserializer: D) -> Result<Option<BigDecimal>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let option = MoveOption::<U64Wrapper>::deserialize(deserializer)?;
+    Ok(option.get().map(|wrapper| wrapper.0))
+}
+
+/// Token identifiers resolved the same way as in token_datas_v2, so activities can be joined
+/// with the token tables for both token standards
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenIdentifiers {
+    pub token_data_id: String,
+    pub collection_id: String,
+    pub creator_address: String,
+    pub collection_name: String,
+    pub token_name: String,
+    pub property_version: Option<BigDecimal>,
+    pub token_standard: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CollectionIdentifiers {
+    pub collection_id: String,
+    pub creator_address: String,
+    pub collection_name: String,
+    pub token_standard: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TokenMetadata {
+    creator_address: String,
+    collection_name: String,
+    collection: MoveOption<ResourceReference>,
+    token_name: String,
+    token: MoveOption<ResourceReference>,
+    #[serde(deserialize_with = "deserialize_optional_u64")]
+    property_version: Option<BigDecimal>,
+}
+
+impl TokenMetadata {
+    /// v2 tokens are objects, v1 tokens are identified by the hash of creator, collection and name
+    pub fn get_identifiers(&self) -> TokenIdentifiers {
+        let creator_address = standardize_address(&self.creator_address);
+        let collection_name = truncate_str(&self.collection_name, NAME_LENGTH);
+        let token_name = truncate_str(&self.token_name, NAME_LENGTH);
+        match (self.token.get(), self.collection.get()) {
+            (Some(token), Some(collection)) => TokenIdentifiers {
+                token_data_id: token.get_reference_address(),
+                collection_id: collection.get_reference_address(),
+                creator_address,
+                collection_name,
+                token_name,
+                property_version: self.property_version.clone(),
+                token_standard: TokenStandard::V2.to_string(),
+            },
+            _ => {
+                let token_data_id_type = TokenDataIdType::new(
+                    self.creator_address.clone(),
+                    self.collection_name.clone(),
+                    self.token_name.clone(),
+                );
+                TokenIdentifiers {
+                    token_data_id: token_data_id_type.to_id(),
+                    collection_id: token_data_id_type.get_collection_id(),
+                    creator_address,
+                    collection_name,
+                    token_name,
+                    property_version: self.property_version.clone(),
+                    token_standard: TokenStandard::V1.to_string(),
+                }
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CollectionMetadata {
+    creator_address: String,
+    collection_name: String,
+    collection: MoveOption<ResourceReference>,
+}
+
+impl CollectionMetadata {
+    pub fn get_identifiers(&self) -> CollectionIdentifiers {
+        let creator_address = standardize_address(&self.creator_address);
+        let collection_name = truncate_str(&self.collection_name, NAME_LENGTH);
+        match self.collection.get() {
+            Some(collection) => CollectionIdentifiers {
+                collection_id: collection.get_reference_address(),
+                creator_address,
+                collection_name,
+                token_standard: TokenStandard::V2.to_string(),
+            },
+            None => CollectionIdentifiers {
+                collection_id: CollectionDataIdType::new(
+                    self.creator_address.clone(),
+                    self.collection_name.clone(),
+                )
+                .to_id(),
+                creator_address,
+                collection_name,
+                token_standard: TokenStandard::V1.to_string(),
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ListingEvent {
+    // fixed price or auction
+    #[serde(rename = "type")]
+    pub listing_type: String,
+    pub listing: String,
+    pub seller: String,
+    pub purchaser: Option<String>,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub price: BigDecimal,
+    pub token_metadata: TokenMetadata,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TokenOfferEvent {
+    pub token_offer: String,
+    pub purchaser: String,
+    pub seller: Option<String>,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub price: BigDecimal,
+    pub token_metadata: TokenMetadata,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CollectionOfferPlacedEvent {
+    pub collection_offer: String,
+    pub purchaser: String,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub price: BigDecimal,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub token_amount: BigDecimal,
+    pub collection_metadata: CollectionMetadata,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CollectionOfferCanceledEvent {
+    pub collection_offer: String,
+    pub purchaser: String,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub price: BigDecimal,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub remaining_token_amount: BigDecimal,
+    pub collection_metadata: CollectionMetadata,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CollectionOfferFilledEvent {
+    pub collection_offer: String,
+    pub purchaser: String,
+    pub seller: String,
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub price: BigDecimal,
+    pub token_metadata: TokenMetadata,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum MarketplaceEvent {
+    ListingPlaced(ListingEvent),
+    ListingCanceled(ListingEvent),
+    ListingFilled(ListingEvent),
+    TokenOfferPlaced(TokenOfferEvent),
+    TokenOfferCanceled(TokenOfferEvent),
+    TokenOfferFilled(TokenOfferEvent),
+    CollectionOfferPlaced(CollectionOfferPlacedEvent),
+    CollectionOfferCanceled(CollectionOfferCanceledEvent),
+    CollectionOfferFilled(CollectionOfferFilledEvent),
+}
+
+impl MarketplaceEvent {
+    /// Parses the events of the `events` module of the marketplace contract. Event names are
+    /// matched with and without the `Event` suffix as it changed between contract versions.
+    pub fn from_event(
+        marketplace: &MarketplaceConfig,
+        data_type: &str,
+        data: &str,
+        txn_version: i64,
+    ) -> Result<Option<Self>> {
+        if !marketplace.is_contract_type(data_type) {
+            return Ok(None);
+        }
+        let event_name = match data_type.split("::").collect::<Vec<_>>().as_slice() {
+            [_, "events", name] => name.strip_suffix("Event").unwrap_or(*name).to_string(),
+            _ => return Ok(None),
+        };
+        match event_name.as_str() {
+            "ListingPlaced" => serde_json::from_str(data).map(|e| Some(Self::ListingPlaced(e))),
+            "ListingCanceled" => serde_json::from_str(data).map(|e| Some(Self::ListingCanceled(e))),
+            "ListingFilled" => serde_json::from_str(data).map(|e| Some(Self::ListingFilled(e))),
+            "TokenOfferPlaced" => {
+                serde_json::from_str(data).map(|e| Some(Self::TokenOfferPlaced(e)))
+            },
+            "TokenOfferCanceled" => {
+                serde_json::from_str(data).map(|e| Some(Self::TokenOfferCanceled(e)))
+            },
+            "TokenOfferFilled" => {
+                serde_json::from_str(data).map(|e| Some(Self::TokenOfferFilled(e)))
+            },
+            "CollectionOfferPlaced" => {
+                serde_json::from_str(data).map(|e| Some(Self::CollectionOfferPlaced(e)))
+            },
+            "CollectionOfferCanceled" => {
+                serde_json::from_str(data).map(|e| Some(Self::CollectionOfferCanceled(e)))
+            },
+            "CollectionOfferFilled" => {
+                serde_json::from_str(data).map(|e| Some(Self::CollectionOfferFilled(e)))
+            },
+            _ => Ok(None),
+        }
+        .context(format!(
+            "version {} failed! failed to parse type {}, data {:?}",
+            txn_version, data_type, data
+        ))
+    }
+
+    /// Short event type written to nft_marketplace_activities, e.g. listing_placed
+    pub fn get_event_type(&self) -> &'static str {
+        match self {
+            Self::ListingPlaced(_) => "listing_placed",
+            Self::ListingCanceled(_) => "listing_canceled",
+            Self::ListingFilled(_) => "listing_filled",
+            Self::TokenOfferPlaced(_) => "token_offer_placed",
+            Self::TokenOfferCanceled(_) => "token_offer_canceled",
+            Self::TokenOfferFilled(_) => "token_offer_filled",
+            Self::CollectionOfferPlaced(_) => "collection_offer_placed",
+            Self::CollectionOfferCanceled(_) => "collection_offer_canceled",
+            Self::CollectionOfferFilled(_) => "collection_offer_filled",
+        }
+    }
+}
+
+/// The `TokenOffer` and `CollectionOffer` resources, only the fields missing from the events
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OfferResource {
+    #[serde(deserialize_with = "deserialize_from_string")]
+    pub expiration_time: BigDecimal,
+    // Only on collection offers
+    #[serde(default, deserialize_with = "deserialize_optional_remaining")]
+    pub remaining: Option<BigDecimal>,
+}
+
+fn deserialize_optional_remaining<'de, D>(deserializer: D) -> Result<Option<BigDecimal>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_from_string(deserializer).map(Some)
+}
+
+impl OfferResource {
+    pub fn from_write_resource(
+        marketplace: &MarketplaceConfig,
+        write_resource: &WriteResource,
+        txn_version: i64,
+    ) -> Result<Option<Self>> {
+        let type_str = write_resource.type_str.as_str();
+        if !marketplace.is_contract_type(type_str)
+            || !(type_str.ends_with("::token_offer::TokenOffer")
+                || type_str.ends_with("::collection_offer::CollectionOffer"))
+        {
+            return Ok(None);
+        }
+        serde_json::from_str(write_resource.data.as_str())
+            .map(Some)
+            .context(format!(
+                "version {} failed! failed to parse type {}, data {:?}",
+                txn_version, type_str, write_resource.data
+            ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marketplace() -> MarketplaceConfig {
+        MarketplaceConfig {
+            name: "example_marketplace".to_string(),
+            contract_address: "0x6de37368e31dff4580b211295198159ee6f98b42ffa93c5683bb955ca1be67e0"
+                .to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_listing_placed_v1_and_v2() {
+        let event_type = "0x6de37368e31dff4580b211295198159ee6f98b42ffa93c5683bb955ca1be67e0::events::ListingPlacedEvent";
+        let v2_data = r#"{"listing":"0xa","price":"100","seller":"0xb","type":"fixed price","token_metadata":{"collection":{"vec":[{"inner":"0xc"}]},"collection_name":"Collection","creator_address":"0xd","property_version":{"vec":[]},"token":{"vec":[{"inner":"0xe"}]},"token_name":"Token"}}"#;
+        let v1_data = r#"{"listing":"0xa","price":"100","seller":"0xb","type":"fixed price","token_metadata":{"collection":{"vec":[]},"collection_name":"Collection","creator_address":"0xd","property_version":{"vec":["0"]},"token":{"vec":[]},"token_name":"Token"}}"#;
+
+        let v2_event = MarketplaceEvent::from_event(&marketplace(), event_type, v2_data, 1)
+            .unwrap()
+            .unwrap();
+        let MarketplaceEvent::ListingPlaced(v2_listing) = v2_event else {
+            panic!("Expected a listing placed event");
+        };
+        let v2_identifiers = v2_listing.token_metadata.get_identifiers();
+        assert_eq!(v2_identifiers.token_data_id, standardize_address("0xe"));
+        assert_eq!(v2_identifiers.collection_id, standardize_address("0xc"));
+        assert_eq!(v2_identifiers.token_standard, "v2");
+        assert_eq!(v2_identifiers.property_version, None);
+
+        let MarketplaceEvent::ListingPlaced(v1_listing) =
+            MarketplaceEvent::from_event(&marketplace(), event_type, v1_data, 1)
+                .unwrap()
+                .unwrap()
+        else {
+            panic!("Expected a listing placed event");
+        };
+        let v1_identifiers = v1_listing.token_metadata.get_identifiers();
+        let token_data_id_type = TokenDataIdType::new(
+            "0xd".to_string(),
+            "Collection".to_string(),
+            "Token".to_string(),
+        );
+        assert_eq!(v1_identifiers.token_data_id, token_data_id_type.to_id());
+        assert_eq!(
+            v1_identifiers.collection_id,
+            token_data_id_type.get_collection_id()
+        );
+        assert_eq!(v1_identifiers.token_standard, "v1");
+        assert_eq!(v1_identifiers.property_version, Some(BigDecimal::from(0)));
+    }
+
+    #[test]
+    fn test_ignore_other_contracts() {
+        assert!(MarketplaceEvent::from_event(
+            &marketplace(),
+            "0x1::events::ListingPlacedEvent",
+            "{}",
+            1
+        )
+        .unwrap()
+        .is_none());
+    }
+}
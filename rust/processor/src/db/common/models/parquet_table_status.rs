@@ -0,0 +1,42 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(clippy::extra_unused_lifetimes)]
+
+use crate::{schema::parquet_table_status, utils::database::DbPoolConnection};
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+#[derive(AsChangeset, Debug, Insertable)]
+#[diesel(table_name = parquet_table_status)]
+/// Only tracking the latest version of the table that has been fully uploaded
+pub struct ParquetTableStatus {
+    pub processor: String,
+    pub table_name: String,
+    pub last_success_version: i64,
+}
+
+#[derive(AsChangeset, Debug, Queryable)]
+#[diesel(table_name = parquet_table_status)]
+/// Only tracking the latest version of the table that has been fully uploaded
+pub struct ParquetTableStatusQuery {
+    pub processor: String,
+    pub table_name: String,
+    pub last_success_version: i64,
+    pub last_updated: chrono::NaiveDateTime,
+}
+
+impl ParquetTableStatusQuery {
+    pub async fn get_by_processor_and_table(
+        processor_name: &str,
+        table_name: &str,
+        conn: &mut DbPoolConnection<'_>,
+    ) -> diesel::QueryResult<Option<Self>> {
+        parquet_table_status::table
+            .filter(parquet_table_status::processor.eq(processor_name))
+            .filter(parquet_table_status::table_name.eq(table_name))
+            .first::<Self>(conn)
+            .await
+            .optional()
+    }
+}
@@ -0,0 +1,142 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(clippy::extra_unused_lifetimes)]
+
+use crate::{schema::parquet_upload_manifests, utils::database::DbPoolConnection};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+#[derive(Clone, Debug, Insertable)]
+#[diesel(table_name = parquet_upload_manifests)]
+/// A contiguous range of transaction versions whose structs are all in the uploaded file. Ranges
+/// without any struct for the table have no file.
+pub struct ParquetUploadManifest {
+    pub processor: String,
+    pub table_name: String,
+    pub start_version: i64,
+    pub end_version: i64,
+    pub file_path: Option<String>,
+    pub num_records: i64,
+    pub file_size: i64,
+}
+
+impl ParquetUploadManifest {
+    /// Returns the version ranges covered by the uploaded files of a table that end at or after
+    /// `from_version`, ordered by start version.
+    pub async fn get_uploaded_ranges(
+        processor_name: &str,
+        table_name: &str,
+        from_version: i64,
+        conn: &mut DbPoolConnection<'_>,
+    ) -> diesel::QueryResult<Vec<(i64, i64)>> {
+        parquet_upload_manifests::table
+            .filter(parquet_upload_manifests::processor.eq(processor_name))
+            .filter(parquet_upload_manifests::table_name.eq(table_name))
+            .filter(parquet_upload_manifests::end_version.ge(from_version))
+            .select((
+                parquet_upload_manifests::start_version,
+                parquet_upload_manifests::end_version,
+            ))
+            .order(parquet_upload_manifests::start_version.asc())
+            .load::<(i64, i64)>(conn)
+            .await
+    }
+
+    /// Deletes the manifests of a table that end before `before_version`. The table checkpoint
+    /// already covers them, so they aren't needed to work out where the table resumes from.
+    pub async fn delete_before(
+        processor_name: &str,
+        table_name: &str,
+        before_version: i64,
+        conn: &mut DbPoolConnection<'_>,
+    ) -> diesel::QueryResult<usize> {
+        diesel::delete(
+            parquet_upload_manifests::table
+                .filter(parquet_upload_manifests::processor.eq(processor_name))
+                .filter(parquet_upload_manifests::table_name.eq(table_name))
+                .filter(parquet_upload_manifests::end_version.lt(before_version)),
+        )
+        .execute(conn)
+        .await
+    }
+
+    /// Returns the version a table has to resume from, i.e. the first version that isn't covered
+    /// by a contiguous run of uploaded files. Returns None if nothing was uploaded for the table.
+    pub async fn get_resume_version(
+        processor_name: &str,
+        table_name: &str,
+        conn: &mut DbPoolConnection<'_>,
+    ) -> diesel::QueryResult<Option<i64>> {
+        let ranges = Self::get_uploaded_ranges(processor_name, table_name, 0, conn).await?;
+        Ok(resume_version_from_ranges(&ranges))
+    }
+}
+
+/// Walks the ranges (ordered by start version) from the first uploaded version and stops at
+/// the first hole. Ranges may overlap when a batch was uploaded twice, e.g. after a restart.
+pub fn resume_version_from_ranges(ranges: &[(i64, i64)]) -> Option<i64> {
+    let first_version = ranges.first()?.0;
+    Some(contiguous_version_from_ranges(ranges, first_version))
+}
+
+/// Returns the first version at or after `next_version` that isn't covered by the ranges.
+pub fn contiguous_version_from_ranges(ranges: &[(i64, i64)], mut next_version: i64) -> i64 {
+    for (start_version, end_version) in ranges {
+        if *start_version > next_version {
+            break;
+        }
+        next_version = next_version.max(end_version + 1);
+    }
+    next_version
+}
+
+/// Merges the batches buffered in a file into the contiguous ranges recorded in the manifest.
+pub fn merge_version_ranges(mut ranges: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+    ranges.sort_unstable();
+    let mut merged: Vec<(i64, i64)> = vec![];
+    for (start_version, end_version) in ranges {
+        match merged.last_mut() {
+            Some(last) if start_version <= last.1 + 1 => last.1 = last.1.max(end_version),
+            _ => merged.push((start_version, end_version)),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resume_version_from_ranges() {
+        assert_eq!(resume_version_from_ranges(&[]), None);
+        assert_eq!(resume_version_from_ranges(&[(0, 99)]), Some(100));
+        assert_eq!(
+            resume_version_from_ranges(&[(0, 99), (100, 199), (150, 249)]),
+            Some(250)
+        );
+        // 200-299 was never uploaded, everything after it has to be reprocessed
+        assert_eq!(
+            resume_version_from_ranges(&[(0, 99), (100, 199), (300, 399)]),
+            Some(200)
+        );
+    }
+
+    #[test]
+    fn test_contiguous_version_from_ranges() {
+        let ranges = [(0, 99), (100, 199), (300, 399)];
+        assert_eq!(contiguous_version_from_ranges(&ranges, 50), 200);
+        assert_eq!(contiguous_version_from_ranges(&ranges, 200), 200);
+        assert_eq!(contiguous_version_from_ranges(&ranges, 300), 400);
+    }
+
+    #[test]
+    fn test_merge_version_ranges() {
+        assert_eq!(merge_version_ranges(vec![]), vec![]);
+        assert_eq!(
+            merge_version_ranges(vec![(200, 299), (0, 99), (100, 199), (400, 499)]),
+            vec![(0, 299), (400, 499)]
+        );
+    }
+}
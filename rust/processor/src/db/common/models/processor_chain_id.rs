@@ -0,0 +1,30 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(clippy::extra_unused_lifetimes)]
+
+use crate::{schema::processor_chain_ids, utils::database::DbPoolConnection};
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = processor_chain_ids)]
+/// The chain a processor indexes
+pub struct ProcessorChainId {
+    pub processor: String,
+    pub chain_id: i64,
+}
+
+impl ProcessorChainId {
+    pub async fn get_by_processor(
+        processor_name: &str,
+        conn: &mut DbPoolConnection<'_>,
+    ) -> diesel::QueryResult<Option<i64>> {
+        processor_chain_ids::table
+            .filter(processor_chain_ids::processor.eq(processor_name))
+            .select(processor_chain_ids::chain_id)
+            .first::<i64>(conn)
+            .await
+            .optional()
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+#![allow(clippy::extra_unused_lifetimes)]
+
+use crate::{
+    schema::processor_gaps,
+    utils::database::{execute_with_better_error, ArcDbPool, DbPoolConnection},
+};
+use diesel::{dsl::now, pg::upsert::excluded, ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+
+pub const MISSING_VERSIONS_REASON: &str = "Versions were never processed";
+
+#[derive(Clone, Debug, Insertable)]
+#[diesel(table_name = processor_gaps)]
+/// A version range that failed to process or that the processor never received
+pub struct ProcessorGap {
+    pub processor: String,
+    pub start_version: i64,
+    pub end_version: i64,
+    pub reason: String,
+}
+
+#[derive(Debug, Queryable)]
+#[diesel(table_name = processor_gaps)]
+pub struct ProcessorGapQuery {
+    pub processor: String,
+    pub start_version: i64,
+    pub end_version: i64,
+    pub reason: String,
+    pub attempts: i32,
+    pub is_resolved: bool,
+    pub inserted_at: chrono::NaiveDateTime,
+    pub last_updated: chrono::NaiveDateTime,
+}
+
+impl ProcessorGap {
+    pub fn new(processor: &str, start_version: u64, end_version: u64, reason: String) -> Self {
+        Self {
+            processor: processor.to_string(),
+            start_version: start_version as i64,
+            end_version: end_version as i64,
+            reason,
+        }
+    }
+
+    /// Records the gap. Recording the same range again counts as another failed attempt and
+    /// reopens it if it was resolved.
+    pub async fn record(&self, pool: ArcDbPool) -> diesel::QueryResult<usize> {
+        execute_with_better_error(
+            pool,
+            diesel::insert_into(processor_gaps::table)
+                .values(self.clone())
+                .on_conflict((
+                    processor_gaps::processor,
+                    processor_gaps::start_version,
+                    processor_gaps::end_version,
+                ))
+                .do_update()
+                .set((
+                    processor_gaps::reason.eq(excluded(processor_gaps::reason)),
+                    processor_gaps::attempts.eq(processor_gaps::attempts + 1),
+                    processor_gaps::is_resolved.eq(false),
+                    processor_gaps::last_updated.eq(now),
+                )),
+            None,
+        )
+        .await
+    }
+
+    pub async fn mark_resolved(&self, pool: ArcDbPool) -> diesel::QueryResult<usize> {
+        execute_with_better_error(
+            pool,
+            diesel::update(processor_gaps::table)
+                .filter(processor_gaps::processor.eq(self.processor.clone()))
+                .filter(processor_gaps::start_version.eq(self.start_version))
+                .filter(processor_gaps::end_version.eq(self.end_version))
+                .set((
+                    processor_gaps::is_resolved.eq(true),
+                    processor_gaps::last_updated.eq(now),
+                )),
+            None,
+        )
+        .await
+    }
+}
+
+impl ProcessorGapQuery {
+    /// Returns the unresolved gaps of a processor, ordered by start version.
+    pub async fn get_unresolved(
+        processor_name: &str,
+        conn: &mut DbPoolConnection<'_>,
+    ) -> diesel::QueryResult<Vec<Self>> {
+        processor_gaps::table
+            .filter(processor_gaps::processor.eq(processor_name))
+            .filter(processor_gaps::is_resolved.eq(false))
+            .order(processor_gaps::start_version.asc())
+            .load::<Self>(conn)
+            .await
+    }
+}
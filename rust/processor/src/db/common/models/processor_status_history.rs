@@ -0,0 +1,15 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+/// Appends the processor status of processor $1 to its history, recorded at $2. It's recorded
+/// periodically whether or not the status moved, so a stalled processor shows up as a growing
+/// lag in the processor_status_history_lag view instead of a hole in the history.
+pub const INSERT_PROCESSOR_STATUS_HISTORY_QUERY: &str = "
+    INSERT INTO processor_status_history (
+        processor, last_success_version, last_transaction_timestamp, recorded_at
+    )
+    SELECT processor, last_success_version, last_transaction_timestamp, $2
+    FROM processor_status
+    WHERE processor = $1
+    ON CONFLICT DO NOTHING
+";
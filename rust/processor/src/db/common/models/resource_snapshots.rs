@@ -0,0 +1,219 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Latest state of the resources configured in the resource snapshot processor, a
+//! `current_<name>` table per configured type, see `runtime_tables`.
+
+use crate::utils::{
+    database::is_valid_identifier,
+    runtime_tables::RuntimeTable,
+    util::{normalize_type_str, standardize_address, strip_generics},
+};
+use anyhow::{Context, Result};
+use aptos_protos::transaction::v1::{DeleteResource, WriteResource};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Postgres truncates longer identifiers, leaving room for the `current_` prefix
+const MAX_NAME_LENGTH: usize = 63 - "current_".len();
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResourceSnapshotConfig {
+    // Suffix of the table name, the resources are written to `current_<name>`
+    pub name: String,
+    // Move struct type, e.g. `0xcafe::pool::Pool`. Without generics every instantiation of the
+    // struct is tracked, with generics only that one, e.g. `0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>`.
+    pub resource_type: String,
+}
+
+impl ResourceSnapshotConfig {
+    pub fn matcher(&self) -> ResourceTypeMatcher {
+        let resource_type = normalize_type_str(&self.resource_type);
+        ResourceTypeMatcher {
+            has_generics: resource_type.contains('<'),
+            resource_type,
+        }
+    }
+}
+
+impl RuntimeTable for ResourceSnapshotConfig {
+    fn table_name(&self) -> String {
+        format!("current_{}", self.name)
+    }
+
+    fn validate(&self) -> Result<()> {
+        anyhow::ensure!(
+            is_valid_identifier(&self.name) && self.name.len() <= MAX_NAME_LENGTH,
+            "Invalid resource snapshot name {}, only lowercase letters, digits and underscores are allowed",
+            self.name
+        );
+        anyhow::ensure!(
+            self.resource_type.split("::").count() >= 3,
+            "Invalid resource type {} of {}",
+            self.resource_type,
+            self.name
+        );
+        Ok(())
+    }
+
+    fn ddl_statements(&self) -> Vec<String> {
+        vec![format!(
+            "CREATE TABLE IF NOT EXISTS \"{table}\" (\
+             address VARCHAR(66) NOT NULL, \
+             resource_type TEXT NOT NULL, \
+             state_key_hash VARCHAR(66) NOT NULL, \
+             data JSONB, \
+             is_deleted BOOLEAN NOT NULL, \
+             last_transaction_version BIGINT NOT NULL, \
+             last_transaction_timestamp TIMESTAMP NOT NULL, \
+             inserted_at TIMESTAMP NOT NULL DEFAULT NOW(), \
+             PRIMARY KEY (address, resource_type))",
+            table = self.table_name(),
+        )]
+    }
+
+    fn insert_statement(&self) -> String {
+        format!(
+            "INSERT INTO \"{table}\" (address, resource_type, state_key_hash, data, is_deleted, \
+             last_transaction_version, last_transaction_timestamp) \
+             SELECT address, resource_type, state_key_hash, data, is_deleted, \
+             last_transaction_version, last_transaction_timestamp \
+             FROM jsonb_to_recordset($1) AS r(address VARCHAR(66), resource_type TEXT, \
+             state_key_hash VARCHAR(66), data JSONB, is_deleted BOOLEAN, \
+             last_transaction_version BIGINT, last_transaction_timestamp TIMESTAMP) \
+             ON CONFLICT (address, resource_type) DO UPDATE SET \
+             state_key_hash = excluded.state_key_hash, \
+             data = excluded.data, \
+             is_deleted = excluded.is_deleted, \
+             last_transaction_version = excluded.last_transaction_version, \
+             last_transaction_timestamp = excluded.last_transaction_timestamp, \
+             inserted_at = NOW() \
+             WHERE \"{table}\".last_transaction_version <= excluded.last_transaction_version",
+            table = self.table_name(),
+        )
+    }
+}
+
+/// The configured resource type, normalized once instead of for every change
+#[derive(Clone, Debug)]
+pub struct ResourceTypeMatcher {
+    resource_type: String,
+    has_generics: bool,
+}
+
+impl ResourceTypeMatcher {
+    /// `type_str` has to be normalized with `normalize_type_str`
+    pub fn matches(&self, type_str: &str) -> bool {
+        if self.has_generics {
+            self.resource_type == type_str
+        } else {
+            self.resource_type == strip_generics(type_str)
+        }
+    }
+}
+
+pub type ResourceSnapshotPK = (String, String);
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ResourceSnapshot {
+    pub address: String,
+    pub resource_type: String,
+    pub state_key_hash: String,
+    pub data: Option<Value>,
+    pub is_deleted: bool,
+    pub last_transaction_version: i64,
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+}
+
+impl ResourceSnapshot {
+    pub fn pk(&self) -> ResourceSnapshotPK {
+        (self.address.clone(), self.resource_type.clone())
+    }
+
+    pub fn from_write_resource(
+        write_resource: &WriteResource,
+        txn_version: i64,
+        txn_timestamp: chrono::NaiveDateTime,
+    ) -> Result<Self> {
+        let data = serde_json::from_str(&write_resource.data).context(format!(
+            "version {} failed! failed to parse type {}, data {:?}",
+            txn_version, write_resource.type_str, write_resource.data
+        ))?;
+        Ok(Self {
+            address: standardize_address(&write_resource.address),
+            resource_type: write_resource.type_str.clone(),
+            state_key_hash: standardize_address(&hex::encode(&write_resource.state_key_hash)),
+            data: Some(data),
+            is_deleted: false,
+            last_transaction_version: txn_version,
+            last_transaction_timestamp: txn_timestamp,
+        })
+    }
+
+    pub fn from_delete_resource(
+        delete_resource: &DeleteResource,
+        txn_version: i64,
+        txn_timestamp: chrono::NaiveDateTime,
+    ) -> Self {
+        Self {
+            address: standardize_address(&delete_resource.address),
+            resource_type: delete_resource.type_str.clone(),
+            state_key_hash: standardize_address(&hex::encode(&delete_resource.state_key_hash)),
+            data: None,
+            is_deleted: true,
+            last_transaction_version: txn_version,
+            last_transaction_timestamp: txn_timestamp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(resource_type: &str) -> ResourceSnapshotConfig {
+        ResourceSnapshotConfig {
+            name: "coin_stores".to_string(),
+            resource_type: resource_type.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_matches_resource_type() {
+        let matches = |config: &ResourceSnapshotConfig, type_str: &str| {
+            config.matcher().matches(&normalize_type_str(type_str))
+        };
+        let any_coin = config("0x1::coin::CoinStore");
+        assert!(matches(
+            &any_coin,
+            "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>"
+        ));
+        assert!(matches(
+            &any_coin,
+            "0x1::coin::CoinStore<0xcafe::usdc::USDC>"
+        ));
+        assert!(!matches(
+            &any_coin,
+            "0x1::coin::CoinInfo<0x1::aptos_coin::AptosCoin>"
+        ));
+
+        let apt = config(
+            "0x0000000000000000000000000000000000000000000000000000000000000001::coin::CoinStore<0x1::aptos_coin::AptosCoin>",
+        );
+        assert!(matches(
+            &apt,
+            "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>"
+        ));
+        assert!(!matches(&apt, "0x1::coin::CoinStore<0xcafe::usdc::USDC>"));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(config("0x1::coin::CoinStore").validate().is_ok());
+        assert!(config("CoinStore").validate().is_err());
+        let mut invalid = config("0x1::coin::CoinStore");
+        invalid.name = "coin\"; DROP TABLE events; --".to_string();
+        assert!(invalid.validate().is_err());
+    }
+}
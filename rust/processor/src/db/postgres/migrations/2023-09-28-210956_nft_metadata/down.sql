@@ -1,4 +1,8 @@
 -- This file should undo anything in `up.sql`
+-- The schema is shared by the networks indexed in the database, it's only dropped with public
+DO $$ BEGIN IF current_schema() = 'public' THEN
 DROP TABLE IF EXISTS nft_metadata_crawler.parsed_asset_uris;
 DROP TABLE IF EXISTS nft_metadata_crawler.ledger_infos;
-DROP SCHEMA IF EXISTS nft_metadata_crawler CASCADE;
\ No newline at end of file
+DROP SCHEMA IF EXISTS nft_metadata_crawler CASCADE;
+END IF;
+END $$;
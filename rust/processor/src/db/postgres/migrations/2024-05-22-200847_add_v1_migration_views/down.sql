@@ -1,4 +1,6 @@
 -- This file should undo anything in `up.sql`
+-- The views are only created for the public schema, see up.sql
+DO $$ BEGIN IF current_schema() = 'public' THEN
 DROP VIEW IF EXISTS legacy_migration_v1.move_resources;
 DROP VIEW IF EXISTS legacy_migration_v1.address_version_from_move_resources;
 DROP VIEW IF EXISTS legacy_migration_v1.coin_activities;
@@ -27,4 +29,6 @@ DROP INDEX IF EXISTS lm1_ca_oa_ct_at_index;
 DROP INDEX IF EXISTS lm1_ca_oa_igf_index;
 DROP INDEX IF EXISTS lm1_ans_d_s_et_index;
 DROP INDEX IF EXISTS lm1_ans_ra_et_index;
-DROP SCHEMA IF EXISTS legacy_migration_v1;
\ No newline at end of file
+DROP SCHEMA IF EXISTS legacy_migration_v1;
+END IF;
+END $$;
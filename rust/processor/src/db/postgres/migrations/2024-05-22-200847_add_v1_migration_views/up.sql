@@ -1,4 +1,7 @@
 -- Your SQL goes here
+-- The views are shared by the networks indexed in the database, so they're only created for
+-- the tables of the public schema and not when the processor runs with db_schema
+DO $$ BEGIN IF current_schema() = 'public' THEN
 -- Create the schema
 CREATE SCHEMA IF NOT EXISTS legacy_migration_v1;
 -- Replace `move_resources` with account transactions
@@ -30,7 +33,7 @@ SElECT transaction_version,
     event_index,
     gas_fee_payer_address,
     storage_refund_amount
-FROM public.fungible_asset_activities
+FROM fungible_asset_activities
 WHERE token_standard = 'v1';
 -- replace `coin_balances` with `fungible_asset_balances`
 CREATE OR REPLACE VIEW legacy_migration_v1.coin_balances AS
@@ -42,7 +45,7 @@ SELECT transaction_version,
     amount,
     transaction_timestamp,
     inserted_at
-FROM public.fungible_asset_balances
+FROM fungible_asset_balances
 WHERE token_standard = 'v1';
 -- replace `coin_infos` with `fungible_asset_metadata`
 CREATE OR REPLACE VIEW legacy_migration_v1.coin_infos AS
@@ -57,7 +60,7 @@ SELECT encode(sha256(asset_type::bytea), 'hex') as coin_type_hash,
     inserted_at,
     supply_aggregator_table_handle_v1 as supply_aggregator_table_handle,
     supply_aggregator_table_key_v1 as supply_aggregator_table_key
-FROM public.fungible_asset_metadata
+FROM fungible_asset_metadata
 WHERE token_standard = 'v1';
 -- replace `current_coin_balances` with `current_fungible_asset_balances`
 CREATE OR REPLACE VIEW legacy_migration_v1.current_coin_balances AS
@@ -68,7 +71,7 @@ SELECT owner_address,
     last_transaction_version,
     last_transaction_timestamp,
     inserted_at
-FROM public.current_fungible_asset_balances
+FROM current_fungible_asset_balances
 WHERE token_standard = 'v1';
 -- replace `token_activities` with `token_activities_v2`
 -- token_activities_v2.token_data_id is 0x prefixed, but token_activities.token_data_id is not. We need to create an index on the substring
@@ -94,7 +97,7 @@ SELECT tav.transaction_version,
     tav.inserted_at,
     tav.transaction_timestamp,
     event_index
-FROM public.token_activities_v2 tav
+FROM token_activities_v2 tav
     JOIN token_datas_v2 tdv ON tav.token_data_id = tdv.token_data_id
     AND tav.transaction_version = tdv.transaction_version
     JOIN collections_v2 cv ON tdv.collection_id = cv.collection_id
@@ -116,10 +119,10 @@ SELECT tov.token_data_id AS token_data_id_hash,
     tov.inserted_at,
     tdv.collection_id AS collection_data_id_hash,
     tov.transaction_timestamp
-FROM public.token_ownerships_v2 tov
-    JOIN public.token_datas_v2 tdv ON tov.token_data_id = tdv.token_data_id
+FROM token_ownerships_v2 tov
+    JOIN token_datas_v2 tdv ON tov.token_data_id = tdv.token_data_id
     AND tov.transaction_version = tdv.transaction_version
-    JOIN public.collections_v2 cv ON tdv.collection_id = cv.collection_id
+    JOIN collections_v2 cv ON tdv.collection_id = cv.collection_id
     AND tdv.transaction_version = cv.transaction_version
 WHERE tov.token_standard = 'v1';
 -- replace `current_token_ownerships` with `current_token_ownerships_v2`
@@ -257,6 +260,8 @@ SELECT domain,
     is_deleted
 FROM current_ans_lookup_v2
 WHERE token_standard = 'v1';
+END IF;
+END $$;
 -----
 -----
 -----
@@ -1,3 +1,3 @@
 -- This file should undo anything in `up.sql`
-ALTER TABLE public.current_objects DROP COLUMN IF EXISTS untransferrable;
-ALTER TABLE public.objects DROP COLUMN IF EXISTS untransferrable;
\ No newline at end of file
+ALTER TABLE current_objects DROP COLUMN IF EXISTS untransferrable;
+ALTER TABLE objects DROP COLUMN IF EXISTS untransferrable;
\ No newline at end of file
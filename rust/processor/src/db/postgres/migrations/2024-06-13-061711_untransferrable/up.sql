@@ -1,5 +1,5 @@
 -- Your SQL goes here
-ALTER TABLE public.current_objects
+ALTER TABLE current_objects
 ADD COLUMN IF NOT EXISTS untransferrable BOOLEAN NOT NULL DEFAULT FALSE;
-ALTER TABLE public.objects
+ALTER TABLE objects
 ADD COLUMN IF NOT EXISTS untransferrable BOOLEAN NOT NULL DEFAULT FALSE;
\ No newline at end of file
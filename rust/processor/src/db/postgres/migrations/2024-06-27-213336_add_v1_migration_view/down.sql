@@ -1,3 +1,6 @@
 -- This file should undo anything in `up.sql`
+DO $$ BEGIN IF current_schema() = 'public' THEN
 DROP VIEW IF EXISTS legacy_migration_v1.current_collection_datas;
-DROP INDEX IF EXISTS lm1_curr_cd_th_index;
\ No newline at end of file
+DROP INDEX IF EXISTS lm1_curr_cd_th_index;
+END IF;
+END $$;
@@ -1,4 +1,6 @@
 -- Your SQL goes here
+-- Only created for the public schema, see 2024-05-22-200847_add_v1_migration_views
+DO $$ BEGIN IF current_schema() = 'public' THEN
 CREATE OR REPLACE VIEW legacy_migration_v1.current_collection_datas AS
 SELECT collection_id as collection_data_id_hash,
     creator_address,
@@ -16,6 +18,8 @@ SELECT collection_id as collection_data_id_hash,
     last_transaction_timestamp
 FROM current_collections_v2
 WHERE token_standard = 'v1';
+END IF;
+END $$;
 
 -- If you would like to run these indices, please do it outside of diesel migration since it will be blocking processing
 -- CREATE INDEX CONCURRENTLY IF NOT EXISTS lm1_curr_cd_th_index ON public.current_collections_v2 USING btree (table_handle_v1);
\ No newline at end of file
@@ -0,0 +1,16 @@
+-- Your SQL goes here
+-- Files uploaded by the parquet processors. Each row covers a contiguous range of transaction
+-- versions that is fully contained in the file, which lets a processor work out where every
+-- table has to resume from after a restart.
+CREATE TABLE IF NOT EXISTS parquet_upload_manifests (
+  processor VARCHAR(50) NOT NULL,
+  table_name VARCHAR(100) NOT NULL,
+  start_version BIGINT NOT NULL,
+  end_version BIGINT NOT NULL,
+  file_path TEXT NOT NULL,
+  num_records BIGINT NOT NULL,
+  file_size BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (processor, table_name, start_version, end_version)
+);
+CREATE INDEX IF NOT EXISTS pum_insat_index ON parquet_upload_manifests (inserted_at);
@@ -0,0 +1,10 @@
+-- Your SQL goes here
+-- Per table checkpoints of the parquet processors, the tables of a processor flush at different
+-- paces so each of them tracks the last version that has been fully uploaded on its own.
+CREATE TABLE IF NOT EXISTS parquet_table_status (
+  processor VARCHAR(50) NOT NULL,
+  table_name VARCHAR(100) NOT NULL,
+  last_success_version BIGINT NOT NULL,
+  last_updated TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (processor, table_name)
+);
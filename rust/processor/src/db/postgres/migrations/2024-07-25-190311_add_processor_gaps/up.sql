@@ -0,0 +1,15 @@
+-- Your SQL goes here
+-- Version ranges a processor failed to process or never received. Unresolved ranges can be
+-- re-run with `replay_gaps` without restarting the processor from its last checkpoint.
+CREATE TABLE IF NOT EXISTS processor_gaps (
+  processor VARCHAR(50) NOT NULL,
+  start_version BIGINT NOT NULL,
+  end_version BIGINT NOT NULL,
+  reason TEXT NOT NULL,
+  attempts INT NOT NULL DEFAULT 1,
+  is_resolved BOOLEAN NOT NULL DEFAULT FALSE,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  last_updated TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (processor, start_version, end_version)
+);
+CREATE INDEX IF NOT EXISTS pg_processor_is_resolved_index ON processor_gaps (processor, is_resolved);
@@ -0,0 +1,10 @@
+-- Your SQL goes here
+-- Transactions skipped by processors running with a dead letter config because they failed to
+-- process on their own.
+CREATE TABLE IF NOT EXISTS dead_letter_transactions (
+  processor VARCHAR(50) NOT NULL,
+  transaction_version BIGINT NOT NULL,
+  error TEXT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (processor, transaction_version)
+);
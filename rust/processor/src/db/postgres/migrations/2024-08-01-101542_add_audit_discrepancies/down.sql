@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS ad_address_index;
+DROP INDEX IF EXISTS ad_insat_index;
+DROP TABLE IF EXISTS audit_discrepancies;
@@ -0,0 +1,16 @@
+-- Your SQL goes here
+-- Inconsistencies between tables written by different processors, found by the audit processor
+CREATE TABLE IF NOT EXISTS audit_discrepancies (
+  check_name VARCHAR(50) NOT NULL,
+  entity_id VARCHAR(66) NOT NULL,
+  transaction_version BIGINT NOT NULL,
+  address VARCHAR(66) NOT NULL,
+  expected_value TEXT NOT NULL,
+  actual_value TEXT NOT NULL,
+  audit_start_version BIGINT NOT NULL,
+  audit_end_version BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (check_name, entity_id, transaction_version)
+);
+CREATE INDEX IF NOT EXISTS ad_address_index ON audit_discrepancies (address);
+CREATE INDEX IF NOT EXISTS ad_insat_index ON audit_discrepancies (inserted_at);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP VIEW IF EXISTS processor_status_history_lag;
+DROP INDEX IF EXISTS psh_recorded_at_index;
+DROP TABLE IF EXISTS processor_status_history;
@@ -0,0 +1,33 @@
+-- Your SQL goes here
+-- Append only history of processor_status, recorded periodically by every processor
+CREATE TABLE IF NOT EXISTS processor_status_history (
+  processor VARCHAR(50) NOT NULL,
+  last_success_version BIGINT NOT NULL,
+  last_transaction_timestamp TIMESTAMP,
+  recorded_at TIMESTAMP NOT NULL,
+  PRIMARY KEY (processor, recorded_at)
+);
+CREATE INDEX IF NOT EXISTS psh_recorded_at_index ON processor_status_history (recorded_at);
+-- Lag behind the chain and throughput since the previous record
+CREATE OR REPLACE VIEW processor_status_history_lag AS
+SELECT processor,
+  recorded_at,
+  last_success_version,
+  last_transaction_timestamp,
+  EXTRACT(
+    EPOCH
+    FROM (recorded_at - last_transaction_timestamp)
+  ) AS lag_secs,
+  (
+    last_success_version - LAG(last_success_version) OVER w
+  ) / NULLIF(
+    EXTRACT(
+      EPOCH
+      FROM (recorded_at - LAG(recorded_at) OVER w)
+    ),
+    0
+  ) AS tps
+FROM processor_status_history WINDOW w AS (
+    PARTITION BY processor
+    ORDER BY recorded_at
+  );
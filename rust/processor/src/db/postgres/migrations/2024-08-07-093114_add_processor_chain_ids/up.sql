@@ -0,0 +1,7 @@
+-- Your SQL goes here
+-- The chain each processor indexes, checked on startup on top of ledger_infos
+CREATE TABLE IF NOT EXISTS processor_chain_ids (
+  processor VARCHAR(50) PRIMARY KEY NOT NULL,
+  chain_id BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
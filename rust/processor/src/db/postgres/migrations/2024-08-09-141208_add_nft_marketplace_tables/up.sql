@@ -0,0 +1,84 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS nft_marketplace_activities (
+  transaction_version BIGINT NOT NULL,
+  event_index BIGINT NOT NULL,
+  marketplace VARCHAR(100) NOT NULL,
+  contract_address VARCHAR(66) NOT NULL,
+  event_type VARCHAR(50) NOT NULL,
+  offer_or_listing_id VARCHAR(66) NOT NULL,
+  collection_id VARCHAR(66) NOT NULL,
+  token_data_id VARCHAR(66),
+  creator_address VARCHAR(66) NOT NULL,
+  collection_name VARCHAR(128) NOT NULL,
+  token_name VARCHAR(128),
+  property_version NUMERIC,
+  price NUMERIC NOT NULL,
+  token_amount NUMERIC NOT NULL,
+  token_standard VARCHAR(10) NOT NULL,
+  seller VARCHAR(66),
+  buyer VARCHAR(66),
+  entry_function_id_str VARCHAR(1000),
+  transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (transaction_version, event_index)
+);
+CREATE INDEX IF NOT EXISTS nma_offer_or_listing_id_index ON nft_marketplace_activities (offer_or_listing_id);
+CREATE INDEX IF NOT EXISTS nma_token_data_id_index ON nft_marketplace_activities (token_data_id);
+CREATE INDEX IF NOT EXISTS nma_collection_id_index ON nft_marketplace_activities (collection_id);
+CREATE INDEX IF NOT EXISTS nma_insat_index ON nft_marketplace_activities (inserted_at);
+
+CREATE TABLE IF NOT EXISTS current_nft_marketplace_listings (
+  listing_id VARCHAR(66) PRIMARY KEY NOT NULL,
+  token_data_id VARCHAR(66) NOT NULL,
+  collection_id VARCHAR(66) NOT NULL,
+  marketplace VARCHAR(100) NOT NULL,
+  contract_address VARCHAR(66) NOT NULL,
+  seller VARCHAR(66) NOT NULL,
+  price NUMERIC NOT NULL,
+  token_amount NUMERIC NOT NULL,
+  token_standard VARCHAR(10) NOT NULL,
+  listing_type VARCHAR(50) NOT NULL,
+  is_deleted BOOLEAN NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  last_transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS cnml_token_data_id_index ON current_nft_marketplace_listings (token_data_id);
+CREATE INDEX IF NOT EXISTS cnml_collection_id_price_index ON current_nft_marketplace_listings (collection_id, price);
+CREATE INDEX IF NOT EXISTS cnml_seller_index ON current_nft_marketplace_listings (seller);
+
+CREATE TABLE IF NOT EXISTS current_nft_marketplace_token_offers (
+  offer_id VARCHAR(66) PRIMARY KEY NOT NULL,
+  token_data_id VARCHAR(66) NOT NULL,
+  collection_id VARCHAR(66) NOT NULL,
+  marketplace VARCHAR(100) NOT NULL,
+  contract_address VARCHAR(66) NOT NULL,
+  buyer VARCHAR(66) NOT NULL,
+  price NUMERIC NOT NULL,
+  expiration_time NUMERIC,
+  token_standard VARCHAR(10) NOT NULL,
+  is_deleted BOOLEAN NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  last_transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS cnmto_token_data_id_index ON current_nft_marketplace_token_offers (token_data_id);
+CREATE INDEX IF NOT EXISTS cnmto_buyer_index ON current_nft_marketplace_token_offers (buyer);
+
+CREATE TABLE IF NOT EXISTS current_nft_marketplace_collection_offers (
+  collection_offer_id VARCHAR(66) PRIMARY KEY NOT NULL,
+  collection_id VARCHAR(66) NOT NULL,
+  marketplace VARCHAR(100) NOT NULL,
+  contract_address VARCHAR(66) NOT NULL,
+  buyer VARCHAR(66) NOT NULL,
+  item_price NUMERIC NOT NULL,
+  remaining_token_amount NUMERIC NOT NULL,
+  expiration_time NUMERIC,
+  token_standard VARCHAR(10) NOT NULL,
+  is_deleted BOOLEAN NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  last_transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS cnmco_collection_id_price_index ON current_nft_marketplace_collection_offers (collection_id, item_price);
+CREATE INDEX IF NOT EXISTS cnmco_buyer_index ON current_nft_marketplace_collection_offers (buyer);
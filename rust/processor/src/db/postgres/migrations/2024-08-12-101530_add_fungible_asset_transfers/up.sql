@@ -0,0 +1,25 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS fungible_asset_transfers (
+  transaction_version BIGINT NOT NULL,
+  transfer_index BIGINT NOT NULL,
+  -- null for a mint, i.e. a deposit without a matching withdrawal
+  withdraw_event_index BIGINT,
+  from_address VARCHAR(66),
+  from_storage_id VARCHAR(66),
+  -- null for a burn, i.e. a withdrawal without a matching deposit
+  deposit_event_index BIGINT,
+  to_address VARCHAR(66),
+  to_storage_id VARCHAR(66),
+  asset_type VARCHAR(1000) NOT NULL,
+  amount NUMERIC NOT NULL,
+  token_standard VARCHAR(10) NOT NULL,
+  gas_fee_payer_address VARCHAR(66),
+  entry_function_id_str VARCHAR(1000),
+  transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (transaction_version, transfer_index)
+);
+CREATE INDEX IF NOT EXISTS fat_from_address_index ON fungible_asset_transfers (from_address, transaction_version);
+CREATE INDEX IF NOT EXISTS fat_to_address_index ON fungible_asset_transfers (to_address, transaction_version);
+CREATE INDEX IF NOT EXISTS fat_asset_type_index ON fungible_asset_transfers (asset_type);
+CREATE INDEX IF NOT EXISTS fat_insat_index ON fungible_asset_transfers (inserted_at);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS dex_swaps;
+DROP TABLE IF EXISTS dex_liquidity_events;
@@ -0,0 +1,46 @@
+-- Your SQL goes here
+-- Pool ids contain the type arguments of the pool and can exceed the btree row limit, the
+-- pools are indexed by the sha256 of the id instead
+CREATE TABLE IF NOT EXISTS dex_swaps (
+  transaction_version BIGINT NOT NULL,
+  event_index BIGINT NOT NULL,
+  dex VARCHAR(100) NOT NULL,
+  contract_address VARCHAR(66) NOT NULL,
+  pool_id TEXT NOT NULL,
+  pool_id_hash VARCHAR(64) NOT NULL,
+  sender VARCHAR(66),
+  asset_in VARCHAR(1000) NOT NULL,
+  amount_in NUMERIC NOT NULL,
+  asset_out VARCHAR(1000) NOT NULL,
+  amount_out NUMERIC NOT NULL,
+  entry_function_id_str VARCHAR(1000),
+  transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (transaction_version, event_index)
+);
+CREATE INDEX IF NOT EXISTS ds_pool_id_hash_index ON dex_swaps (pool_id_hash, transaction_version);
+CREATE INDEX IF NOT EXISTS ds_sender_index ON dex_swaps (sender);
+CREATE INDEX IF NOT EXISTS ds_insat_index ON dex_swaps (inserted_at);
+
+CREATE TABLE IF NOT EXISTS dex_liquidity_events (
+  transaction_version BIGINT NOT NULL,
+  event_index BIGINT NOT NULL,
+  dex VARCHAR(100) NOT NULL,
+  contract_address VARCHAR(66) NOT NULL,
+  pool_id TEXT NOT NULL,
+  pool_id_hash VARCHAR(64) NOT NULL,
+  sender VARCHAR(66),
+  event_type VARCHAR(50) NOT NULL,
+  asset_x VARCHAR(1000) NOT NULL,
+  amount_x NUMERIC NOT NULL,
+  asset_y VARCHAR(1000) NOT NULL,
+  amount_y NUMERIC NOT NULL,
+  lp_token_amount NUMERIC,
+  entry_function_id_str VARCHAR(1000),
+  transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (transaction_version, event_index)
+);
+CREATE INDEX IF NOT EXISTS dle_pool_id_hash_index ON dex_liquidity_events (pool_id_hash, transaction_version);
+CREATE INDEX IF NOT EXISTS dle_sender_index ON dex_liquidity_events (sender);
+CREATE INDEX IF NOT EXISTS dle_insat_index ON dex_liquidity_events (inserted_at);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS account_authentication_keys;
+DROP TABLE IF EXISTS current_auth_key_to_address;
@@ -0,0 +1,25 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS account_authentication_keys (
+  transaction_version BIGINT NOT NULL,
+  event_index BIGINT NOT NULL,
+  account_address VARCHAR(66) NOT NULL,
+  old_authentication_key VARCHAR(66) NOT NULL,
+  new_authentication_key VARCHAR(66) NOT NULL,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (transaction_version, event_index)
+);
+CREATE INDEX IF NOT EXISTS aak_account_address_index ON account_authentication_keys (account_address, transaction_version);
+CREATE INDEX IF NOT EXISTS aak_old_authentication_key_index ON account_authentication_keys (old_authentication_key);
+CREATE INDEX IF NOT EXISTS aak_new_authentication_key_index ON account_authentication_keys (new_authentication_key);
+CREATE INDEX IF NOT EXISTS aak_insat_index ON account_authentication_keys (inserted_at);
+
+CREATE TABLE IF NOT EXISTS current_auth_key_to_address (
+  account_address VARCHAR(66) PRIMARY KEY NOT NULL,
+  authentication_key VARCHAR(66) NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  last_transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS cakta_authentication_key_index ON current_auth_key_to_address (authentication_key);
+CREATE INDEX IF NOT EXISTS cakta_insat_index ON current_auth_key_to_address (inserted_at);
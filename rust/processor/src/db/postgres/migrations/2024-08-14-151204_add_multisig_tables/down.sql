@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS current_multisig_accounts;
+DROP TABLE IF EXISTS multisig_transactions;
+DROP TABLE IF EXISTS multisig_activities;
@@ -0,0 +1,47 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS current_multisig_accounts (
+  multisig_address VARCHAR(66) PRIMARY KEY NOT NULL,
+  owners JSONB NOT NULL,
+  num_signatures_required BIGINT NOT NULL,
+  last_executed_sequence_number BIGINT NOT NULL,
+  next_sequence_number BIGINT NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  last_transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS cma_owners_index ON current_multisig_accounts USING GIN (owners);
+CREATE INDEX IF NOT EXISTS cma_insat_index ON current_multisig_accounts (inserted_at);
+
+CREATE TABLE IF NOT EXISTS multisig_transactions (
+  multisig_address VARCHAR(66) NOT NULL,
+  sequence_number BIGINT NOT NULL,
+  creator_address VARCHAR(66) NOT NULL,
+  payload TEXT,
+  payload_hash VARCHAR(66),
+  transaction_version BIGINT NOT NULL,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (multisig_address, sequence_number)
+);
+CREATE INDEX IF NOT EXISTS mt_creator_address_index ON multisig_transactions (creator_address);
+CREATE INDEX IF NOT EXISTS mt_insat_index ON multisig_transactions (inserted_at);
+
+CREATE TABLE IF NOT EXISTS multisig_activities (
+  transaction_version BIGINT NOT NULL,
+  event_index BIGINT NOT NULL,
+  multisig_address VARCHAR(66) NOT NULL,
+  activity_type VARCHAR(50) NOT NULL,
+  sequence_number BIGINT,
+  account_address VARCHAR(66),
+  approved BOOLEAN,
+  owners JSONB,
+  num_signatures_required BIGINT,
+  num_approvals BIGINT,
+  num_rejections BIGINT,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (transaction_version, event_index)
+);
+CREATE INDEX IF NOT EXISTS ma_multisig_address_index ON multisig_activities (multisig_address, sequence_number);
+CREATE INDEX IF NOT EXISTS ma_account_address_index ON multisig_activities (account_address);
+CREATE INDEX IF NOT EXISTS ma_insat_index ON multisig_activities (inserted_at);
@@ -0,0 +1,46 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS governance_proposals (
+  transaction_version BIGINT NOT NULL,
+  proposal_id BIGINT NOT NULL,
+  proposer_address VARCHAR(66) NOT NULL,
+  stake_pool_address VARCHAR(66),
+  execution_hash VARCHAR(66) NOT NULL,
+  metadata JSONB NOT NULL,
+  min_vote_threshold NUMERIC NOT NULL,
+  early_resolution_vote_threshold NUMERIC,
+  yes_votes NUMERIC NOT NULL,
+  no_votes NUMERIC NOT NULL,
+  is_resolved BOOLEAN NOT NULL,
+  resolved_early BOOLEAN,
+  creation_timestamp TIMESTAMP NOT NULL,
+  expiration_timestamp TIMESTAMP NOT NULL,
+  resolution_timestamp TIMESTAMP,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (transaction_version, proposal_id)
+);
+CREATE INDEX IF NOT EXISTS gp_proposal_id_index ON governance_proposals (proposal_id);
+CREATE INDEX IF NOT EXISTS gp_insat_index ON governance_proposals (inserted_at);
+
+CREATE TABLE IF NOT EXISTS current_governance_proposals (
+  proposal_id BIGINT PRIMARY KEY NOT NULL,
+  proposer_address VARCHAR(66) NOT NULL,
+  stake_pool_address VARCHAR(66),
+  execution_hash VARCHAR(66) NOT NULL,
+  metadata JSONB NOT NULL,
+  min_vote_threshold NUMERIC NOT NULL,
+  early_resolution_vote_threshold NUMERIC,
+  yes_votes NUMERIC NOT NULL,
+  no_votes NUMERIC NOT NULL,
+  is_resolved BOOLEAN NOT NULL,
+  resolved_early BOOLEAN,
+  creation_timestamp TIMESTAMP NOT NULL,
+  expiration_timestamp TIMESTAMP NOT NULL,
+  resolution_timestamp TIMESTAMP,
+  last_transaction_version BIGINT NOT NULL,
+  last_transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS cgp_proposer_address_index ON current_governance_proposals (proposer_address);
+CREATE INDEX IF NOT EXISTS cgp_is_resolved_index ON current_governance_proposals (is_resolved);
+CREATE INDEX IF NOT EXISTS cgp_insat_index ON current_governance_proposals (inserted_at);
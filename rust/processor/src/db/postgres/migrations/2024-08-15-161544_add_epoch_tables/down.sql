@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS epochs;
+DROP TABLE IF EXISTS epoch_validators;
+DROP TABLE IF EXISTS epoch_validator_performances;
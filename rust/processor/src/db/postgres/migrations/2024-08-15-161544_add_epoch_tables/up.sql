@@ -0,0 +1,40 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS epochs (
+  epoch BIGINT PRIMARY KEY NOT NULL,
+  transaction_version BIGINT NOT NULL,
+  block_height BIGINT NOT NULL,
+  num_validators BIGINT NOT NULL,
+  total_voting_power NUMERIC NOT NULL,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+CREATE INDEX IF NOT EXISTS e_insat_index ON epochs (inserted_at);
+
+CREATE TABLE IF NOT EXISTS epoch_validators (
+  epoch BIGINT NOT NULL,
+  validator_address VARCHAR(66) NOT NULL,
+  validator_index BIGINT NOT NULL,
+  voting_power NUMERIC NOT NULL,
+  consensus_public_key TEXT NOT NULL,
+  network_addresses TEXT NOT NULL,
+  fullnode_addresses TEXT NOT NULL,
+  transaction_version BIGINT NOT NULL,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (epoch, validator_address)
+);
+CREATE INDEX IF NOT EXISTS ev_validator_address_index ON epoch_validators (validator_address, epoch);
+CREATE INDEX IF NOT EXISTS ev_epoch_validator_index_index ON epoch_validators (epoch, validator_index);
+CREATE INDEX IF NOT EXISTS ev_insat_index ON epoch_validators (inserted_at);
+
+CREATE TABLE IF NOT EXISTS epoch_validator_performances (
+  epoch BIGINT NOT NULL,
+  validator_index BIGINT NOT NULL,
+  successful_proposals BIGINT NOT NULL,
+  failed_proposals BIGINT NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  last_transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (epoch, validator_index)
+);
+CREATE INDEX IF NOT EXISTS evp_insat_index ON epoch_validator_performances (inserted_at);
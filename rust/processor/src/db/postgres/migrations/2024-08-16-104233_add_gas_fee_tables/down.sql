@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS gas_fee_breakdowns;
+DROP TABLE IF EXISTS sender_daily_gas_fees;
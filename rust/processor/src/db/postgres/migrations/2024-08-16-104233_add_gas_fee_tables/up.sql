@@ -0,0 +1,36 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS gas_fee_breakdowns (
+  transaction_version BIGINT PRIMARY KEY NOT NULL,
+  sender VARCHAR(66) NOT NULL,
+  gas_fee_payer_address VARCHAR(66),
+  is_transaction_success BOOLEAN NOT NULL,
+  entry_function_id_str VARCHAR(1000),
+  gas_unit_price NUMERIC NOT NULL,
+  total_gas_units BIGINT NOT NULL,
+  execution_gas_units BIGINT,
+  io_gas_units BIGINT,
+  storage_fee_octas NUMERIC,
+  storage_fee_refund_octas NUMERIC,
+  gas_fee_octas NUMERIC NOT NULL,
+  block_height BIGINT NOT NULL,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+-- Used to recompute the daily totals of a sender
+CREATE INDEX IF NOT EXISTS gfb_sender_date_index ON gas_fee_breakdowns (sender, (transaction_timestamp::date));
+CREATE INDEX IF NOT EXISTS gfb_gas_fee_payer_address_index ON gas_fee_breakdowns (gas_fee_payer_address);
+CREATE INDEX IF NOT EXISTS gfb_insat_index ON gas_fee_breakdowns (inserted_at);
+
+CREATE TABLE IF NOT EXISTS sender_daily_gas_fees (
+  sender VARCHAR(66) NOT NULL,
+  date DATE NOT NULL,
+  num_transactions BIGINT NOT NULL,
+  total_gas_units NUMERIC NOT NULL,
+  gas_fee_octas NUMERIC NOT NULL,
+  storage_fee_refund_octas NUMERIC NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  PRIMARY KEY (sender, date)
+);
+CREATE INDEX IF NOT EXISTS sdgf_date_gas_fee_octas_index ON sender_daily_gas_fees (date, gas_fee_octas);
+CREATE INDEX IF NOT EXISTS sdgf_insat_index ON sender_daily_gas_fees (inserted_at);
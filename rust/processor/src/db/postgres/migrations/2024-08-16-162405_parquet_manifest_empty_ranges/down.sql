@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM parquet_upload_manifests
+WHERE file_path IS NULL;
+ALTER TABLE parquet_upload_manifests
+ALTER COLUMN file_path SET NOT NULL;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+-- Version ranges that didn't produce any structs for a table are recorded without a file
+ALTER TABLE parquet_upload_manifests
+ALTER COLUMN file_path DROP NOT NULL;
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS cfab_ltv_index;
+DROP INDEX IF EXISTS curr_to2_ltv_index;
+DROP INDEX IF EXISTS cufab_ltv_v1_index;
+DROP INDEX IF EXISTS cufab_ltv_v2_index;
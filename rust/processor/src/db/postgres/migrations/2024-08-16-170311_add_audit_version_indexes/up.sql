@@ -0,0 +1,6 @@
+-- Your SQL goes here
+-- The audit processor samples the current tables by last transaction version
+CREATE INDEX IF NOT EXISTS cfab_ltv_index ON current_fungible_asset_balances (last_transaction_version);
+CREATE INDEX IF NOT EXISTS curr_to2_ltv_index ON current_token_ownerships_v2 (last_transaction_version);
+CREATE INDEX IF NOT EXISTS cufab_ltv_v1_index ON current_unified_fungible_asset_balances_to_be_renamed (last_transaction_version_v1);
+CREATE INDEX IF NOT EXISTS cufab_ltv_v2_index ON current_unified_fungible_asset_balances_to_be_renamed (last_transaction_version_v2);
@@ -1,5 +1,20 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    account_authentication_keys (transaction_version, event_index) {
+        transaction_version -> Int8,
+        event_index -> Int8,
+        #[max_length = 66]
+        account_address -> Varchar,
+        #[max_length = 66]
+        old_authentication_key -> Varchar,
+        #[max_length = 66]
+        new_authentication_key -> Varchar,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     account_transactions (account_address, transaction_version) {
         transaction_version -> Int8,
@@ -84,6 +99,23 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    audit_discrepancies (check_name, entity_id, transaction_version) {
+        #[max_length = 50]
+        check_name -> Varchar,
+        #[max_length = 66]
+        entity_id -> Varchar,
+        transaction_version -> Int8,
+        #[max_length = 66]
+        address -> Varchar,
+        expected_value -> Text,
+        actual_value -> Text,
+        audit_start_version -> Int8,
+        audit_end_version -> Int8,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     block_metadata_transactions (version) {
         version -> Int8,
@@ -303,6 +335,18 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    current_auth_key_to_address (account_address) {
+        #[max_length = 66]
+        account_address -> Varchar,
+        #[max_length = 66]
+        authentication_key -> Varchar,
+        last_transaction_version -> Int8,
+        last_transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     current_coin_balances (owner_address, coin_type_hash) {
         #[max_length = 66]
@@ -440,6 +484,121 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    current_governance_proposals (proposal_id) {
+        proposal_id -> Int8,
+        #[max_length = 66]
+        proposer_address -> Varchar,
+        #[max_length = 66]
+        stake_pool_address -> Nullable<Varchar>,
+        #[max_length = 66]
+        execution_hash -> Varchar,
+        metadata -> Jsonb,
+        min_vote_threshold -> Numeric,
+        early_resolution_vote_threshold -> Nullable<Numeric>,
+        yes_votes -> Numeric,
+        no_votes -> Numeric,
+        is_resolved -> Bool,
+        resolved_early -> Nullable<Bool>,
+        creation_timestamp -> Timestamp,
+        expiration_timestamp -> Timestamp,
+        resolution_timestamp -> Nullable<Timestamp>,
+        last_transaction_version -> Int8,
+        last_transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    current_multisig_accounts (multisig_address) {
+        #[max_length = 66]
+        multisig_address -> Varchar,
+        owners -> Jsonb,
+        num_signatures_required -> Int8,
+        last_executed_sequence_number -> Int8,
+        next_sequence_number -> Int8,
+        last_transaction_version -> Int8,
+        last_transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    current_nft_marketplace_collection_offers (collection_offer_id) {
+        #[max_length = 66]
+        collection_offer_id -> Varchar,
+        #[max_length = 66]
+        collection_id -> Varchar,
+        #[max_length = 100]
+        marketplace -> Varchar,
+        #[max_length = 66]
+        contract_address -> Varchar,
+        #[max_length = 66]
+        buyer -> Varchar,
+        item_price -> Numeric,
+        remaining_token_amount -> Numeric,
+        expiration_time -> Nullable<Numeric>,
+        #[max_length = 10]
+        token_standard -> Varchar,
+        is_deleted -> Bool,
+        last_transaction_version -> Int8,
+        last_transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    current_nft_marketplace_listings (listing_id) {
+        #[max_length = 66]
+        listing_id -> Varchar,
+        #[max_length = 66]
+        token_data_id -> Varchar,
+        #[max_length = 66]
+        collection_id -> Varchar,
+        #[max_length = 100]
+        marketplace -> Varchar,
+        #[max_length = 66]
+        contract_address -> Varchar,
+        #[max_length = 66]
+        seller -> Varchar,
+        price -> Numeric,
+        token_amount -> Numeric,
+        #[max_length = 10]
+        token_standard -> Varchar,
+        #[max_length = 50]
+        listing_type -> Varchar,
+        is_deleted -> Bool,
+        last_transaction_version -> Int8,
+        last_transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    current_nft_marketplace_token_offers (offer_id) {
+        #[max_length = 66]
+        offer_id -> Varchar,
+        #[max_length = 66]
+        token_data_id -> Varchar,
+        #[max_length = 66]
+        collection_id -> Varchar,
+        #[max_length = 100]
+        marketplace -> Varchar,
+        #[max_length = 66]
+        contract_address -> Varchar,
+        #[max_length = 66]
+        buyer -> Varchar,
+        price -> Numeric,
+        expiration_time -> Nullable<Numeric>,
+        #[max_length = 10]
+        token_standard -> Varchar,
+        is_deleted -> Bool,
+        last_transaction_version -> Int8,
+        last_transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     current_objects (object_address) {
         #[max_length = 66]
@@ -680,6 +839,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    dead_letter_transactions (processor, transaction_version) {
+        #[max_length = 50]
+        processor -> Varchar,
+        transaction_version -> Int8,
+        error -> Text,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     delegated_staking_activities (transaction_version, event_index) {
         transaction_version -> Int8,
@@ -738,6 +907,101 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    dex_liquidity_events (transaction_version, event_index) {
+        transaction_version -> Int8,
+        event_index -> Int8,
+        #[max_length = 100]
+        dex -> Varchar,
+        #[max_length = 66]
+        contract_address -> Varchar,
+        pool_id -> Text,
+        #[max_length = 64]
+        pool_id_hash -> Varchar,
+        #[max_length = 66]
+        sender -> Nullable<Varchar>,
+        #[max_length = 50]
+        event_type -> Varchar,
+        #[max_length = 1000]
+        asset_x -> Varchar,
+        amount_x -> Numeric,
+        #[max_length = 1000]
+        asset_y -> Varchar,
+        amount_y -> Numeric,
+        lp_token_amount -> Nullable<Numeric>,
+        #[max_length = 1000]
+        entry_function_id_str -> Nullable<Varchar>,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    dex_swaps (transaction_version, event_index) {
+        transaction_version -> Int8,
+        event_index -> Int8,
+        #[max_length = 100]
+        dex -> Varchar,
+        #[max_length = 66]
+        contract_address -> Varchar,
+        pool_id -> Text,
+        #[max_length = 64]
+        pool_id_hash -> Varchar,
+        #[max_length = 66]
+        sender -> Nullable<Varchar>,
+        #[max_length = 1000]
+        asset_in -> Varchar,
+        amount_in -> Numeric,
+        #[max_length = 1000]
+        asset_out -> Varchar,
+        amount_out -> Numeric,
+        #[max_length = 1000]
+        entry_function_id_str -> Nullable<Varchar>,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    epoch_validator_performances (epoch, validator_index) {
+        epoch -> Int8,
+        validator_index -> Int8,
+        successful_proposals -> Int8,
+        failed_proposals -> Int8,
+        last_transaction_version -> Int8,
+        last_transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    epoch_validators (epoch, validator_address) {
+        epoch -> Int8,
+        #[max_length = 66]
+        validator_address -> Varchar,
+        validator_index -> Int8,
+        voting_power -> Numeric,
+        consensus_public_key -> Text,
+        network_addresses -> Text,
+        fullnode_addresses -> Text,
+        transaction_version -> Int8,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    epochs (epoch) {
+        epoch -> Int8,
+        transaction_version -> Int8,
+        block_height -> Int8,
+        num_validators -> Int8,
+        total_voting_power -> Numeric,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     event_size_info (transaction_version, index) {
         transaction_version -> Int8,
@@ -844,6 +1108,82 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    fungible_asset_transfers (transaction_version, transfer_index) {
+        transaction_version -> Int8,
+        transfer_index -> Int8,
+        withdraw_event_index -> Nullable<Int8>,
+        #[max_length = 66]
+        from_address -> Nullable<Varchar>,
+        #[max_length = 66]
+        from_storage_id -> Nullable<Varchar>,
+        deposit_event_index -> Nullable<Int8>,
+        #[max_length = 66]
+        to_address -> Nullable<Varchar>,
+        #[max_length = 66]
+        to_storage_id -> Nullable<Varchar>,
+        #[max_length = 1000]
+        asset_type -> Varchar,
+        amount -> Numeric,
+        #[max_length = 10]
+        token_standard -> Varchar,
+        #[max_length = 66]
+        gas_fee_payer_address -> Nullable<Varchar>,
+        #[max_length = 1000]
+        entry_function_id_str -> Nullable<Varchar>,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    gas_fee_breakdowns (transaction_version) {
+        transaction_version -> Int8,
+        #[max_length = 66]
+        sender -> Varchar,
+        #[max_length = 66]
+        gas_fee_payer_address -> Nullable<Varchar>,
+        is_transaction_success -> Bool,
+        #[max_length = 1000]
+        entry_function_id_str -> Nullable<Varchar>,
+        gas_unit_price -> Numeric,
+        total_gas_units -> Int8,
+        execution_gas_units -> Nullable<Int8>,
+        io_gas_units -> Nullable<Int8>,
+        storage_fee_octas -> Nullable<Numeric>,
+        storage_fee_refund_octas -> Nullable<Numeric>,
+        gas_fee_octas -> Numeric,
+        block_height -> Int8,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    governance_proposals (transaction_version, proposal_id) {
+        transaction_version -> Int8,
+        proposal_id -> Int8,
+        #[max_length = 66]
+        proposer_address -> Varchar,
+        #[max_length = 66]
+        stake_pool_address -> Nullable<Varchar>,
+        #[max_length = 66]
+        execution_hash -> Varchar,
+        metadata -> Jsonb,
+        min_vote_threshold -> Numeric,
+        early_resolution_vote_threshold -> Nullable<Numeric>,
+        yes_votes -> Numeric,
+        no_votes -> Numeric,
+        is_resolved -> Bool,
+        resolved_early -> Nullable<Bool>,
+        creation_timestamp -> Timestamp,
+        expiration_timestamp -> Timestamp,
+        resolution_timestamp -> Nullable<Timestamp>,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     indexer_status (db) {
         #[max_length = 50]
@@ -896,6 +1236,81 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    multisig_activities (transaction_version, event_index) {
+        transaction_version -> Int8,
+        event_index -> Int8,
+        #[max_length = 66]
+        multisig_address -> Varchar,
+        #[max_length = 50]
+        activity_type -> Varchar,
+        sequence_number -> Nullable<Int8>,
+        #[max_length = 66]
+        account_address -> Nullable<Varchar>,
+        approved -> Nullable<Bool>,
+        owners -> Nullable<Jsonb>,
+        num_signatures_required -> Nullable<Int8>,
+        num_approvals -> Nullable<Int8>,
+        num_rejections -> Nullable<Int8>,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    multisig_transactions (multisig_address, sequence_number) {
+        #[max_length = 66]
+        multisig_address -> Varchar,
+        sequence_number -> Int8,
+        #[max_length = 66]
+        creator_address -> Varchar,
+        payload -> Nullable<Text>,
+        #[max_length = 66]
+        payload_hash -> Nullable<Varchar>,
+        transaction_version -> Int8,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    nft_marketplace_activities (transaction_version, event_index) {
+        transaction_version -> Int8,
+        event_index -> Int8,
+        #[max_length = 100]
+        marketplace -> Varchar,
+        #[max_length = 66]
+        contract_address -> Varchar,
+        #[max_length = 50]
+        event_type -> Varchar,
+        #[max_length = 66]
+        offer_or_listing_id -> Varchar,
+        #[max_length = 66]
+        collection_id -> Varchar,
+        #[max_length = 66]
+        token_data_id -> Nullable<Varchar>,
+        #[max_length = 66]
+        creator_address -> Varchar,
+        #[max_length = 128]
+        collection_name -> Varchar,
+        #[max_length = 128]
+        token_name -> Nullable<Varchar>,
+        property_version -> Nullable<Numeric>,
+        price -> Numeric,
+        token_amount -> Numeric,
+        #[max_length = 10]
+        token_standard -> Varchar,
+        #[max_length = 66]
+        seller -> Nullable<Varchar>,
+        #[max_length = 66]
+        buyer -> Nullable<Varchar>,
+        #[max_length = 1000]
+        entry_function_id_str -> Nullable<Varchar>,
+        transaction_timestamp -> Timestamp,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     nft_points (transaction_version) {
         transaction_version -> Int8,
@@ -927,6 +1342,55 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    parquet_table_status (processor, table_name) {
+        #[max_length = 50]
+        processor -> Varchar,
+        #[max_length = 100]
+        table_name -> Varchar,
+        last_success_version -> Int8,
+        last_updated -> Timestamp,
+    }
+}
+
+diesel::table! {
+    parquet_upload_manifests (processor, table_name, start_version, end_version) {
+        #[max_length = 50]
+        processor -> Varchar,
+        #[max_length = 100]
+        table_name -> Varchar,
+        start_version -> Int8,
+        end_version -> Int8,
+        file_path -> Nullable<Text>,
+        num_records -> Int8,
+        file_size -> Int8,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    processor_chain_ids (processor) {
+        #[max_length = 50]
+        processor -> Varchar,
+        chain_id -> Int8,
+        inserted_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    processor_gaps (processor, start_version, end_version) {
+        #[max_length = 50]
+        processor -> Varchar,
+        start_version -> Int8,
+        end_version -> Int8,
+        reason -> Text,
+        attempts -> Int4,
+        is_resolved -> Bool,
+        inserted_at -> Timestamp,
+        last_updated -> Timestamp,
+    }
+}
+
 diesel::table! {
     processor_status (processor) {
         #[max_length = 50]
@@ -937,6 +1401,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    processor_status_history (processor, recorded_at) {
+        #[max_length = 50]
+        processor -> Varchar,
+        last_success_version -> Int8,
+        last_transaction_timestamp -> Nullable<Timestamp>,
+        recorded_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     proposal_votes (transaction_version, proposal_id, voter_address) {
         transaction_version -> Int8,
@@ -952,6 +1426,20 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    sender_daily_gas_fees (sender, date) {
+        #[max_length = 66]
+        sender -> Varchar,
+        date -> Date,
+        num_transactions -> Int8,
+        total_gas_units -> Numeric,
+        gas_fee_octas -> Numeric,
+        storage_fee_refund_octas -> Numeric,
+        last_transaction_version -> Int8,
+        inserted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     signatures (transaction_version, multi_agent_index, multi_sig_index, is_sender_primary) {
         transaction_version -> Int8,
@@ -1281,11 +1769,13 @@ diesel::table! {
 }
 
 diesel::allow_tables_to_appear_in_same_query!(
+    account_authentication_keys,
     account_transactions,
     ans_lookup,
     ans_lookup_v2,
     ans_primary_name,
     ans_primary_name_v2,
+    audit_discrepancies,
     block_metadata_transactions,
     coin_activities,
     coin_balances,
@@ -1297,6 +1787,7 @@ diesel::allow_tables_to_appear_in_same_query!(
     current_ans_lookup_v2,
     current_ans_primary_name,
     current_ans_primary_name_v2,
+    current_auth_key_to_address,
     current_coin_balances,
     current_collection_datas,
     current_collections_v2,
@@ -1304,6 +1795,11 @@ diesel::allow_tables_to_appear_in_same_query!(
     current_delegated_voter,
     current_delegator_balances,
     current_fungible_asset_balances,
+    current_governance_proposals,
+    current_multisig_accounts,
+    current_nft_marketplace_collection_offers,
+    current_nft_marketplace_listings,
+    current_nft_marketplace_token_offers,
     current_objects,
     current_staking_pool_voter,
     current_table_items,
@@ -1315,23 +1811,41 @@ diesel::allow_tables_to_appear_in_same_query!(
     current_token_royalty_v1,
     current_token_v2_metadata,
     current_unified_fungible_asset_balances_to_be_renamed,
+    dead_letter_transactions,
     delegated_staking_activities,
     delegated_staking_pool_balances,
     delegated_staking_pools,
     delegator_balances,
+    dex_liquidity_events,
+    dex_swaps,
+    epoch_validator_performances,
+    epoch_validators,
+    epochs,
     event_size_info,
     events,
     fungible_asset_activities,
     fungible_asset_balances,
     fungible_asset_metadata,
+    fungible_asset_transfers,
+    gas_fee_breakdowns,
+    governance_proposals,
     indexer_status,
     ledger_infos,
     move_modules,
     move_resources,
+    multisig_activities,
+    multisig_transactions,
+    nft_marketplace_activities,
     nft_points,
     objects,
+    parquet_table_status,
+    parquet_upload_manifests,
+    processor_chain_ids,
+    processor_gaps,
     processor_status,
+    processor_status_history,
     proposal_votes,
+    sender_daily_gas_fees,
     signatures,
     spam_assets,
     table_items,
@@ -17,6 +17,8 @@ pub struct DefaultGapDetector {
 pub struct DefaultGapDetectorResult {
     pub next_version_to_process: u64,
     pub num_gaps: u64,
+    // Last version of the gap that starts at `next_version_to_process`, if there's one
+    pub gap_end_version: Option<u64>,
     pub last_success_batch: Option<DefaultProcessingResult>,
 }
 
@@ -38,6 +40,11 @@ impl GapDetectorTrait for DefaultGapDetector {
                     DefaultGapDetectorResult {
                         next_version_to_process: self.next_version_to_process,
                         num_gaps: self.seen_versions.len() as u64,
+                        gap_end_version: self
+                            .seen_versions
+                            .keys()
+                            .min()
+                            .map(|start_version| start_version - 1),
                         last_success_batch: self.last_success_batch.clone(),
                     },
                 ))
@@ -98,6 +105,7 @@ mod test {
 
             assert_eq!(default_gap_detector_result.num_gaps, i + 1);
             assert_eq!(default_gap_detector_result.next_version_to_process, 0);
+            assert_eq!(default_gap_detector_result.gap_end_version, Some(99));
             assert_eq!(default_gap_detector_result.last_success_batch, None);
         }
 
@@ -118,6 +126,7 @@ mod test {
             _ => panic!("Invalid result type"),
         };
         assert_eq!(default_gap_detector_result.num_gaps, 0);
+        assert_eq!(default_gap_detector_result.gap_end_version, None);
         assert_eq!(
             default_gap_detector_result.next_version_to_process,
             100 + (DEFAULT_GAP_DETECTION_BATCH_SIZE) * 100
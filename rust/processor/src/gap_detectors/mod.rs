@@ -1,16 +1,29 @@
 use crate::{
     bq_analytics::ParquetProcessingResult,
+    db::common::models::{
+        processor_gap::{ProcessorGap, MISSING_VERSIONS_REASON},
+        processor_status_history::INSERT_PROCESSOR_STATUS_HISTORY_QUERY,
+    },
     gap_detectors::{
         gap_detector::{DefaultGapDetector, DefaultGapDetectorResult},
         parquet_gap_detector::{ParquetFileGapDetector, ParquetFileGapDetectorResult},
     },
     processors::{DefaultProcessingResult, Processor, ProcessorTrait},
-    utils::counters::{PARQUET_PROCESSOR_DATA_GAP_COUNT, PROCESSOR_DATA_GAP_COUNT},
+    utils::{
+        counters::{PARQUET_PROCESSOR_DATA_GAP_COUNT, PROCESSOR_DATA_GAP_COUNT},
+        database::{execute_with_better_error, ArcDbPool},
+    },
     worker::PROCESSOR_SERVICE_TYPE,
 };
 use anyhow::Result;
+use diesel::{
+    sql_query,
+    sql_types::{Text, Timestamp},
+};
 use enum_dispatch::enum_dispatch;
 use kanal::AsyncReceiver;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
 use tracing::{error, info};
 pub mod gap_detector;
 pub mod parquet_gap_detector;
@@ -67,6 +80,8 @@ pub async fn create_gap_detector_status_tracker_loop(
     );
 
     let mut last_update_time = std::time::Instant::now();
+    // Missing range persisted to processor_gaps, until the versions show up or get replayed
+    let mut recorded_gap: Option<ProcessorGap> = None;
     loop {
         match gap_detector_receiver.recv().await {
             Ok(ProcessingResult::DefaultProcessingResult(result)) => {
@@ -87,6 +102,43 @@ pub async fn create_gap_detector_status_tracker_loop(
                                     "[Parser] Processed {gap_detection_batch_size} batches with a gap",
                                 );
                                     // We don't panic as everything downstream will panic if it doesn't work/receive
+                                    if let Some(gap_end_version) = res.gap_end_version {
+                                        let gap = ProcessorGap::new(
+                                            processor_name,
+                                            res.next_version_to_process,
+                                            gap_end_version,
+                                            MISSING_VERSIONS_REASON.to_string(),
+                                        );
+                                        if recorded_gap.as_ref().map(|g| g.start_version)
+                                            != Some(gap.start_version)
+                                        {
+                                            if let Err(e) = gap.record(processor.get_pool()).await {
+                                                error!(
+                                                    processor_name,
+                                                    service_type = PROCESSOR_SERVICE_TYPE,
+                                                    error = ?e,
+                                                    "[Parser] Failed to record processor gap"
+                                                );
+                                            }
+                                            recorded_gap = Some(gap);
+                                        }
+                                    }
+                                }
+                                // The missing batches eventually arrived
+                                if let Some(gap) = recorded_gap.as_ref() {
+                                    if res.next_version_to_process as i64 > gap.start_version {
+                                        if let Err(e) =
+                                            gap.mark_resolved(processor.get_pool()).await
+                                        {
+                                            error!(
+                                                processor_name,
+                                                service_type = PROCESSOR_SERVICE_TYPE,
+                                                error = ?e,
+                                                "[Parser] Failed to resolve processor gap"
+                                            );
+                                        }
+                                        recorded_gap = None;
+                                    }
                                 }
                                 if let Some(res_last_success_batch) = res.last_success_batch {
                                     if last_update_time.elapsed().as_secs()
@@ -147,20 +199,25 @@ pub async fn create_gap_detector_status_tracker_loop(
                                     // We don't panic as everything downstream will panic if it doesn't work/receive
                                 }
 
-                                if last_update_time.elapsed().as_secs()
-                                    >= UPDATE_PROCESSOR_STATUS_SECS
-                                {
-                                    tracing::info!("Updating last processed version");
-                                    processor
-                                        .update_last_processed_version(
-                                            res.start_version,
-                                            res.last_transaction_timestamp,
-                                        )
-                                        .await
-                                        .unwrap();
-                                    last_update_time = std::time::Instant::now();
-                                } else {
-                                    tracing::info!("Not Updating last processed version");
+                                // Only report the contiguous version that has been fully uploaded
+                                match res.last_success_version {
+                                    Some(last_success_version)
+                                        if last_update_time.elapsed().as_secs()
+                                            >= UPDATE_PROCESSOR_STATUS_SECS =>
+                                    {
+                                        tracing::info!("Updating last processed version");
+                                        processor
+                                            .update_last_processed_version(
+                                                last_success_version,
+                                                res.last_transaction_timestamp,
+                                            )
+                                            .await
+                                            .unwrap();
+                                        last_update_time = std::time::Instant::now();
+                                    },
+                                    _ => {
+                                        tracing::info!("Not Updating last processed version");
+                                    },
                                 }
                             },
                             _ => {
@@ -191,3 +248,29 @@ pub async fn create_gap_detector_status_tracker_loop(
         };
     }
 }
+
+/// Appends the processor status to processor_status_history every interval. It runs on its own
+/// timer rather than on status updates, so the history keeps going while the processor is stalled.
+pub async fn create_processor_status_history_loop(
+    db_pool: ArcDbPool,
+    processor_name: &'static str,
+    interval_secs: u64,
+) {
+    let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let query = sql_query(INSERT_PROCESSOR_STATUS_HISTORY_QUERY)
+            .bind::<Text, _>(processor_name)
+            .bind::<Timestamp, _>(chrono::Utc::now().naive_utc());
+        // The history is only used for reporting, so failing to record it doesn't stop the processor
+        if let Err(e) = execute_with_better_error(db_pool.clone(), query, None).await {
+            error!(
+                processor_name,
+                service_type = PROCESSOR_SERVICE_TYPE,
+                error = ?e,
+                "[Parser] Failed to record processor status history"
+            );
+        }
+    }
+}
@@ -8,24 +8,30 @@ use std::cmp::max;
 use tracing::{debug, info};
 
 pub struct ParquetFileGapDetector {
+    starting_version: i64,
     next_version_to_process: i64,
     version_counters: AHashMap<i64, i64>,
     max_version: i64,
+    // Results of structs skipped by a table carry no timestamp, the last known one is reported
+    last_transaction_timestamp: Option<aptos_protos::util::timestamp::Timestamp>,
 }
 
 pub struct ParquetFileGapDetectorResult {
     pub next_version_to_process: u64,
     pub num_gaps: u64,
-    pub start_version: u64,
+    // Last version up to which all structs have been uploaded, None until the first version is
+    pub last_success_version: Option<u64>,
     pub last_transaction_timestamp: Option<aptos_protos::util::timestamp::Timestamp>,
 }
 
 impl ParquetFileGapDetector {
     pub fn new(starting_version: u64) -> Self {
         Self {
+            starting_version: starting_version as i64,
             next_version_to_process: starting_version as i64,
             version_counters: AHashMap::new(),
             max_version: 0,
+            last_transaction_timestamp: None,
         }
     }
 }
@@ -46,6 +52,10 @@ impl GapDetectorTrait for ParquetFileGapDetector {
             *self.version_counters.entry(*version).or_default() -= 1;
         }
 
+        if result.last_transaction_timestamp.is_some() {
+            self.last_transaction_timestamp = result.last_transaction_timestamp;
+        }
+
         // Update next version to process and move forward
         let mut current_version = result.start_version;
 
@@ -79,8 +89,9 @@ impl GapDetectorTrait for ParquetFileGapDetector {
             ParquetFileGapDetectorResult {
                 next_version_to_process: self.next_version_to_process as u64,
                 num_gaps: (self.max_version - self.next_version_to_process) as u64,
-                start_version: result.start_version as u64,
-                last_transaction_timestamp: result.last_transaction_timestamp,
+                last_success_version: (self.next_version_to_process > self.starting_version)
+                    .then(|| (self.next_version_to_process - 1) as u64),
+                last_transaction_timestamp: self.last_transaction_timestamp.clone(),
             },
         ))
     }
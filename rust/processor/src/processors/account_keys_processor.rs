@@ -0,0 +1,242 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{DefaultProcessingResult, ProcessorName, ProcessorTrait};
+use crate::{
+    db::common::models::account_key_models::{
+        account_authentication_keys::AccountAuthenticationKey,
+        current_auth_key_to_address::CurrentAuthKeyToAddress,
+    },
+    gap_detectors::ProcessingResult,
+    schema,
+    utils::{
+        database::{execute_in_chunks, get_config_table_chunk_size, ArcDbPool},
+        util::parse_timestamp,
+    },
+};
+use ahash::AHashMap;
+use anyhow::bail;
+use aptos_protos::transaction::v1::{transaction::TxnData, write_set_change::Change, Transaction};
+use async_trait::async_trait;
+use diesel::{
+    pg::{upsert::excluded, Pg},
+    query_builder::QueryFragment,
+    ExpressionMethods,
+};
+use std::fmt::Debug;
+use tracing::error;
+
+/// Tracks the key rotations of each account and the current authentication key of each account,
+/// so that wallets can find the account controlled by a key after it was rotated.
+pub struct AccountKeysProcessor {
+    connection_pool: ArcDbPool,
+    per_table_chunk_sizes: AHashMap<String, usize>,
+}
+
+impl AccountKeysProcessor {
+    pub fn new(connection_pool: ArcDbPool, per_table_chunk_sizes: AHashMap<String, usize>) -> Self {
+        Self {
+            connection_pool,
+            per_table_chunk_sizes,
+        }
+    }
+}
+
+impl Debug for AccountKeysProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = &self.connection_pool.state();
+        write!(
+            f,
+            "AccountKeysProcessor {{ connections: {:?}  idle_connections: {:?} }}",
+            state.connections, state.idle_connections
+        )
+    }
+}
+
+async fn insert_to_db(
+    conn: ArcDbPool,
+    name: &'static str,
+    start_version: u64,
+    end_version: u64,
+    account_authentication_keys: &[AccountAuthenticationKey],
+    current_auth_key_to_address: &[CurrentAuthKeyToAddress],
+    per_table_chunk_sizes: &AHashMap<String, usize>,
+) -> Result<(), diesel::result::Error> {
+    tracing::trace!(
+        name = name,
+        start_version = start_version,
+        end_version = end_version,
+        "Inserting to db",
+    );
+    let aak = execute_in_chunks(
+        conn.clone(),
+        insert_account_authentication_keys_query,
+        account_authentication_keys,
+        get_config_table_chunk_size::<AccountAuthenticationKey>(
+            "account_authentication_keys",
+            per_table_chunk_sizes,
+        ),
+    );
+    let cakta = execute_in_chunks(
+        conn,
+        insert_current_auth_key_to_address_query,
+        current_auth_key_to_address,
+        get_config_table_chunk_size::<CurrentAuthKeyToAddress>(
+            "current_auth_key_to_address",
+            per_table_chunk_sizes,
+        ),
+    );
+    let (aak_res, cakta_res) = tokio::join!(aak, cakta);
+    for res in [aak_res, cakta_res] {
+        res?;
+    }
+    Ok(())
+}
+
+fn insert_account_authentication_keys_query(
+    items_to_insert: Vec<AccountAuthenticationKey>,
+) -> (
+    impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send,
+    Option<&'static str>,
+) {
+    use schema::account_authentication_keys::dsl::*;
+    (
+        diesel::insert_into(schema::account_authentication_keys::table)
+            .values(items_to_insert)
+            .on_conflict((transaction_version, event_index))
+            .do_nothing(),
+        None,
+    )
+}
+
+fn insert_current_auth_key_to_address_query(
+    items_to_insert: Vec<CurrentAuthKeyToAddress>,
+) -> (
+    impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send,
+    Option<&'static str>,
+) {
+    use schema::current_auth_key_to_address::dsl::*;
+    (
+        diesel::insert_into(schema::current_auth_key_to_address::table)
+            .values(items_to_insert)
+            .on_conflict(account_address)
+            .do_update()
+            .set((
+                authentication_key.eq(excluded(authentication_key)),
+                last_transaction_version.eq(excluded(last_transaction_version)),
+                last_transaction_timestamp.eq(excluded(last_transaction_timestamp)),
+                inserted_at.eq(excluded(inserted_at)),
+            )),
+        Some(" WHERE current_auth_key_to_address.last_transaction_version <= excluded.last_transaction_version "),
+    )
+}
+
+#[async_trait]
+impl ProcessorTrait for AccountKeysProcessor {
+    fn name(&self) -> &'static str {
+        ProcessorName::AccountKeysProcessor.into()
+    }
+
+    async fn process_transactions(
+        &self,
+        transactions: Vec<Transaction>,
+        start_version: u64,
+        end_version: u64,
+        _: Option<u64>,
+    ) -> anyhow::Result<ProcessingResult> {
+        let processing_start = std::time::Instant::now();
+        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+
+        let mut account_authentication_keys = vec![];
+        let mut current_auth_key_to_address: AHashMap<String, CurrentAuthKeyToAddress> =
+            AHashMap::new();
+        for txn in &transactions {
+            let txn_version = txn.version as i64;
+            let txn_timestamp = parse_timestamp(txn.timestamp.as_ref().unwrap(), txn_version);
+
+            // Key rotations can only be done by the account itself
+            if let Some(TxnData::User(user_txn)) = txn.txn_data.as_ref() {
+                for (index, event) in user_txn.events.iter().enumerate() {
+                    if let Some(key) = AccountAuthenticationKey::from_event(
+                        event,
+                        txn_version,
+                        index as i64,
+                        txn_timestamp,
+                    )? {
+                        account_authentication_keys.push(key);
+                    }
+                }
+            }
+
+            // The account resource is written on account creation as well as on rotation
+            let changes = &txn
+                .info
+                .as_ref()
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Transaction info doesn't exist! Transaction {}",
+                        txn_version
+                    )
+                })
+                .changes;
+            for wsc in changes {
+                if let Some(Change::WriteResource(wr)) = wsc.change.as_ref() {
+                    if let Some(current) = CurrentAuthKeyToAddress::from_write_resource(
+                        wr,
+                        txn_version,
+                        txn_timestamp,
+                    )? {
+                        current_auth_key_to_address
+                            .insert(current.account_address.clone(), current);
+                    }
+                }
+            }
+        }
+        // Sort by PK to avoid deadlocks between concurrent upserts
+        let mut current_auth_key_to_address = current_auth_key_to_address
+            .into_values()
+            .collect::<Vec<_>>();
+        current_auth_key_to_address.sort_by(|a, b| a.account_address.cmp(&b.account_address));
+
+        let processing_duration_in_secs = processing_start.elapsed().as_secs_f64();
+        let db_insertion_start = std::time::Instant::now();
+
+        let tx_result = insert_to_db(
+            self.get_pool(),
+            self.name(),
+            start_version,
+            end_version,
+            &account_authentication_keys,
+            &current_auth_key_to_address,
+            &self.per_table_chunk_sizes,
+        )
+        .await;
+        let db_insertion_duration_in_secs = db_insertion_start.elapsed().as_secs_f64();
+
+        match tx_result {
+            Ok(_) => Ok(ProcessingResult::DefaultProcessingResult(
+                DefaultProcessingResult {
+                    start_version,
+                    end_version,
+                    processing_duration_in_secs,
+                    db_insertion_duration_in_secs,
+                    last_transaction_timestamp,
+                },
+            )),
+            Err(e) => {
+                error!(
+                    start_version = start_version,
+                    end_version = end_version,
+                    processor_name = self.name(),
+                    error = ?e,
+                    "[Parser] Error inserting transactions to db",
+                );
+                bail!(e)
+            },
+        }
+    }
+
+    fn connection_pool(&self) -> &ArcDbPool {
+        &self.connection_pool
+    }
+}
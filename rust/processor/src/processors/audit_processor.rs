@@ -0,0 +1,366 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{DefaultProcessingResult, ProcessorName, ProcessorTrait};
+use crate::{
+    db::common::models::{
+        audit_models::audit_discrepancies::{
+            AuditDiscrepancy, FungibleAssetBalanceMismatch, ResourceBalanceMismatch,
+            TokenOwnerMismatch,
+        },
+        processor_status::ProcessorStatusQuery,
+    },
+    gap_detectors::ProcessingResult,
+    schema,
+    utils::{
+        counters::AUDIT_DISCREPANCIES_COUNT,
+        database::{execute_in_chunks, get_config_table_chunk_size, ArcDbPool},
+    },
+};
+use ahash::AHashMap;
+use aptos_protos::transaction::v1::Transaction;
+use async_trait::async_trait;
+use diesel::{pg::Pg, query_builder::QueryFragment};
+use serde::{Deserialize, Serialize};
+use std::{fmt::Debug, sync::Mutex, time::Instant};
+use tracing::{info, warn};
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize, strum::IntoStaticStr)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AuditCheck {
+    /// current_fungible_asset_balances amounts equal their balance before the audited range in
+    /// fungible_asset_balances plus the fungible_asset_activities of the range
+    FungibleAssetBalances,
+    /// current_token_ownerships_v2 owners of v2 tokens equal current_objects owners
+    TokenOwners,
+    /// current_fungible_asset_balances amounts equal the balances of the CoinStore and
+    /// FungibleStore resources in move_resources
+    FungibleAssetBalanceResources,
+    /// Same as above for current_unified_fungible_asset_balances
+    UnifiedFungibleAssetBalanceResources,
+}
+
+impl AuditCheck {
+    /// Processors writing the tables of the check, they have to be past the audited range
+    fn audited_processors(&self) -> &'static [ProcessorName] {
+        match self {
+            AuditCheck::FungibleAssetBalances => &[ProcessorName::FungibleAssetProcessor],
+            AuditCheck::TokenOwners => &[
+                ProcessorName::ObjectsProcessor,
+                ProcessorName::TokenV2Processor,
+            ],
+            AuditCheck::FungibleAssetBalanceResources
+            | AuditCheck::UnifiedFungibleAssetBalanceResources => &[
+                ProcessorName::DefaultProcessor,
+                ProcessorName::FungibleAssetProcessor,
+            ],
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuditProcessorConfig {
+    // The resource balance checks need move_resources, so they're not enabled by default
+    #[serde(default = "AuditProcessorConfig::default_checks")]
+    pub checks: Vec<AuditCheck>,
+    // A batch is audited if it contains a multiple of this version
+    #[serde(default = "AuditProcessorConfig::default_sample_interval_versions")]
+    pub sample_interval_versions: u64,
+    // Maximum number of rows cross-checked per check and sampled batch
+    #[serde(default = "AuditProcessorConfig::default_max_rows_per_check")]
+    pub max_rows_per_check: i64,
+    // How long a sampled batch is deferred, waiting for the audited processors to reach it,
+    // before it's skipped
+    #[serde(default = "AuditProcessorConfig::default_max_wait_secs")]
+    pub max_wait_secs: u64,
+}
+
+impl AuditProcessorConfig {
+    pub fn default_checks() -> Vec<AuditCheck> {
+        vec![AuditCheck::FungibleAssetBalances, AuditCheck::TokenOwners]
+    }
+
+    pub const fn default_sample_interval_versions() -> u64 {
+        100_000
+    }
+
+    pub const fn default_max_rows_per_check() -> i64 {
+        1000
+    }
+
+    pub const fn default_max_wait_secs() -> u64 {
+        600
+    }
+}
+
+/// A sampled range whose audited processors were behind, checked again with the next batches
+struct DeferredCheck {
+    check: AuditCheck,
+    start_version: u64,
+    end_version: u64,
+    deferred_at: Instant,
+}
+
+/// Cross-checks tables written by other processors for sampled version ranges and records
+/// the discrepancies in audit_discrepancies. The transactions themselves aren't processed.
+pub struct AuditProcessor {
+    connection_pool: ArcDbPool,
+    config: AuditProcessorConfig,
+    per_table_chunk_sizes: AHashMap<String, usize>,
+    // Only kept in memory, the deferred ranges of a previous run aren't audited
+    deferred_checks: Mutex<Vec<DeferredCheck>>,
+}
+
+impl AuditProcessor {
+    pub fn new(
+        connection_pool: ArcDbPool,
+        config: AuditProcessorConfig,
+        per_table_chunk_sizes: AHashMap<String, usize>,
+    ) -> Self {
+        Self {
+            connection_pool,
+            config,
+            per_table_chunk_sizes,
+            deferred_checks: Mutex::new(vec![]),
+        }
+    }
+
+    /// Whether all the processors of the check processed the range
+    async fn audited_processors_caught_up(&self, check: AuditCheck, end_version: u64) -> bool {
+        let mut conn = self.get_conn().await;
+        for processor_name in check.audited_processors() {
+            let processor_name: &'static str = processor_name.into();
+            let last_success_version =
+                ProcessorStatusQuery::get_by_processor(processor_name, &mut conn)
+                    .await
+                    .ok()
+                    .flatten()
+                    .map(|status| status.last_success_version);
+            if !last_success_version.is_some_and(|version| version >= end_version as i64) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Returns the checks of the sampled batch and the deferred ones that can run now. Checks
+    /// whose audited processors are behind are deferred instead of holding the batch, and
+    /// skipped once they've been deferred for `max_wait_secs`.
+    async fn get_ready_checks(
+        &self,
+        start_version: u64,
+        end_version: u64,
+    ) -> Vec<(AuditCheck, u64, u64)> {
+        let mut pending_checks = std::mem::take(&mut *self.deferred_checks.lock().unwrap());
+        if is_sampled(
+            start_version,
+            end_version,
+            self.config.sample_interval_versions,
+        ) {
+            let deferred_at = Instant::now();
+            pending_checks.extend(self.config.checks.iter().map(|check| DeferredCheck {
+                check: *check,
+                start_version,
+                end_version,
+                deferred_at,
+            }));
+        }
+
+        let mut ready_checks = vec![];
+        let mut deferred_checks = vec![];
+        for pending in pending_checks {
+            if self
+                .audited_processors_caught_up(pending.check, pending.end_version)
+                .await
+            {
+                ready_checks.push((pending.check, pending.start_version, pending.end_version));
+            } else if pending.deferred_at.elapsed().as_secs() >= self.config.max_wait_secs {
+                warn!(
+                    processor_name = self.name(),
+                    check_name = <&'static str>::from(pending.check),
+                    start_version = pending.start_version,
+                    end_version = pending.end_version,
+                    "[Parser] Audited processors are behind, skipping audit"
+                );
+            } else {
+                deferred_checks.push(pending);
+            }
+        }
+        self.deferred_checks.lock().unwrap().extend(deferred_checks);
+        ready_checks
+    }
+
+    async fn run_check(
+        &self,
+        check: AuditCheck,
+        start_version: i64,
+        end_version: i64,
+    ) -> anyhow::Result<Vec<AuditDiscrepancy>> {
+        let check_name: &'static str = check.into();
+        let limit = self.config.max_rows_per_check;
+        let mut conn = self.get_conn().await;
+        let discrepancies = match check {
+            AuditCheck::FungibleAssetBalances => {
+                FungibleAssetBalanceMismatch::get_by_version_range(
+                    start_version,
+                    end_version,
+                    limit,
+                    &mut conn,
+                )
+                .await?
+                .iter()
+                .map(|m| m.to_discrepancy(check_name, start_version, end_version))
+                .collect::<Vec<_>>()
+            },
+            AuditCheck::TokenOwners => TokenOwnerMismatch::get_by_version_range(
+                start_version,
+                end_version,
+                limit,
+                &mut conn,
+            )
+            .await?
+            .iter()
+            .map(|m| m.to_discrepancy(check_name, start_version, end_version))
+            .collect::<Vec<_>>(),
+            AuditCheck::FungibleAssetBalanceResources => {
+                ResourceBalanceMismatch::get_fungible_asset_balances_by_version_range(
+                    start_version,
+                    end_version,
+                    limit,
+                    &mut conn,
+                )
+                .await?
+                .iter()
+                .map(|m| m.to_discrepancy(check_name, start_version, end_version))
+                .collect::<Vec<_>>()
+            },
+            AuditCheck::UnifiedFungibleAssetBalanceResources => {
+                ResourceBalanceMismatch::get_unified_fungible_asset_balances_by_version_range(
+                    start_version,
+                    end_version,
+                    limit,
+                    &mut conn,
+                )
+                .await?
+                .iter()
+                .map(|m| m.to_discrepancy(check_name, start_version, end_version))
+                .collect::<Vec<_>>()
+            },
+        };
+        AUDIT_DISCREPANCIES_COUNT
+            .with_label_values(&[check_name])
+            .inc_by(discrepancies.len() as u64);
+        Ok(discrepancies)
+    }
+}
+
+impl Debug for AuditProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = &self.connection_pool.state();
+        write!(
+            f,
+            "AuditProcessor {{ connections: {:?}  idle_connections: {:?} }}",
+            state.connections, state.idle_connections
+        )
+    }
+}
+
+/// Whether the batch contains a multiple of the sample interval
+pub fn is_sampled(start_version: u64, end_version: u64, sample_interval_versions: u64) -> bool {
+    let sample_interval_versions = sample_interval_versions.max(1);
+    start_version.div_ceil(sample_interval_versions) * sample_interval_versions <= end_version
+}
+
+fn insert_audit_discrepancies_query(
+    items_to_insert: Vec<AuditDiscrepancy>,
+) -> (
+    impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send,
+    Option<&'static str>,
+) {
+    (
+        diesel::insert_into(schema::audit_discrepancies::table)
+            .values(items_to_insert)
+            .on_conflict_do_nothing(),
+        None,
+    )
+}
+
+#[async_trait]
+impl ProcessorTrait for AuditProcessor {
+    fn name(&self) -> &'static str {
+        ProcessorName::AuditProcessor.into()
+    }
+
+    async fn process_transactions(
+        &self,
+        transactions: Vec<Transaction>,
+        start_version: u64,
+        end_version: u64,
+        _: Option<u64>,
+    ) -> anyhow::Result<ProcessingResult> {
+        let processing_start = std::time::Instant::now();
+        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+
+        let mut all_discrepancies = vec![];
+        for (check, check_start_version, check_end_version) in
+            self.get_ready_checks(start_version, end_version).await
+        {
+            let discrepancies = self
+                .run_check(check, check_start_version as i64, check_end_version as i64)
+                .await?;
+            info!(
+                processor_name = self.name(),
+                check_name = <&'static str>::from(check),
+                start_version = check_start_version,
+                end_version = check_end_version,
+                num_discrepancies = discrepancies.len(),
+                "[Parser] Audited version range"
+            );
+            all_discrepancies.extend(discrepancies);
+        }
+
+        let processing_duration_in_secs = processing_start.elapsed().as_secs_f64();
+        let db_insertion_start = std::time::Instant::now();
+        execute_in_chunks(
+            self.get_pool(),
+            insert_audit_discrepancies_query,
+            &all_discrepancies,
+            get_config_table_chunk_size::<AuditDiscrepancy>(
+                "audit_discrepancies",
+                &self.per_table_chunk_sizes,
+            ),
+        )
+        .await?;
+        let db_insertion_duration_in_secs = db_insertion_start.elapsed().as_secs_f64();
+
+        Ok(ProcessingResult::DefaultProcessingResult(
+            DefaultProcessingResult {
+                start_version,
+                end_version,
+                processing_duration_in_secs,
+                db_insertion_duration_in_secs,
+                last_transaction_timestamp,
+            },
+        ))
+    }
+
+    fn connection_pool(&self) -> &ArcDbPool {
+        &self.connection_pool
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_sampled() {
+        assert!(is_sampled(0, 99, 1000));
+        assert!(!is_sampled(1, 999, 1000));
+        assert!(is_sampled(1, 1000, 1000));
+        assert!(is_sampled(1000, 1000, 1000));
+        assert!(is_sampled(5, 5, 0));
+    }
+}
@@ -13,7 +13,10 @@ use crate::{
     },
     gap_detectors::ProcessingResult,
     schema,
-    utils::database::{execute_in_chunks, get_config_table_chunk_size, ArcDbPool},
+    utils::{
+        database::{execute_in_chunks, get_config_table_chunk_size, ArcDbPool},
+        dead_letter::spawn_blocking,
+    },
     worker::TableFlags,
 };
 use ahash::AHashMap;
@@ -330,7 +333,7 @@ impl ProcessorTrait for DefaultProcessor {
             block_metadata_transactions,
             write_set_changes,
             (move_modules, move_resources, table_items, current_table_items, table_metadata),
-        ) = tokio::task::spawn_blocking(move || process_transactions(transactions, flags))
+        ) = spawn_blocking(move || process_transactions(transactions, flags))
             .await
             .expect("Failed to spawn_blocking for TransactionModel::from_transactions");
         let processing_duration_in_secs = processing_start.elapsed().as_secs_f64();
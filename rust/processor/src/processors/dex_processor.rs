@@ -0,0 +1,198 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{DefaultProcessingResult, ProcessorName, ProcessorTrait};
+use crate::{
+    db::common::models::dex_models::{
+        dex_liquidity_events::DexLiquidityEvent,
+        dex_swaps::DexSwap,
+        dex_utils::{parse_transaction, DexConfig},
+    },
+    gap_detectors::ProcessingResult,
+    schema,
+    utils::database::{execute_in_chunks, get_config_table_chunk_size, ArcDbPool},
+};
+use ahash::AHashMap;
+use anyhow::bail;
+use aptos_protos::transaction::v1::Transaction;
+use async_trait::async_trait;
+use diesel::{pg::Pg, query_builder::QueryFragment};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use tracing::error;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DexProcessorConfig {
+    pub dexes: Vec<DexConfig>,
+}
+
+/// Indexes the swaps and liquidity changes of the configured dexes into a schema shared by all
+/// dexes, each dex being read by the adapter for its events.
+pub struct DexProcessor {
+    connection_pool: ArcDbPool,
+    config: DexProcessorConfig,
+    per_table_chunk_sizes: AHashMap<String, usize>,
+}
+
+impl DexProcessor {
+    pub fn new(
+        connection_pool: ArcDbPool,
+        config: DexProcessorConfig,
+        per_table_chunk_sizes: AHashMap<String, usize>,
+    ) -> Self {
+        Self {
+            connection_pool,
+            config,
+            per_table_chunk_sizes,
+        }
+    }
+}
+
+impl Debug for DexProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = &self.connection_pool.state();
+        write!(
+            f,
+            "DexProcessor {{ connections: {:?}  idle_connections: {:?} }}",
+            state.connections, state.idle_connections
+        )
+    }
+}
+
+async fn insert_to_db(
+    conn: ArcDbPool,
+    name: &'static str,
+    start_version: u64,
+    end_version: u64,
+    swaps: &[DexSwap],
+    liquidity_events: &[DexLiquidityEvent],
+    per_table_chunk_sizes: &AHashMap<String, usize>,
+) -> Result<(), diesel::result::Error> {
+    tracing::trace!(
+        name = name,
+        start_version = start_version,
+        end_version = end_version,
+        "Inserting to db",
+    );
+
+    let s = execute_in_chunks(
+        conn.clone(),
+        insert_dex_swaps_query,
+        swaps,
+        get_config_table_chunk_size::<DexSwap>("dex_swaps", per_table_chunk_sizes),
+    );
+    let le = execute_in_chunks(
+        conn,
+        insert_dex_liquidity_events_query,
+        liquidity_events,
+        get_config_table_chunk_size::<DexLiquidityEvent>(
+            "dex_liquidity_events",
+            per_table_chunk_sizes,
+        ),
+    );
+    let (s_res, le_res) = tokio::join!(s, le);
+    for res in [s_res, le_res] {
+        res?;
+    }
+
+    Ok(())
+}
+
+fn insert_dex_swaps_query(
+    items_to_insert: Vec<DexSwap>,
+) -> (
+    impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send,
+    Option<&'static str>,
+) {
+    use schema::dex_swaps::dsl::*;
+    (
+        diesel::insert_into(schema::dex_swaps::table)
+            .values(items_to_insert)
+            .on_conflict((transaction_version, event_index))
+            .do_nothing(),
+        None,
+    )
+}
+
+fn insert_dex_liquidity_events_query(
+    items_to_insert: Vec<DexLiquidityEvent>,
+) -> (
+    impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send,
+    Option<&'static str>,
+) {
+    use schema::dex_liquidity_events::dsl::*;
+    (
+        diesel::insert_into(schema::dex_liquidity_events::table)
+            .values(items_to_insert)
+            .on_conflict((transaction_version, event_index))
+            .do_nothing(),
+        None,
+    )
+}
+
+#[async_trait]
+impl ProcessorTrait for DexProcessor {
+    fn name(&self) -> &'static str {
+        ProcessorName::DexProcessor.into()
+    }
+
+    async fn process_transactions(
+        &self,
+        transactions: Vec<Transaction>,
+        start_version: u64,
+        end_version: u64,
+        _: Option<u64>,
+    ) -> anyhow::Result<ProcessingResult> {
+        let processing_start = std::time::Instant::now();
+        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+
+        let (mut swaps, mut liquidity_events) = (vec![], vec![]);
+        for txn in &transactions {
+            let (txn_swaps, txn_liquidity_events) = parse_transaction(txn, &self.config.dexes)?;
+            swaps.extend(txn_swaps);
+            liquidity_events.extend(txn_liquidity_events);
+        }
+
+        let processing_duration_in_secs = processing_start.elapsed().as_secs_f64();
+        let db_insertion_start = std::time::Instant::now();
+
+        let tx_result = insert_to_db(
+            self.get_pool(),
+            self.name(),
+            start_version,
+            end_version,
+            &swaps,
+            &liquidity_events,
+            &self.per_table_chunk_sizes,
+        )
+        .await;
+        let db_insertion_duration_in_secs = db_insertion_start.elapsed().as_secs_f64();
+
+        match tx_result {
+            Ok(_) => Ok(ProcessingResult::DefaultProcessingResult(
+                DefaultProcessingResult {
+                    start_version,
+                    end_version,
+                    processing_duration_in_secs,
+                    db_insertion_duration_in_secs,
+                    last_transaction_timestamp,
+                },
+            )),
+            Err(e) => {
+                error!(
+                    start_version = start_version,
+                    end_version = end_version,
+                    processor_name = self.name(),
+                    error = ?e,
+                    "[Parser] Error inserting transactions to db",
+                );
+                bail!(e)
+            },
+        }
+    }
+
+    fn connection_pool(&self) -> &ArcDbPool {
+        &self.connection_pool
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{DefaultProcessingResult, ProcessorName, ProcessorTrait};
+use crate::{
+    db::common::models::events_models::event_tables::EventTableConfig,
+    gap_detectors::ProcessingResult,
+    utils::{
+        counters::PROCESSOR_UNKNOWN_TYPE_COUNT, database::ArcDbPool, runtime_tables::insert_rows,
+        util::normalize_type_str,
+    },
+};
+use ahash::AHashMap;
+use anyhow::bail;
+use aptos_protos::transaction::v1::{transaction::TxnData, Transaction};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::Debug;
+use tracing::error;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EventTablesProcessorConfig {
+    pub tables: Vec<EventTableConfig>,
+}
+
+/// Writes the events of the configured types to their own tables, with a column per
+/// configured field of the event data.
+pub struct EventTablesProcessor {
+    connection_pool: ArcDbPool,
+    config: EventTablesProcessorConfig,
+    // Event type of each table, normalized once instead of for every event
+    event_types: Vec<String>,
+    per_table_chunk_sizes: AHashMap<String, usize>,
+}
+
+impl EventTablesProcessor {
+    pub fn new(
+        connection_pool: ArcDbPool,
+        config: EventTablesProcessorConfig,
+        per_table_chunk_sizes: AHashMap<String, usize>,
+    ) -> Self {
+        let event_types = config
+            .tables
+            .iter()
+            .map(|table| table.normalized_event_type())
+            .collect();
+        Self {
+            connection_pool,
+            config,
+            event_types,
+            per_table_chunk_sizes,
+        }
+    }
+}
+
+impl Debug for EventTablesProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = &self.connection_pool.state();
+        write!(
+            f,
+            "EventTablesProcessor {{ connections: {:?}  idle_connections: {:?} }}",
+            state.connections, state.idle_connections
+        )
+    }
+}
+
+async fn insert_to_db(
+    conn: ArcDbPool,
+    name: &'static str,
+    start_version: u64,
+    end_version: u64,
+    tables: &[EventTableConfig],
+    rows: Vec<Vec<Value>>,
+    per_table_chunk_sizes: &AHashMap<String, usize>,
+) -> Result<(), diesel::result::Error> {
+    tracing::trace!(
+        name = name,
+        start_version = start_version,
+        end_version = end_version,
+        "Inserting to db",
+    );
+    insert_rows(conn, tables, rows, per_table_chunk_sizes).await
+}
+
+#[async_trait]
+impl ProcessorTrait for EventTablesProcessor {
+    fn name(&self) -> &'static str {
+        ProcessorName::EventTablesProcessor.into()
+    }
+
+    async fn process_transactions(
+        &self,
+        transactions: Vec<Transaction>,
+        start_version: u64,
+        end_version: u64,
+        _: Option<u64>,
+    ) -> anyhow::Result<ProcessingResult> {
+        let processing_start = std::time::Instant::now();
+        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+
+        // Rows of each table, in the order of the tables in the config
+        let mut rows = vec![vec![]; self.config.tables.len()];
+        for txn in &transactions {
+            let txn_data = match txn.txn_data.as_ref() {
+                Some(data) => data,
+                None => {
+                    tracing::warn!(
+                        transaction_version = txn.version,
+                        "Transaction data doesn't exist"
+                    );
+                    PROCESSOR_UNKNOWN_TYPE_COUNT
+                        .with_label_values(&["EventTablesProcessor"])
+                        .inc();
+                    continue;
+                },
+            };
+            let default = vec![];
+            let raw_events = match txn_data {
+                TxnData::BlockMetadata(tx_inner) => &tx_inner.events,
+                TxnData::Genesis(tx_inner) => &tx_inner.events,
+                TxnData::User(tx_inner) => &tx_inner.events,
+                TxnData::Validator(tx_inner) => &tx_inner.events,
+                _ => &default,
+            };
+            for (index, event) in raw_events.iter().enumerate() {
+                let event_type = normalize_type_str(&event.type_str);
+                for ((table, table_event_type), table_rows) in self
+                    .config
+                    .tables
+                    .iter()
+                    .zip(self.event_types.iter())
+                    .zip(rows.iter_mut())
+                {
+                    if *table_event_type == event_type {
+                        table_rows.push(table.to_row(event, txn, index as i64)?);
+                    }
+                }
+            }
+        }
+
+        let processing_duration_in_secs = processing_start.elapsed().as_secs_f64();
+        let db_insertion_start = std::time::Instant::now();
+
+        let tx_result = insert_to_db(
+            self.get_pool(),
+            self.name(),
+            start_version,
+            end_version,
+            &self.config.tables,
+            rows,
+            &self.per_table_chunk_sizes,
+        )
+        .await;
+        let db_insertion_duration_in_secs = db_insertion_start.elapsed().as_secs_f64();
+
+        match tx_result {
+            Ok(_) => Ok(ProcessingResult::DefaultProcessingResult(
+                DefaultProcessingResult {
+                    start_version,
+                    end_version,
+                    processing_duration_in_secs,
+                    db_insertion_duration_in_secs,
+                    last_transaction_timestamp,
+                },
+            )),
+            Err(e) => {
+                error!(
+                    start_version = start_version,
+                    end_version = end_version,
+                    processor_name = self.name(),
+                    error = ?e,
+                    "[Parser] Error inserting transactions to db",
+                );
+                bail!(e)
+            },
+        }
+    }
+
+    fn connection_pool(&self) -> &ArcDbPool {
+        &self.connection_pool
+    }
+}
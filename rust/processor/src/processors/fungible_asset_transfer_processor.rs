@@ -0,0 +1,151 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    fungible_asset_processor::parse_v2_coin, DefaultProcessingResult, ProcessorName, ProcessorTrait,
+};
+use crate::{
+    db::common::models::fungible_asset_models::v2_fungible_asset_transfers::FungibleAssetTransfer,
+    gap_detectors::ProcessingResult,
+    schema,
+    utils::database::{execute_in_chunks, get_config_table_chunk_size, ArcDbPool},
+};
+use ahash::AHashMap;
+use anyhow::bail;
+use aptos_protos::transaction::v1::Transaction;
+use async_trait::async_trait;
+use diesel::{pg::Pg, query_builder::QueryFragment};
+use std::fmt::Debug;
+use tracing::error;
+
+/// Pairs the fungible asset and coin withdrawals and deposits of each transaction into
+/// transfers from one owner to another.
+pub struct FungibleAssetTransferProcessor {
+    connection_pool: ArcDbPool,
+    per_table_chunk_sizes: AHashMap<String, usize>,
+}
+
+impl FungibleAssetTransferProcessor {
+    pub fn new(connection_pool: ArcDbPool, per_table_chunk_sizes: AHashMap<String, usize>) -> Self {
+        Self {
+            connection_pool,
+            per_table_chunk_sizes,
+        }
+    }
+}
+
+impl Debug for FungibleAssetTransferProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = &self.connection_pool.state();
+        write!(
+            f,
+            "FungibleAssetTransferProcessor {{ connections: {:?}  idle_connections: {:?} }}",
+            state.connections, state.idle_connections
+        )
+    }
+}
+
+async fn insert_to_db(
+    conn: ArcDbPool,
+    name: &'static str,
+    start_version: u64,
+    end_version: u64,
+    transfers: &[FungibleAssetTransfer],
+    per_table_chunk_sizes: &AHashMap<String, usize>,
+) -> Result<(), diesel::result::Error> {
+    tracing::trace!(
+        name = name,
+        start_version = start_version,
+        end_version = end_version,
+        "Inserting to db",
+    );
+    execute_in_chunks(
+        conn,
+        insert_fungible_asset_transfers_query,
+        transfers,
+        get_config_table_chunk_size::<FungibleAssetTransfer>(
+            "fungible_asset_transfers",
+            per_table_chunk_sizes,
+        ),
+    )
+    .await?;
+    Ok(())
+}
+
+fn insert_fungible_asset_transfers_query(
+    items_to_insert: Vec<FungibleAssetTransfer>,
+) -> (
+    impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send,
+    Option<&'static str>,
+) {
+    use schema::fungible_asset_transfers::dsl::*;
+    (
+        diesel::insert_into(schema::fungible_asset_transfers::table)
+            .values(items_to_insert)
+            .on_conflict((transaction_version, transfer_index))
+            .do_nothing(),
+        None,
+    )
+}
+
+#[async_trait]
+impl ProcessorTrait for FungibleAssetTransferProcessor {
+    fn name(&self) -> &'static str {
+        ProcessorName::FungibleAssetTransferProcessor.into()
+    }
+
+    async fn process_transactions(
+        &self,
+        transactions: Vec<Transaction>,
+        start_version: u64,
+        end_version: u64,
+        _: Option<u64>,
+    ) -> anyhow::Result<ProcessingResult> {
+        let processing_start = std::time::Instant::now();
+        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+
+        // The activities have the owners and asset types resolved from the write set
+        let (fungible_asset_activities, ..) = parse_v2_coin(&transactions).await;
+        let transfers = FungibleAssetTransfer::from_activities(&fungible_asset_activities);
+
+        let processing_duration_in_secs = processing_start.elapsed().as_secs_f64();
+        let db_insertion_start = std::time::Instant::now();
+
+        let tx_result = insert_to_db(
+            self.get_pool(),
+            self.name(),
+            start_version,
+            end_version,
+            &transfers,
+            &self.per_table_chunk_sizes,
+        )
+        .await;
+        let db_insertion_duration_in_secs = db_insertion_start.elapsed().as_secs_f64();
+
+        match tx_result {
+            Ok(_) => Ok(ProcessingResult::DefaultProcessingResult(
+                DefaultProcessingResult {
+                    start_version,
+                    end_version,
+                    processing_duration_in_secs,
+                    db_insertion_duration_in_secs,
+                    last_transaction_timestamp,
+                },
+            )),
+            Err(e) => {
+                error!(
+                    start_version = start_version,
+                    end_version = end_version,
+                    processor_name = self.name(),
+                    error = ?e,
+                    "[Parser] Error inserting transactions to db",
+                );
+                bail!(e)
+            },
+        }
+    }
+
+    fn connection_pool(&self) -> &ArcDbPool {
+        &self.connection_pool
+    }
+}
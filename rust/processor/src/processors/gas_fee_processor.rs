@@ -0,0 +1,138 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{DefaultProcessingResult, ProcessorName, ProcessorTrait};
+use crate::{
+    db::common::models::gas_fee_models::{
+        gas_fee_breakdowns::GasFeeBreakdown,
+        sender_daily_gas_fees::INSERT_GAS_FEE_BREAKDOWNS_AND_DAILY_TOTALS,
+    },
+    gap_detectors::ProcessingResult,
+    utils::database::{execute_with_better_error, get_config_table_chunk_size, ArcDbPool},
+};
+use ahash::AHashMap;
+use anyhow::bail;
+use aptos_protos::transaction::v1::Transaction;
+use async_trait::async_trait;
+use diesel::sql_types::Jsonb;
+use futures_util::future::try_join_all;
+use std::fmt::Debug;
+use tracing::error;
+
+/// Records the gas charged to each user transaction, with the breakdown of the fee statement, and
+/// the gas spent by each sender per day.
+pub struct GasFeeProcessor {
+    connection_pool: ArcDbPool,
+    per_table_chunk_sizes: AHashMap<String, usize>,
+}
+
+impl GasFeeProcessor {
+    pub fn new(connection_pool: ArcDbPool, per_table_chunk_sizes: AHashMap<String, usize>) -> Self {
+        Self {
+            connection_pool,
+            per_table_chunk_sizes,
+        }
+    }
+}
+
+impl Debug for GasFeeProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = &self.connection_pool.state();
+        write!(
+            f,
+            "GasFeeProcessor {{ connections: {:?}  idle_connections: {:?} }}",
+            state.connections, state.idle_connections
+        )
+    }
+}
+
+async fn insert_to_db(
+    conn: ArcDbPool,
+    name: &'static str,
+    start_version: u64,
+    end_version: u64,
+    gas_fee_breakdowns: &[GasFeeBreakdown],
+    per_table_chunk_sizes: &AHashMap<String, usize>,
+) -> Result<(), diesel::result::Error> {
+    tracing::trace!(
+        name = name,
+        start_version = start_version,
+        end_version = end_version,
+        "Inserting to db",
+    );
+    let chunk_size =
+        get_config_table_chunk_size::<GasFeeBreakdown>("gas_fee_breakdowns", per_table_chunk_sizes);
+    let mut queries = vec![];
+    for chunk in gas_fee_breakdowns.chunks(chunk_size) {
+        let rows = serde_json::to_value(chunk)
+            .map_err(|e| diesel::result::Error::SerializationError(Box::new(e)))?;
+        let query =
+            diesel::sql_query(INSERT_GAS_FEE_BREAKDOWNS_AND_DAILY_TOTALS).bind::<Jsonb, _>(rows);
+        queries.push(execute_with_better_error(conn.clone(), query, None));
+    }
+    try_join_all(queries).await?;
+    Ok(())
+}
+
+#[async_trait]
+impl ProcessorTrait for GasFeeProcessor {
+    fn name(&self) -> &'static str {
+        ProcessorName::GasFeeProcessor.into()
+    }
+
+    async fn process_transactions(
+        &self,
+        transactions: Vec<Transaction>,
+        start_version: u64,
+        end_version: u64,
+        _: Option<u64>,
+    ) -> anyhow::Result<ProcessingResult> {
+        let processing_start = std::time::Instant::now();
+        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+
+        let gas_fee_breakdowns = transactions
+            .iter()
+            .filter_map(GasFeeBreakdown::from_transaction)
+            .collect::<Vec<_>>();
+
+        let processing_duration_in_secs = processing_start.elapsed().as_secs_f64();
+        let db_insertion_start = std::time::Instant::now();
+
+        let tx_result = insert_to_db(
+            self.get_pool(),
+            self.name(),
+            start_version,
+            end_version,
+            &gas_fee_breakdowns,
+            &self.per_table_chunk_sizes,
+        )
+        .await;
+        let db_insertion_duration_in_secs = db_insertion_start.elapsed().as_secs_f64();
+
+        match tx_result {
+            Ok(_) => Ok(ProcessingResult::DefaultProcessingResult(
+                DefaultProcessingResult {
+                    start_version,
+                    end_version,
+                    processing_duration_in_secs,
+                    db_insertion_duration_in_secs,
+                    last_transaction_timestamp,
+                },
+            )),
+            Err(e) => {
+                error!(
+                    start_version = start_version,
+                    end_version = end_version,
+                    processor_name = self.name(),
+                    error = ?e,
+                    "[Parser] Error inserting transactions to db",
+                );
+                bail!(e)
+            },
+        }
+    }
+
+    fn connection_pool(&self) -> &ArcDbPool {
+        &self.connection_pool
+    }
+}
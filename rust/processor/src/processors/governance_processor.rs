@@ -0,0 +1,317 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{DefaultProcessingResult, ProcessorName, ProcessorTrait};
+use crate::{
+    db::common::models::governance_models::{
+        governance_proposals::{
+            CurrentGovernanceProposal, GovernanceProposal, ProposalEarlyResolutions,
+            ProposalStakePools,
+        },
+        governance_utils::GovernanceEvent,
+    },
+    gap_detectors::ProcessingResult,
+    schema,
+    utils::{
+        database::{execute_in_chunks, get_config_table_chunk_size, ArcDbPool},
+        util::{parse_timestamp, standardize_address},
+    },
+};
+use ahash::AHashMap;
+use anyhow::bail;
+use aptos_protos::transaction::v1::{transaction::TxnData, write_set_change::Change, Transaction};
+use async_trait::async_trait;
+use diesel::{
+    dsl::sql,
+    pg::{upsert::excluded, Pg},
+    query_builder::QueryFragment,
+    sql_types::{Nullable, Varchar},
+    ExpressionMethods,
+};
+use std::fmt::Debug;
+use tracing::error;
+
+/// Indexes the proposals of `0x1::aptos_governance`, with their metadata, vote tallies and
+/// resolution. The individual votes are recorded by the stake processor in proposal_votes.
+pub struct GovernanceProcessor {
+    connection_pool: ArcDbPool,
+    per_table_chunk_sizes: AHashMap<String, usize>,
+}
+
+impl GovernanceProcessor {
+    pub fn new(connection_pool: ArcDbPool, per_table_chunk_sizes: AHashMap<String, usize>) -> Self {
+        Self {
+            connection_pool,
+            per_table_chunk_sizes,
+        }
+    }
+}
+
+impl Debug for GovernanceProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = &self.connection_pool.state();
+        write!(
+            f,
+            "GovernanceProcessor {{ connections: {:?}  idle_connections: {:?} }}",
+            state.connections, state.idle_connections
+        )
+    }
+}
+
+async fn insert_to_db(
+    conn: ArcDbPool,
+    name: &'static str,
+    start_version: u64,
+    end_version: u64,
+    governance_proposals: &[GovernanceProposal],
+    current_governance_proposals: &[CurrentGovernanceProposal],
+    per_table_chunk_sizes: &AHashMap<String, usize>,
+) -> Result<(), diesel::result::Error> {
+    tracing::trace!(
+        name = name,
+        start_version = start_version,
+        end_version = end_version,
+        "Inserting to db",
+    );
+    let gp = execute_in_chunks(
+        conn.clone(),
+        insert_governance_proposals_query,
+        governance_proposals,
+        get_config_table_chunk_size::<GovernanceProposal>(
+            "governance_proposals",
+            per_table_chunk_sizes,
+        ),
+    );
+    let cgp_chunk_size = get_config_table_chunk_size::<CurrentGovernanceProposal>(
+        "current_governance_proposals",
+        per_table_chunk_sizes,
+    );
+    let created_proposals = current_governance_proposals
+        .iter()
+        .filter(|proposal| proposal.stake_pool_address.is_some())
+        .cloned()
+        .collect::<Vec<_>>();
+    // The stake pools are set after the upsert so they update the same rows in the same order
+    let cgp = async {
+        execute_in_chunks(
+            conn.clone(),
+            insert_current_governance_proposals_query,
+            current_governance_proposals,
+            cgp_chunk_size,
+        )
+        .await?;
+        execute_in_chunks(
+            conn,
+            insert_current_governance_proposal_stake_pools_query,
+            &created_proposals,
+            cgp_chunk_size,
+        )
+        .await
+    };
+    let (gp_res, cgp_res) = tokio::join!(gp, cgp);
+    for res in [gp_res, cgp_res] {
+        res?;
+    }
+    Ok(())
+}
+
+fn insert_governance_proposals_query(
+    items_to_insert: Vec<GovernanceProposal>,
+) -> (
+    impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send,
+    Option<&'static str>,
+) {
+    use schema::governance_proposals::dsl::*;
+    (
+        diesel::insert_into(schema::governance_proposals::table)
+            .values(items_to_insert)
+            .on_conflict((transaction_version, proposal_id))
+            .do_nothing(),
+        None,
+    )
+}
+
+fn insert_current_governance_proposals_query(
+    items_to_insert: Vec<CurrentGovernanceProposal>,
+) -> (
+    impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send,
+    Option<&'static str>,
+) {
+    use schema::current_governance_proposals::dsl::*;
+    // The stake pool is only known on creation, it's set by
+    // insert_current_governance_proposal_stake_pools_query
+    (
+        diesel::insert_into(schema::current_governance_proposals::table)
+            .values(items_to_insert)
+            .on_conflict(proposal_id)
+            .do_update()
+            .set((
+                proposer_address.eq(excluded(proposer_address)),
+                execution_hash.eq(excluded(execution_hash)),
+                metadata.eq(excluded(metadata)),
+                min_vote_threshold.eq(excluded(min_vote_threshold)),
+                early_resolution_vote_threshold.eq(excluded(early_resolution_vote_threshold)),
+                yes_votes.eq(excluded(yes_votes)),
+                no_votes.eq(excluded(no_votes)),
+                is_resolved.eq(excluded(is_resolved)),
+                resolved_early.eq(excluded(resolved_early)),
+                creation_timestamp.eq(excluded(creation_timestamp)),
+                expiration_timestamp.eq(excluded(expiration_timestamp)),
+                resolution_timestamp.eq(excluded(resolution_timestamp)),
+                last_transaction_version.eq(excluded(last_transaction_version)),
+                last_transaction_timestamp.eq(excluded(last_transaction_timestamp)),
+                inserted_at.eq(excluded(inserted_at)),
+            )),
+        Some(" WHERE current_governance_proposals.last_transaction_version <= excluded.last_transaction_version "),
+    )
+}
+
+/// The creation of a proposal may be written after a batch with its later votes, in which case
+/// the version guard rejects the creation upsert. The stake pool is only known on creation, so
+/// it's set without the guard and never cleared.
+fn insert_current_governance_proposal_stake_pools_query(
+    items_to_insert: Vec<CurrentGovernanceProposal>,
+) -> (
+    impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send,
+    Option<&'static str>,
+) {
+    use schema::current_governance_proposals::dsl::*;
+    (
+        diesel::insert_into(schema::current_governance_proposals::table)
+            .values(items_to_insert)
+            .on_conflict(proposal_id)
+            .do_update()
+            .set(stake_pool_address.eq(sql::<Nullable<Varchar>>(
+                "COALESCE(excluded.stake_pool_address, current_governance_proposals.stake_pool_address)",
+            ))),
+        None,
+    )
+}
+
+#[async_trait]
+impl ProcessorTrait for GovernanceProcessor {
+    fn name(&self) -> &'static str {
+        ProcessorName::GovernanceProcessor.into()
+    }
+
+    async fn process_transactions(
+        &self,
+        transactions: Vec<Transaction>,
+        start_version: u64,
+        end_version: u64,
+        _: Option<u64>,
+    ) -> anyhow::Result<ProcessingResult> {
+        let processing_start = std::time::Instant::now();
+        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+
+        let mut governance_proposals = vec![];
+        let mut current_governance_proposals: AHashMap<i64, CurrentGovernanceProposal> =
+            AHashMap::new();
+        for txn in &transactions {
+            let txn_version = txn.version as i64;
+            let txn_timestamp = parse_timestamp(txn.timestamp.as_ref().unwrap(), txn_version);
+            // Proposals are created, voted on and resolved through user transactions
+            let Some(TxnData::User(user_txn)) = txn.txn_data.as_ref() else {
+                continue;
+            };
+
+            let mut stake_pools: ProposalStakePools = AHashMap::new();
+            let mut early_resolutions: ProposalEarlyResolutions = AHashMap::new();
+            for event in &user_txn.events {
+                match GovernanceEvent::from_event(&event.type_str, &event.data, txn_version)? {
+                    Some(GovernanceEvent::CreateProposalEvent(inner)) => {
+                        stake_pools
+                            .insert(inner.proposal_id, standardize_address(&inner.stake_pool));
+                    },
+                    Some(GovernanceEvent::ResolveProposalEvent(inner)) => {
+                        early_resolutions.insert(inner.proposal_id, inner.resolved_early);
+                    },
+                    None => {},
+                }
+            }
+
+            let changes = &txn
+                .info
+                .as_ref()
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Transaction info doesn't exist! Transaction {}",
+                        txn_version
+                    )
+                })
+                .changes;
+            for wsc in changes {
+                let Some(Change::WriteTableItem(wti)) = wsc.change.as_ref() else {
+                    continue;
+                };
+                let Some((proposal, mut current_proposal)) =
+                    GovernanceProposal::from_write_table_item(
+                        wti,
+                        txn_version,
+                        txn_timestamp,
+                        &stake_pools,
+                        &early_resolutions,
+                    )?
+                else {
+                    continue;
+                };
+                // Keep the stake pool of a proposal created earlier in the batch
+                if let Some(previous) = current_governance_proposals.get(&proposal.proposal_id) {
+                    if current_proposal.stake_pool_address.is_none() {
+                        current_proposal
+                            .stake_pool_address
+                            .clone_from(&previous.stake_pool_address);
+                    }
+                }
+                governance_proposals.push(proposal);
+                current_governance_proposals.insert(current_proposal.proposal_id, current_proposal);
+            }
+        }
+        // Sort by PK to avoid deadlocks between concurrent upserts
+        let mut current_governance_proposals = current_governance_proposals
+            .into_values()
+            .collect::<Vec<_>>();
+        current_governance_proposals.sort_by_key(|proposal| proposal.proposal_id);
+
+        let processing_duration_in_secs = processing_start.elapsed().as_secs_f64();
+        let db_insertion_start = std::time::Instant::now();
+
+        let tx_result = insert_to_db(
+            self.get_pool(),
+            self.name(),
+            start_version,
+            end_version,
+            &governance_proposals,
+            &current_governance_proposals,
+            &self.per_table_chunk_sizes,
+        )
+        .await;
+        let db_insertion_duration_in_secs = db_insertion_start.elapsed().as_secs_f64();
+
+        match tx_result {
+            Ok(_) => Ok(ProcessingResult::DefaultProcessingResult(
+                DefaultProcessingResult {
+                    start_version,
+                    end_version,
+                    processing_duration_in_secs,
+                    db_insertion_duration_in_secs,
+                    last_transaction_timestamp,
+                },
+            )),
+            Err(e) => {
+                error!(
+                    start_version = start_version,
+                    end_version = end_version,
+                    processor_name = self.name(),
+                    error = ?e,
+                    "[Parser] Error inserting transactions to db",
+                );
+                bail!(e)
+            },
+        }
+    }
+
+    fn connection_pool(&self) -> &ArcDbPool {
+        &self.connection_pool
+    }
+}
@@ -4,35 +4,58 @@
 // Note: For enum_dispatch to work nicely, it is easiest to have the trait and the enum
 // in the same file (ProcessorTrait and Processor).
 
+pub mod account_keys_processor;
 pub mod account_transactions_processor;
 pub mod ans_processor;
+pub mod audit_processor;
 pub mod default_processor;
+pub mod dex_processor;
+pub mod event_tables_processor;
 pub mod events_processor;
 pub mod fungible_asset_processor;
+pub mod fungible_asset_transfer_processor;
+pub mod gas_fee_processor;
+pub mod governance_processor;
 pub mod monitoring_processor;
+pub mod multisig_processor;
+pub mod nft_marketplace_processor;
 pub mod nft_metadata_processor;
 pub mod objects_processor;
 pub mod parquet_processors;
+pub mod resource_snapshot_processor;
 pub mod stake_processor;
 pub mod token_v2_processor;
 pub mod transaction_metadata_processor;
 pub mod user_transaction_processor;
+pub mod validator_set_processor;
 
 use self::{
+    account_keys_processor::AccountKeysProcessor,
     account_transactions_processor::AccountTransactionsProcessor,
     ans_processor::{AnsProcessor, AnsProcessorConfig},
+    audit_processor::{AuditProcessor, AuditProcessorConfig},
     default_processor::DefaultProcessor,
+    dex_processor::{DexProcessor, DexProcessorConfig},
+    event_tables_processor::{EventTablesProcessor, EventTablesProcessorConfig},
     events_processor::EventsProcessor,
     fungible_asset_processor::FungibleAssetProcessor,
+    fungible_asset_transfer_processor::FungibleAssetTransferProcessor,
+    gas_fee_processor::GasFeeProcessor,
+    governance_processor::GovernanceProcessor,
     monitoring_processor::MonitoringProcessor,
+    multisig_processor::MultisigProcessor,
+    nft_marketplace_processor::{NftMarketplaceProcessor, NftMarketplaceProcessorConfig},
     nft_metadata_processor::{NftMetadataProcessor, NftMetadataProcessorConfig},
     objects_processor::{ObjectsProcessor, ObjectsProcessorConfig},
+    resource_snapshot_processor::{ResourceSnapshotProcessor, ResourceSnapshotProcessorConfig},
     stake_processor::{StakeProcessor, StakeProcessorConfig},
     token_v2_processor::{TokenV2Processor, TokenV2ProcessorConfig},
     transaction_metadata_processor::TransactionMetadataProcessor,
     user_transaction_processor::UserTransactionProcessor,
+    validator_set_processor::ValidatorSetProcessor,
 };
 use crate::{
+    bq_analytics::parquet_writer_config::validate_writer_configs,
     db::common::models::processor_status::ProcessorStatus,
     gap_detectors::ProcessingResult,
     processors::parquet_processors::{
@@ -48,6 +71,7 @@ use crate::{
         util::parse_timestamp,
     },
 };
+use ahash::AHashMap;
 use aptos_protos::transaction::v1::Transaction as ProtoTransaction;
 use async_trait::async_trait;
 use diesel::{pg::upsert::excluded, ExpressionMethods};
@@ -180,18 +204,29 @@ pub trait ProcessorTrait: Send + Sync + Debug {
     strum(serialize_all = "snake_case")
 )]
 pub enum ProcessorConfig {
+    AccountKeysProcessor,
     AccountTransactionsProcessor,
     AnsProcessor(AnsProcessorConfig),
+    AuditProcessor(AuditProcessorConfig),
     DefaultProcessor,
+    DexProcessor(DexProcessorConfig),
+    EventTablesProcessor(EventTablesProcessorConfig),
     EventsProcessor,
     FungibleAssetProcessor,
+    FungibleAssetTransferProcessor,
+    GasFeeProcessor,
+    GovernanceProcessor,
     MonitoringProcessor,
+    MultisigProcessor,
+    NftMarketplaceProcessor(NftMarketplaceProcessorConfig),
     NftMetadataProcessor(NftMetadataProcessorConfig),
     ObjectsProcessor(ObjectsProcessorConfig),
+    ResourceSnapshotProcessor(ResourceSnapshotProcessorConfig),
     StakeProcessor(StakeProcessorConfig),
     TokenV2Processor(TokenV2ProcessorConfig),
     TransactionMetadataProcessor,
     UserTransactionProcessor,
+    ValidatorSetProcessor,
     ParquetDefaultProcessor(ParquetDefaultProcessorConfig),
     ParquetFungibleAssetProcessor(ParquetFungibleAssetProcessorConfig),
 }
@@ -210,6 +245,45 @@ impl ProcessorConfig {
                 | ProcessorConfig::ParquetFungibleAssetProcessor(_)
         )
     }
+
+    /// Tables written by a parquet processor, empty for all other processors
+    pub fn parquet_table_names(&self) -> &'static [&'static str] {
+        match self {
+            ProcessorConfig::ParquetDefaultProcessor(_) => ParquetDefaultProcessor::TABLE_NAMES,
+            ProcessorConfig::ParquetFungibleAssetProcessor(_) => {
+                ParquetFungibleAssetProcessor::TABLE_NAMES
+            },
+            _ => &[],
+        }
+    }
+
+    /// Rejects invalid processor configs on startup, before any processor is built
+    pub fn validate(&self) -> anyhow::Result<()> {
+        match self {
+            ProcessorConfig::ParquetDefaultProcessor(config) => validate_writer_configs(
+                &config.per_table_writer_configs,
+                self.parquet_table_names(),
+            ),
+            ProcessorConfig::ParquetFungibleAssetProcessor(config) => validate_writer_configs(
+                &config.per_table_writer_configs,
+                self.parquet_table_names(),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Per table starting versions of a parquet processor, from the processor config
+    pub fn parquet_per_table_starting_versions(&self) -> Option<&AHashMap<String, u64>> {
+        match self {
+            ProcessorConfig::ParquetDefaultProcessor(config) => {
+                Some(&config.per_table_starting_versions)
+            },
+            ProcessorConfig::ParquetFungibleAssetProcessor(config) => {
+                Some(&config.per_table_starting_versions)
+            },
+            _ => None,
+        }
+    }
 }
 
 /// This enum contains all the processors defined in this crate. We use enum_dispatch
@@ -230,18 +304,29 @@ impl ProcessorConfig {
     )
 )]
 pub enum Processor {
+    AccountKeysProcessor,
     AccountTransactionsProcessor,
     AnsProcessor,
+    AuditProcessor,
     DefaultProcessor,
+    DexProcessor,
+    EventTablesProcessor,
     EventsProcessor,
     FungibleAssetProcessor,
+    FungibleAssetTransferProcessor,
+    GasFeeProcessor,
+    GovernanceProcessor,
     MonitoringProcessor,
+    MultisigProcessor,
+    NftMarketplaceProcessor,
     NftMetadataProcessor,
     ObjectsProcessor,
+    ResourceSnapshotProcessor,
     StakeProcessor,
     TokenV2Processor,
     TransactionMetadataProcessor,
     UserTransactionProcessor,
+    ValidatorSetProcessor,
     ParquetDefaultProcessor,
     ParquetFungibleAssetProcessor,
 }
@@ -0,0 +1,295 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{DefaultProcessingResult, ProcessorName, ProcessorTrait};
+use crate::{
+    db::common::models::multisig_models::{
+        current_multisig_accounts::CurrentMultisigAccount,
+        multisig_activities::MultisigActivity,
+        multisig_transactions::MultisigTransaction,
+        multisig_utils::{is_multisig_account_creation, MultisigEvent},
+    },
+    gap_detectors::ProcessingResult,
+    schema,
+    utils::{
+        database::{execute_in_chunks, get_config_table_chunk_size, ArcDbPool},
+        util::{get_entry_function_from_user_request, parse_timestamp},
+    },
+};
+use ahash::AHashMap;
+use anyhow::bail;
+use aptos_protos::transaction::v1::{transaction::TxnData, write_set_change::Change, Transaction};
+use async_trait::async_trait;
+use diesel::{
+    pg::{upsert::excluded, Pg},
+    query_builder::QueryFragment,
+    ExpressionMethods,
+};
+use std::fmt::Debug;
+use tracing::error;
+
+/// Indexes the multisig accounts of `0x1::multisig_account`, their owners and threshold, and the
+/// transactions proposed to them with their votes and execution.
+pub struct MultisigProcessor {
+    connection_pool: ArcDbPool,
+    per_table_chunk_sizes: AHashMap<String, usize>,
+}
+
+impl MultisigProcessor {
+    pub fn new(connection_pool: ArcDbPool, per_table_chunk_sizes: AHashMap<String, usize>) -> Self {
+        Self {
+            connection_pool,
+            per_table_chunk_sizes,
+        }
+    }
+}
+
+impl Debug for MultisigProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = &self.connection_pool.state();
+        write!(
+            f,
+            "MultisigProcessor {{ connections: {:?}  idle_connections: {:?} }}",
+            state.connections, state.idle_connections
+        )
+    }
+}
+
+async fn insert_to_db(
+    conn: ArcDbPool,
+    name: &'static str,
+    start_version: u64,
+    end_version: u64,
+    current_multisig_accounts: &[CurrentMultisigAccount],
+    multisig_transactions: &[MultisigTransaction],
+    multisig_activities: &[MultisigActivity],
+    per_table_chunk_sizes: &AHashMap<String, usize>,
+) -> Result<(), diesel::result::Error> {
+    tracing::trace!(
+        name = name,
+        start_version = start_version,
+        end_version = end_version,
+        "Inserting to db",
+    );
+    let cma = execute_in_chunks(
+        conn.clone(),
+        insert_current_multisig_accounts_query,
+        current_multisig_accounts,
+        get_config_table_chunk_size::<CurrentMultisigAccount>(
+            "current_multisig_accounts",
+            per_table_chunk_sizes,
+        ),
+    );
+    let mt = execute_in_chunks(
+        conn.clone(),
+        insert_multisig_transactions_query,
+        multisig_transactions,
+        get_config_table_chunk_size::<MultisigTransaction>(
+            "multisig_transactions",
+            per_table_chunk_sizes,
+        ),
+    );
+    let ma = execute_in_chunks(
+        conn,
+        insert_multisig_activities_query,
+        multisig_activities,
+        get_config_table_chunk_size::<MultisigActivity>(
+            "multisig_activities",
+            per_table_chunk_sizes,
+        ),
+    );
+    let (cma_res, mt_res, ma_res) = tokio::join!(cma, mt, ma);
+    for res in [cma_res, mt_res, ma_res] {
+        res?;
+    }
+    Ok(())
+}
+
+fn insert_current_multisig_accounts_query(
+    items_to_insert: Vec<CurrentMultisigAccount>,
+) -> (
+    impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send,
+    Option<&'static str>,
+) {
+    use schema::current_multisig_accounts::dsl::*;
+    (
+        diesel::insert_into(schema::current_multisig_accounts::table)
+            .values(items_to_insert)
+            .on_conflict(multisig_address)
+            .do_update()
+            .set((
+                owners.eq(excluded(owners)),
+                num_signatures_required.eq(excluded(num_signatures_required)),
+                last_executed_sequence_number.eq(excluded(last_executed_sequence_number)),
+                next_sequence_number.eq(excluded(next_sequence_number)),
+                last_transaction_version.eq(excluded(last_transaction_version)),
+                last_transaction_timestamp.eq(excluded(last_transaction_timestamp)),
+                inserted_at.eq(excluded(inserted_at)),
+            )),
+        Some(" WHERE current_multisig_accounts.last_transaction_version <= excluded.last_transaction_version "),
+    )
+}
+
+fn insert_multisig_transactions_query(
+    items_to_insert: Vec<MultisigTransaction>,
+) -> (
+    impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send,
+    Option<&'static str>,
+) {
+    use schema::multisig_transactions::dsl::*;
+    (
+        diesel::insert_into(schema::multisig_transactions::table)
+            .values(items_to_insert)
+            .on_conflict((multisig_address, sequence_number))
+            .do_nothing(),
+        None,
+    )
+}
+
+fn insert_multisig_activities_query(
+    items_to_insert: Vec<MultisigActivity>,
+) -> (
+    impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send,
+    Option<&'static str>,
+) {
+    use schema::multisig_activities::dsl::*;
+    (
+        diesel::insert_into(schema::multisig_activities::table)
+            .values(items_to_insert)
+            .on_conflict((transaction_version, event_index))
+            .do_nothing(),
+        None,
+    )
+}
+
+#[async_trait]
+impl ProcessorTrait for MultisigProcessor {
+    fn name(&self) -> &'static str {
+        ProcessorName::MultisigProcessor.into()
+    }
+
+    async fn process_transactions(
+        &self,
+        transactions: Vec<Transaction>,
+        start_version: u64,
+        end_version: u64,
+        _: Option<u64>,
+    ) -> anyhow::Result<ProcessingResult> {
+        let processing_start = std::time::Instant::now();
+        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+
+        let mut current_multisig_accounts: AHashMap<String, CurrentMultisigAccount> =
+            AHashMap::new();
+        let mut multisig_transactions = vec![];
+        let mut multisig_activities = vec![];
+        for txn in &transactions {
+            let txn_version = txn.version as i64;
+            let txn_timestamp = parse_timestamp(txn.timestamp.as_ref().unwrap(), txn_version);
+            // Multisig accounts are created and operated through user transactions only
+            let Some(TxnData::User(user_txn)) = txn.txn_data.as_ref() else {
+                continue;
+            };
+
+            for (index, event) in user_txn.events.iter().enumerate() {
+                let Some(multisig_event) = MultisigEvent::from_event(event, txn_version)? else {
+                    continue;
+                };
+                if let Some(multisig_transaction) = MultisigTransaction::from_multisig_event(
+                    &multisig_event,
+                    event,
+                    txn_version,
+                    txn_timestamp,
+                )? {
+                    multisig_transactions.push(multisig_transaction);
+                }
+                multisig_activities.push(MultisigActivity::from_multisig_event(
+                    &multisig_event,
+                    event,
+                    txn_version,
+                    index as i64,
+                    txn_timestamp,
+                )?);
+            }
+
+            let changes = &txn
+                .info
+                .as_ref()
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Transaction info doesn't exist! Transaction {}",
+                        txn_version
+                    )
+                })
+                .changes;
+            let user_request = user_txn.request.as_ref();
+            let is_creation = user_request
+                .and_then(get_entry_function_from_user_request)
+                .is_some_and(|entry_function| is_multisig_account_creation(&entry_function));
+            for wsc in changes {
+                let Some(Change::WriteResource(wr)) = wsc.change.as_ref() else {
+                    continue;
+                };
+                let Some(account) =
+                    CurrentMultisigAccount::from_write_resource(wr, txn_version, txn_timestamp)?
+                else {
+                    continue;
+                };
+                // A multisig account executing a proposal to create another one is written as
+                // well, but it has had proposals already
+                if is_creation && account.next_sequence_number == 1 {
+                    multisig_activities.push(MultisigActivity::from_created_account(
+                        &account,
+                        &user_request.unwrap().sender,
+                    ));
+                }
+                current_multisig_accounts.insert(account.multisig_address.clone(), account);
+            }
+        }
+        // Sort by PK to avoid deadlocks between concurrent upserts
+        let mut current_multisig_accounts =
+            current_multisig_accounts.into_values().collect::<Vec<_>>();
+        current_multisig_accounts.sort_by(|a, b| a.multisig_address.cmp(&b.multisig_address));
+
+        let processing_duration_in_secs = processing_start.elapsed().as_secs_f64();
+        let db_insertion_start = std::time::Instant::now();
+
+        let tx_result = insert_to_db(
+            self.get_pool(),
+            self.name(),
+            start_version,
+            end_version,
+            &current_multisig_accounts,
+            &multisig_transactions,
+            &multisig_activities,
+            &self.per_table_chunk_sizes,
+        )
+        .await;
+        let db_insertion_duration_in_secs = db_insertion_start.elapsed().as_secs_f64();
+
+        match tx_result {
+            Ok(_) => Ok(ProcessingResult::DefaultProcessingResult(
+                DefaultProcessingResult {
+                    start_version,
+                    end_version,
+                    processing_duration_in_secs,
+                    db_insertion_duration_in_secs,
+                    last_transaction_timestamp,
+                },
+            )),
+            Err(e) => {
+                error!(
+                    start_version = start_version,
+                    end_version = end_version,
+                    processor_name = self.name(),
+                    error = ?e,
+                    "[Parser] Error inserting transactions to db",
+                );
+                bail!(e)
+            },
+        }
+    }
+
+    fn connection_pool(&self) -> &ArcDbPool {
+        &self.connection_pool
+    }
+}
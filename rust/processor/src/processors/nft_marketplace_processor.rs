@@ -0,0 +1,305 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{DefaultProcessingResult, ProcessorName, ProcessorTrait};
+use crate::{
+    db::common::models::nft_marketplace_models::{
+        current_nft_marketplace_listings::CurrentNftMarketplaceListing,
+        current_nft_marketplace_offers::{
+            CurrentNftMarketplaceCollectionOffer, CurrentNftMarketplaceTokenOffer,
+        },
+        nft_marketplace_activities::{NftMarketplaceActivity, NftMarketplaceChanges},
+        nft_marketplace_utils::MarketplaceConfig,
+    },
+    gap_detectors::ProcessingResult,
+    schema,
+    utils::database::{execute_in_chunks, get_config_table_chunk_size, ArcDbPool},
+};
+use ahash::AHashMap;
+use anyhow::bail;
+use aptos_protos::transaction::v1::Transaction;
+use async_trait::async_trait;
+use diesel::{
+    pg::{upsert::excluded, Pg},
+    query_builder::QueryFragment,
+    ExpressionMethods,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use tracing::error;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct NftMarketplaceProcessorConfig {
+    pub marketplaces: Vec<MarketplaceConfig>,
+}
+
+/// Indexes listings, token offers and collection offers of marketplaces deployed from the aptos
+/// marketplace example contract.
+pub struct NftMarketplaceProcessor {
+    connection_pool: ArcDbPool,
+    config: NftMarketplaceProcessorConfig,
+    per_table_chunk_sizes: AHashMap<String, usize>,
+}
+
+impl NftMarketplaceProcessor {
+    pub fn new(
+        connection_pool: ArcDbPool,
+        config: NftMarketplaceProcessorConfig,
+        per_table_chunk_sizes: AHashMap<String, usize>,
+    ) -> Self {
+        Self {
+            connection_pool,
+            config,
+            per_table_chunk_sizes,
+        }
+    }
+}
+
+impl Debug for NftMarketplaceProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = &self.connection_pool.state();
+        write!(
+            f,
+            "NftMarketplaceProcessor {{ connections: {:?}  idle_connections: {:?} }}",
+            state.connections, state.idle_connections
+        )
+    }
+}
+
+async fn insert_to_db(
+    conn: ArcDbPool,
+    name: &'static str,
+    start_version: u64,
+    end_version: u64,
+    (activities, current_listings, current_token_offers, current_collection_offers): (
+        &[NftMarketplaceActivity],
+        &[CurrentNftMarketplaceListing],
+        &[CurrentNftMarketplaceTokenOffer],
+        &[CurrentNftMarketplaceCollectionOffer],
+    ),
+    per_table_chunk_sizes: &AHashMap<String, usize>,
+) -> Result<(), diesel::result::Error> {
+    tracing::trace!(
+        name = name,
+        start_version = start_version,
+        end_version = end_version,
+        "Inserting to db",
+    );
+
+    let a = execute_in_chunks(
+        conn.clone(),
+        insert_nft_marketplace_activities_query,
+        activities,
+        get_config_table_chunk_size::<NftMarketplaceActivity>(
+            "nft_marketplace_activities",
+            per_table_chunk_sizes,
+        ),
+    );
+    let cl = execute_in_chunks(
+        conn.clone(),
+        insert_current_nft_marketplace_listings_query,
+        current_listings,
+        get_config_table_chunk_size::<CurrentNftMarketplaceListing>(
+            "current_nft_marketplace_listings",
+            per_table_chunk_sizes,
+        ),
+    );
+    let cto = execute_in_chunks(
+        conn.clone(),
+        insert_current_nft_marketplace_token_offers_query,
+        current_token_offers,
+        get_config_table_chunk_size::<CurrentNftMarketplaceTokenOffer>(
+            "current_nft_marketplace_token_offers",
+            per_table_chunk_sizes,
+        ),
+    );
+    let cco = execute_in_chunks(
+        conn,
+        insert_current_nft_marketplace_collection_offers_query,
+        current_collection_offers,
+        get_config_table_chunk_size::<CurrentNftMarketplaceCollectionOffer>(
+            "current_nft_marketplace_collection_offers",
+            per_table_chunk_sizes,
+        ),
+    );
+    let (a_res, cl_res, cto_res, cco_res) = tokio::join!(a, cl, cto, cco);
+    for res in [a_res, cl_res, cto_res, cco_res] {
+        res?;
+    }
+
+    Ok(())
+}
+
+fn insert_nft_marketplace_activities_query(
+    items_to_insert: Vec<NftMarketplaceActivity>,
+) -> (
+    impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send,
+    Option<&'static str>,
+) {
+    use schema::nft_marketplace_activities::dsl::*;
+    (
+        diesel::insert_into(schema::nft_marketplace_activities::table)
+            .values(items_to_insert)
+            .on_conflict((transaction_version, event_index))
+            .do_nothing(),
+        None,
+    )
+}
+
+fn insert_current_nft_marketplace_listings_query(
+    items_to_insert: Vec<CurrentNftMarketplaceListing>,
+) -> (
+    impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send,
+    Option<&'static str>,
+) {
+    use schema::current_nft_marketplace_listings::dsl::*;
+    (
+        diesel::insert_into(schema::current_nft_marketplace_listings::table)
+            .values(items_to_insert)
+            .on_conflict(listing_id)
+            .do_update()
+            .set((
+                price.eq(excluded(price)),
+                token_amount.eq(excluded(token_amount)),
+                is_deleted.eq(excluded(is_deleted)),
+                last_transaction_version.eq(excluded(last_transaction_version)),
+                last_transaction_timestamp.eq(excluded(last_transaction_timestamp)),
+                inserted_at.eq(excluded(inserted_at)),
+            )),
+        Some(" WHERE current_nft_marketplace_listings.last_transaction_version <= excluded.last_transaction_version "),
+    )
+}
+
+fn insert_current_nft_marketplace_token_offers_query(
+    items_to_insert: Vec<CurrentNftMarketplaceTokenOffer>,
+) -> (
+    impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send,
+    Option<&'static str>,
+) {
+    use schema::current_nft_marketplace_token_offers::dsl::*;
+    // The expiration time is only known on placement, so it's not overwritten
+    (
+        diesel::insert_into(schema::current_nft_marketplace_token_offers::table)
+            .values(items_to_insert)
+            .on_conflict(offer_id)
+            .do_update()
+            .set((
+                price.eq(excluded(price)),
+                is_deleted.eq(excluded(is_deleted)),
+                last_transaction_version.eq(excluded(last_transaction_version)),
+                last_transaction_timestamp.eq(excluded(last_transaction_timestamp)),
+                inserted_at.eq(excluded(inserted_at)),
+            )),
+        Some(" WHERE current_nft_marketplace_token_offers.last_transaction_version <= excluded.last_transaction_version "),
+    )
+}
+
+fn insert_current_nft_marketplace_collection_offers_query(
+    items_to_insert: Vec<CurrentNftMarketplaceCollectionOffer>,
+) -> (
+    impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send,
+    Option<&'static str>,
+) {
+    use schema::current_nft_marketplace_collection_offers::dsl::*;
+    (
+        diesel::insert_into(schema::current_nft_marketplace_collection_offers::table)
+            .values(items_to_insert)
+            .on_conflict(collection_offer_id)
+            .do_update()
+            .set((
+                item_price.eq(excluded(item_price)),
+                remaining_token_amount.eq(excluded(remaining_token_amount)),
+                is_deleted.eq(excluded(is_deleted)),
+                last_transaction_version.eq(excluded(last_transaction_version)),
+                last_transaction_timestamp.eq(excluded(last_transaction_timestamp)),
+                inserted_at.eq(excluded(inserted_at)),
+            )),
+        Some(" WHERE current_nft_marketplace_collection_offers.last_transaction_version <= excluded.last_transaction_version "),
+    )
+}
+
+#[async_trait]
+impl ProcessorTrait for NftMarketplaceProcessor {
+    fn name(&self) -> &'static str {
+        ProcessorName::NftMarketplaceProcessor.into()
+    }
+
+    async fn process_transactions(
+        &self,
+        transactions: Vec<Transaction>,
+        start_version: u64,
+        end_version: u64,
+        _: Option<u64>,
+    ) -> anyhow::Result<ProcessingResult> {
+        let processing_start = std::time::Instant::now();
+        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+
+        let mut changes = NftMarketplaceChanges::default();
+        for txn in &transactions {
+            NftMarketplaceActivity::from_transaction(txn, &self.config.marketplaces, &mut changes)?;
+        }
+
+        // Sort by PK
+        let mut current_listings = changes
+            .current_listings
+            .into_values()
+            .collect::<Vec<CurrentNftMarketplaceListing>>();
+        current_listings.sort_by(|a, b| a.listing_id.cmp(&b.listing_id));
+        let mut current_token_offers = changes
+            .current_token_offers
+            .into_values()
+            .collect::<Vec<CurrentNftMarketplaceTokenOffer>>();
+        current_token_offers.sort_by(|a, b| a.offer_id.cmp(&b.offer_id));
+        let mut current_collection_offers = changes
+            .current_collection_offers
+            .into_values()
+            .collect::<Vec<CurrentNftMarketplaceCollectionOffer>>();
+        current_collection_offers.sort_by(|a, b| a.collection_offer_id.cmp(&b.collection_offer_id));
+
+        let processing_duration_in_secs = processing_start.elapsed().as_secs_f64();
+        let db_insertion_start = std::time::Instant::now();
+
+        let tx_result = insert_to_db(
+            self.get_pool(),
+            self.name(),
+            start_version,
+            end_version,
+            (
+                &changes.activities,
+                &current_listings,
+                &current_token_offers,
+                &current_collection_offers,
+            ),
+            &self.per_table_chunk_sizes,
+        )
+        .await;
+        let db_insertion_duration_in_secs = db_insertion_start.elapsed().as_secs_f64();
+
+        match tx_result {
+            Ok(_) => Ok(ProcessingResult::DefaultProcessingResult(
+                DefaultProcessingResult {
+                    start_version,
+                    end_version,
+                    processing_duration_in_secs,
+                    db_insertion_duration_in_secs,
+                    last_transaction_timestamp,
+                },
+            )),
+            Err(e) => {
+                error!(
+                    start_version = start_version,
+                    end_version = end_version,
+                    processor_name = self.name(),
+                    error = ?e,
+                    "[Parser] Error inserting transactions to db",
+                );
+                bail!(e)
+            },
+        }
+    }
+
+    fn connection_pool(&self) -> &ArcDbPool {
+        &self.connection_pool
+    }
+}
@@ -3,7 +3,10 @@
 
 use crate::{
     bq_analytics::{
-        create_parquet_handler_loop, generic_parquet_processor::ParquetDataGeneric,
+        create_parquet_handler_loop,
+        delta_log::ParquetTableFormat,
+        generic_parquet_processor::{NamedTable, ParquetDataGeneric},
+        parquet_writer_config::ParquetWriterConfig,
         ParquetProcessingResult,
     },
     db::common::models::default_models::{
@@ -40,6 +43,16 @@ pub struct ParquetDefaultProcessorConfig {
     pub parquet_handler_response_channel_size: usize,
     pub max_buffer_size: usize,
     pub parquet_upload_interval: u64,
+    // Parquet writer properties keyed by table name, tables not listed use the defaults
+    #[serde(default = "AHashMap::new")]
+    pub per_table_writer_configs: AHashMap<String, ParquetWriterConfig>,
+    // Whether to commit the uploaded files to a table log (e.g. Delta Lake)
+    #[serde(default)]
+    pub table_format: ParquetTableFormat,
+    // Versions to start each table from, overriding the table checkpoints. Tables not listed
+    // resume from their own checkpoint, so a lagging table can be backfilled on its own.
+    #[serde(default = "AHashMap::new")]
+    pub per_table_starting_versions: AHashMap<String, u64>,
 }
 impl UploadIntervalConfig for ParquetDefaultProcessorConfig {
     fn parquet_upload_interval_in_secs(&self) -> Duration {
@@ -57,19 +70,31 @@ pub struct ParquetDefaultProcessor {
     table_metadata_sender: AsyncSender<ParquetDataGeneric<TableMetadata>>,
 }
 
-// TODO: Since each table item has different size allocated, the pace of being backfilled to PQ varies a lot.
-// Maybe we can have also have a way to configure different starting version for each table later.
+// Since each table item has different size allocated, the pace of being backfilled to PQ varies a lot.
+// Each table therefore tracks its own checkpoint and can be started from a different version.
 impl ParquetDefaultProcessor {
+    pub const TABLE_NAMES: &'static [&'static str] = &[
+        ParquetTransaction::TABLE_NAME,
+        MoveResource::TABLE_NAME,
+        WriteSetChangeModel::TABLE_NAME,
+        TableItem::TABLE_NAME,
+        MoveModule::TABLE_NAME,
+        TableMetadata::TABLE_NAME,
+    ];
+
     pub fn new(
         connection_pool: ArcDbPool,
         config: ParquetDefaultProcessorConfig,
         new_gap_detector_sender: AsyncSender<ProcessingResult>,
+        parquet_table_start_versions: AHashMap<String, u64>,
     ) -> Self {
         if let Some(credentials) = config.google_application_credentials.clone() {
             std::env::set_var(GOOGLE_APPLICATION_CREDENTIALS, credentials);
         }
 
         let transaction_sender = create_parquet_handler_loop::<ParquetTransaction>(
+            connection_pool.clone(),
+            &parquet_table_start_versions,
             new_gap_detector_sender.clone(),
             ProcessorName::ParquetDefaultProcessor.into(),
             config.bucket_name.clone(),
@@ -77,9 +102,13 @@ impl ParquetDefaultProcessor {
             config.parquet_handler_response_channel_size,
             config.max_buffer_size,
             config.parquet_upload_interval_in_secs(),
+            &config.per_table_writer_configs,
+            config.table_format,
         );
 
         let move_resource_sender = create_parquet_handler_loop::<MoveResource>(
+            connection_pool.clone(),
+            &parquet_table_start_versions,
             new_gap_detector_sender.clone(),
             ProcessorName::ParquetDefaultProcessor.into(),
             config.bucket_name.clone(),
@@ -87,9 +116,13 @@ impl ParquetDefaultProcessor {
             config.parquet_handler_response_channel_size,
             config.max_buffer_size,
             config.parquet_upload_interval_in_secs(),
+            &config.per_table_writer_configs,
+            config.table_format,
         );
 
         let wsc_sender = create_parquet_handler_loop::<WriteSetChangeModel>(
+            connection_pool.clone(),
+            &parquet_table_start_versions,
             new_gap_detector_sender.clone(),
             ProcessorName::ParquetDefaultProcessor.into(),
             config.bucket_name.clone(),
@@ -97,9 +130,13 @@ impl ParquetDefaultProcessor {
             config.parquet_handler_response_channel_size,
             config.max_buffer_size,
             config.parquet_upload_interval_in_secs(),
+            &config.per_table_writer_configs,
+            config.table_format,
         );
 
         let table_item_sender = create_parquet_handler_loop::<TableItem>(
+            connection_pool.clone(),
+            &parquet_table_start_versions,
             new_gap_detector_sender.clone(),
             ProcessorName::ParquetDefaultProcessor.into(),
             config.bucket_name.clone(),
@@ -107,8 +144,12 @@ impl ParquetDefaultProcessor {
             config.parquet_handler_response_channel_size,
             config.max_buffer_size,
             config.parquet_upload_interval_in_secs(),
+            &config.per_table_writer_configs,
+            config.table_format,
         );
         let move_module_sender = create_parquet_handler_loop::<MoveModule>(
+            connection_pool.clone(),
+            &parquet_table_start_versions,
             new_gap_detector_sender.clone(),
             ProcessorName::ParquetDefaultProcessor.into(),
             config.bucket_name.clone(),
@@ -116,9 +157,13 @@ impl ParquetDefaultProcessor {
             config.parquet_handler_response_channel_size,
             config.max_buffer_size,
             config.parquet_upload_interval_in_secs(),
+            &config.per_table_writer_configs,
+            config.table_format,
         );
 
         let table_metadata_sender = create_parquet_handler_loop::<TableMetadata>(
+            connection_pool.clone(),
+            &parquet_table_start_versions,
             new_gap_detector_sender.clone(),
             ProcessorName::ParquetDefaultProcessor.into(),
             config.bucket_name.clone(),
@@ -126,6 +171,8 @@ impl ParquetDefaultProcessor {
             config.parquet_handler_response_channel_size,
             config.max_buffer_size,
             config.parquet_upload_interval_in_secs(),
+            &config.per_table_writer_configs,
+            config.table_format,
         );
 
         Self {
@@ -187,6 +234,8 @@ impl ProcessorTrait for ParquetDefaultProcessor {
         let mr_parquet_data = ParquetDataGeneric {
             data: move_resources,
             transaction_version_to_struct_count: transaction_version_to_struct_count.clone(),
+            start_version: start_version as i64,
+            end_version: end_version as i64,
         };
 
         self.move_resource_sender
@@ -197,6 +246,8 @@ impl ProcessorTrait for ParquetDefaultProcessor {
         let wsc_parquet_data = ParquetDataGeneric {
             data: write_set_changes,
             transaction_version_to_struct_count: transaction_version_to_struct_count.clone(),
+            start_version: start_version as i64,
+            end_version: end_version as i64,
         };
         self.wsc_sender
             .send(wsc_parquet_data)
@@ -206,6 +257,8 @@ impl ProcessorTrait for ParquetDefaultProcessor {
         let t_parquet_data = ParquetDataGeneric {
             data: transactions,
             transaction_version_to_struct_count: transaction_version_to_struct_count.clone(),
+            start_version: start_version as i64,
+            end_version: end_version as i64,
         };
         self.transaction_sender
             .send(t_parquet_data)
@@ -215,6 +268,8 @@ impl ProcessorTrait for ParquetDefaultProcessor {
         let ti_parquet_data = ParquetDataGeneric {
             data: table_items,
             transaction_version_to_struct_count: transaction_version_to_struct_count.clone(),
+            start_version: start_version as i64,
+            end_version: end_version as i64,
         };
 
         self.table_item_sender
@@ -225,6 +280,8 @@ impl ProcessorTrait for ParquetDefaultProcessor {
         let mm_parquet_data = ParquetDataGeneric {
             data: move_modules,
             transaction_version_to_struct_count: transaction_version_to_struct_count.clone(),
+            start_version: start_version as i64,
+            end_version: end_version as i64,
         };
 
         self.move_module_sender
@@ -235,6 +292,8 @@ impl ProcessorTrait for ParquetDefaultProcessor {
         let tm_parquet_data = ParquetDataGeneric {
             data: table_metadata,
             transaction_version_to_struct_count: transaction_version_to_struct_count.clone(),
+            start_version: start_version as i64,
+            end_version: end_version as i64,
         };
 
         self.table_metadata_sender
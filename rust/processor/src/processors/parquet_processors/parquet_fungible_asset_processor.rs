@@ -4,7 +4,10 @@
 use super::{UploadIntervalConfig, GOOGLE_APPLICATION_CREDENTIALS};
 use crate::{
     bq_analytics::{
-        create_parquet_handler_loop, generic_parquet_processor::ParquetDataGeneric,
+        create_parquet_handler_loop,
+        delta_log::ParquetTableFormat,
+        generic_parquet_processor::{NamedTable, ParquetDataGeneric},
+        parquet_writer_config::ParquetWriterConfig,
         ParquetProcessingResult,
     },
     db::common::models::{
@@ -38,6 +41,16 @@ pub struct ParquetFungibleAssetProcessorConfig {
     pub parquet_handler_response_channel_size: usize,
     pub max_buffer_size: usize,
     pub parquet_upload_interval: u64,
+    // Parquet writer properties keyed by table name, tables not listed use the defaults
+    #[serde(default = "AHashMap::new")]
+    pub per_table_writer_configs: AHashMap<String, ParquetWriterConfig>,
+    // Whether to commit the uploaded files to a table log (e.g. Delta Lake)
+    #[serde(default)]
+    pub table_format: ParquetTableFormat,
+    // Versions to start each table from, overriding the table checkpoints. Tables not listed
+    // resume from their own checkpoint, so a lagging table can be backfilled on its own.
+    #[serde(default = "AHashMap::new")]
+    pub per_table_starting_versions: AHashMap<String, u64>,
 }
 
 impl UploadIntervalConfig for ParquetFungibleAssetProcessorConfig {
@@ -53,16 +66,22 @@ pub struct ParquetFungibleAssetProcessor {
 }
 
 impl ParquetFungibleAssetProcessor {
+    pub const TABLE_NAMES: &'static [&'static str] =
+        &[CoinSupply::TABLE_NAME, FungibleAssetBalance::TABLE_NAME];
+
     pub fn new(
         connection_pool: ArcDbPool,
         config: ParquetFungibleAssetProcessorConfig,
         new_gap_detector_sender: AsyncSender<ProcessingResult>,
+        parquet_table_start_versions: AHashMap<String, u64>,
     ) -> Self {
         if let Some(credentials) = config.google_application_credentials.clone() {
             std::env::set_var(GOOGLE_APPLICATION_CREDENTIALS, credentials);
         }
 
         let coin_supply_sender = create_parquet_handler_loop::<CoinSupply>(
+            connection_pool.clone(),
+            &parquet_table_start_versions,
             new_gap_detector_sender.clone(),
             ProcessorName::ParquetFungibleAssetProcessor.into(),
             config.bucket_name.clone(),
@@ -70,9 +89,13 @@ impl ParquetFungibleAssetProcessor {
             config.parquet_handler_response_channel_size,
             config.max_buffer_size,
             config.parquet_upload_interval_in_secs(),
+            &config.per_table_writer_configs,
+            config.table_format,
         );
 
         let fungible_asset_balances_sender = create_parquet_handler_loop::<FungibleAssetBalance>(
+            connection_pool.clone(),
+            &parquet_table_start_versions,
             new_gap_detector_sender.clone(),
             ProcessorName::ParquetFungibleAssetProcessor.into(),
             config.bucket_name.clone(),
@@ -80,6 +103,8 @@ impl ParquetFungibleAssetProcessor {
             config.parquet_handler_response_channel_size,
             config.max_buffer_size,
             config.parquet_upload_interval_in_secs(),
+            &config.per_table_writer_configs,
+            config.table_format,
         );
 
         Self {
@@ -123,6 +148,8 @@ impl ProcessorTrait for ParquetFungibleAssetProcessor {
         let parquet_coin_supply = ParquetDataGeneric {
             data: coin_supply,
             transaction_version_to_struct_count: transaction_version_to_struct_count.clone(),
+            start_version: start_version as i64,
+            end_version: end_version as i64,
         };
 
         self.coin_supply_sender
@@ -133,6 +160,8 @@ impl ProcessorTrait for ParquetFungibleAssetProcessor {
         let parquet_fungible_asset_balances = ParquetDataGeneric {
             data: fungible_asset_balances,
             transaction_version_to_struct_count: transaction_version_to_struct_count.clone(),
+            start_version: start_version as i64,
+            end_version: end_version as i64,
         };
 
         self.fungible_asset_balances_sender
@@ -0,0 +1,204 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{DefaultProcessingResult, ProcessorName, ProcessorTrait};
+use crate::{
+    db::common::models::resource_snapshots::{
+        ResourceSnapshot, ResourceSnapshotConfig, ResourceSnapshotPK, ResourceTypeMatcher,
+    },
+    gap_detectors::ProcessingResult,
+    utils::{
+        database::ArcDbPool,
+        runtime_tables::insert_rows,
+        util::{normalize_type_str, parse_timestamp},
+    },
+};
+use ahash::AHashMap;
+use anyhow::bail;
+use aptos_protos::transaction::v1::{write_set_change::Change, Transaction};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use tracing::error;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResourceSnapshotProcessorConfig {
+    pub resources: Vec<ResourceSnapshotConfig>,
+}
+
+/// Keeps the latest state of each resource of the configured types, keyed by address and type.
+/// Deleted resources are kept with `is_deleted` set and no data.
+pub struct ResourceSnapshotProcessor {
+    connection_pool: ArcDbPool,
+    config: ResourceSnapshotProcessorConfig,
+    // Matchers of the configured resources, in the same order
+    matchers: Vec<ResourceTypeMatcher>,
+    per_table_chunk_sizes: AHashMap<String, usize>,
+}
+
+impl ResourceSnapshotProcessor {
+    pub fn new(
+        connection_pool: ArcDbPool,
+        config: ResourceSnapshotProcessorConfig,
+        per_table_chunk_sizes: AHashMap<String, usize>,
+    ) -> Self {
+        let matchers = config
+            .resources
+            .iter()
+            .map(|resource| resource.matcher())
+            .collect();
+        Self {
+            connection_pool,
+            config,
+            matchers,
+            per_table_chunk_sizes,
+        }
+    }
+}
+
+impl Debug for ResourceSnapshotProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = &self.connection_pool.state();
+        write!(
+            f,
+            "ResourceSnapshotProcessor {{ connections: {:?}  idle_connections: {:?} }}",
+            state.connections, state.idle_connections
+        )
+    }
+}
+
+async fn insert_to_db(
+    conn: ArcDbPool,
+    name: &'static str,
+    start_version: u64,
+    end_version: u64,
+    resources: &[ResourceSnapshotConfig],
+    snapshots: Vec<Vec<ResourceSnapshot>>,
+    per_table_chunk_sizes: &AHashMap<String, usize>,
+) -> Result<(), diesel::result::Error> {
+    tracing::trace!(
+        name = name,
+        start_version = start_version,
+        end_version = end_version,
+        "Inserting to db",
+    );
+    insert_rows(conn, resources, snapshots, per_table_chunk_sizes).await
+}
+
+#[async_trait]
+impl ProcessorTrait for ResourceSnapshotProcessor {
+    fn name(&self) -> &'static str {
+        ProcessorName::ResourceSnapshotProcessor.into()
+    }
+
+    async fn process_transactions(
+        &self,
+        transactions: Vec<Transaction>,
+        start_version: u64,
+        end_version: u64,
+        _: Option<u64>,
+    ) -> anyhow::Result<ProcessingResult> {
+        let processing_start = std::time::Instant::now();
+        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+
+        // Latest snapshot of each resource, per configured type
+        let mut current_snapshots: Vec<AHashMap<ResourceSnapshotPK, ResourceSnapshot>> =
+            vec![AHashMap::new(); self.config.resources.len()];
+        for txn in &transactions {
+            let txn_version = txn.version as i64;
+            let txn_timestamp = parse_timestamp(txn.timestamp.as_ref().unwrap(), txn_version);
+            let changes = &txn
+                .info
+                .as_ref()
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Transaction info doesn't exist! Transaction {}",
+                        txn_version
+                    )
+                })
+                .changes;
+            for wsc in changes {
+                let change = wsc.change.as_ref().unwrap();
+                let type_str = match change {
+                    Change::WriteResource(inner) => normalize_type_str(&inner.type_str),
+                    Change::DeleteResource(inner) => normalize_type_str(&inner.type_str),
+                    _ => continue,
+                };
+                let matching_resources = self
+                    .matchers
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, matcher)| matcher.matches(&type_str))
+                    .map(|(index, _)| index)
+                    .collect::<Vec<_>>();
+                if matching_resources.is_empty() {
+                    continue;
+                }
+                let snapshot = match change {
+                    Change::WriteResource(inner) => {
+                        ResourceSnapshot::from_write_resource(inner, txn_version, txn_timestamp)?
+                    },
+                    Change::DeleteResource(inner) => {
+                        ResourceSnapshot::from_delete_resource(inner, txn_version, txn_timestamp)
+                    },
+                    _ => continue,
+                };
+                for index in matching_resources {
+                    current_snapshots[index].insert(snapshot.pk(), snapshot.clone());
+                }
+            }
+        }
+
+        // Sort by PK
+        let snapshots = current_snapshots
+            .into_iter()
+            .map(|snapshots| {
+                let mut snapshots = snapshots.into_values().collect::<Vec<_>>();
+                snapshots.sort_by(|a, b| a.pk().cmp(&b.pk()));
+                snapshots
+            })
+            .collect::<Vec<_>>();
+
+        let processing_duration_in_secs = processing_start.elapsed().as_secs_f64();
+        let db_insertion_start = std::time::Instant::now();
+
+        let tx_result = insert_to_db(
+            self.get_pool(),
+            self.name(),
+            start_version,
+            end_version,
+            &self.config.resources,
+            snapshots,
+            &self.per_table_chunk_sizes,
+        )
+        .await;
+        let db_insertion_duration_in_secs = db_insertion_start.elapsed().as_secs_f64();
+
+        match tx_result {
+            Ok(_) => Ok(ProcessingResult::DefaultProcessingResult(
+                DefaultProcessingResult {
+                    start_version,
+                    end_version,
+                    processing_duration_in_secs,
+                    db_insertion_duration_in_secs,
+                    last_transaction_timestamp,
+                },
+            )),
+            Err(e) => {
+                error!(
+                    start_version = start_version,
+                    end_version = end_version,
+                    processor_name = self.name(),
+                    error = ?e,
+                    "[Parser] Error inserting transactions to db",
+                );
+                bail!(e)
+            },
+        }
+    }
+
+    fn connection_pool(&self) -> &ArcDbPool {
+        &self.connection_pool
+    }
+}
@@ -0,0 +1,290 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{DefaultProcessingResult, ProcessorName, ProcessorTrait};
+use crate::{
+    db::common::models::epoch_models::{
+        epoch_utils::{EpochResource, NewEpochEvent},
+        epoch_validator_performances::EpochValidatorPerformance,
+        epoch_validators::EpochValidator,
+        epochs::Epoch,
+    },
+    gap_detectors::ProcessingResult,
+    schema,
+    utils::{
+        database::{execute_in_chunks, get_config_table_chunk_size, ArcDbPool},
+        util::{get_events, parse_timestamp},
+    },
+};
+use ahash::AHashMap;
+use anyhow::bail;
+use aptos_protos::transaction::v1::{write_set_change::Change, Transaction};
+use async_trait::async_trait;
+use diesel::{
+    pg::{upsert::excluded, Pg},
+    query_builder::QueryFragment,
+    ExpressionMethods,
+};
+use std::fmt::Debug;
+use tracing::error;
+
+/// Records the validator set of each epoch, with the voting power and consensus key of each
+/// validator, and the proposals of each validator during the epoch.
+pub struct ValidatorSetProcessor {
+    connection_pool: ArcDbPool,
+    per_table_chunk_sizes: AHashMap<String, usize>,
+}
+
+impl ValidatorSetProcessor {
+    pub fn new(connection_pool: ArcDbPool, per_table_chunk_sizes: AHashMap<String, usize>) -> Self {
+        Self {
+            connection_pool,
+            per_table_chunk_sizes,
+        }
+    }
+}
+
+impl Debug for ValidatorSetProcessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = &self.connection_pool.state();
+        write!(
+            f,
+            "ValidatorSetProcessor {{ connections: {:?}  idle_connections: {:?} }}",
+            state.connections, state.idle_connections
+        )
+    }
+}
+
+async fn insert_to_db(
+    conn: ArcDbPool,
+    name: &'static str,
+    start_version: u64,
+    end_version: u64,
+    epochs: &[Epoch],
+    epoch_validators: &[EpochValidator],
+    epoch_validator_performances: &[EpochValidatorPerformance],
+    per_table_chunk_sizes: &AHashMap<String, usize>,
+) -> Result<(), diesel::result::Error> {
+    tracing::trace!(
+        name = name,
+        start_version = start_version,
+        end_version = end_version,
+        "Inserting to db",
+    );
+    let e = execute_in_chunks(
+        conn.clone(),
+        insert_epochs_query,
+        epochs,
+        get_config_table_chunk_size::<Epoch>("epochs", per_table_chunk_sizes),
+    );
+    let ev = execute_in_chunks(
+        conn.clone(),
+        insert_epoch_validators_query,
+        epoch_validators,
+        get_config_table_chunk_size::<EpochValidator>("epoch_validators", per_table_chunk_sizes),
+    );
+    let evp = execute_in_chunks(
+        conn,
+        insert_epoch_validator_performances_query,
+        epoch_validator_performances,
+        get_config_table_chunk_size::<EpochValidatorPerformance>(
+            "epoch_validator_performances",
+            per_table_chunk_sizes,
+        ),
+    );
+    let (e_res, ev_res, evp_res) = tokio::join!(e, ev, evp);
+    for res in [e_res, ev_res, evp_res] {
+        res?;
+    }
+    Ok(())
+}
+
+fn insert_epochs_query(
+    items_to_insert: Vec<Epoch>,
+) -> (
+    impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send,
+    Option<&'static str>,
+) {
+    use schema::epochs::dsl::*;
+    (
+        diesel::insert_into(schema::epochs::table)
+            .values(items_to_insert)
+            .on_conflict(epoch)
+            .do_nothing(),
+        None,
+    )
+}
+
+fn insert_epoch_validators_query(
+    items_to_insert: Vec<EpochValidator>,
+) -> (
+    impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send,
+    Option<&'static str>,
+) {
+    use schema::epoch_validators::dsl::*;
+    (
+        diesel::insert_into(schema::epoch_validators::table)
+            .values(items_to_insert)
+            .on_conflict((epoch, validator_address))
+            .do_nothing(),
+        None,
+    )
+}
+
+fn insert_epoch_validator_performances_query(
+    items_to_insert: Vec<EpochValidatorPerformance>,
+) -> (
+    impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send,
+    Option<&'static str>,
+) {
+    use schema::epoch_validator_performances::dsl::*;
+    (
+        diesel::insert_into(schema::epoch_validator_performances::table)
+            .values(items_to_insert)
+            .on_conflict((epoch, validator_index))
+            .do_update()
+            .set((
+                successful_proposals.eq(excluded(successful_proposals)),
+                failed_proposals.eq(excluded(failed_proposals)),
+                last_transaction_version.eq(excluded(last_transaction_version)),
+                last_transaction_timestamp.eq(excluded(last_transaction_timestamp)),
+                inserted_at.eq(excluded(inserted_at)),
+            )),
+        Some(" WHERE epoch_validator_performances.last_transaction_version <= excluded.last_transaction_version "),
+    )
+}
+
+#[async_trait]
+impl ProcessorTrait for ValidatorSetProcessor {
+    fn name(&self) -> &'static str {
+        ProcessorName::ValidatorSetProcessor.into()
+    }
+
+    async fn process_transactions(
+        &self,
+        transactions: Vec<Transaction>,
+        start_version: u64,
+        end_version: u64,
+        _: Option<u64>,
+    ) -> anyhow::Result<ProcessingResult> {
+        let processing_start = std::time::Instant::now();
+        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+
+        let mut epochs = vec![];
+        let mut epoch_validators = vec![];
+        let mut epoch_validator_performances: AHashMap<(i64, i64), EpochValidatorPerformance> =
+            AHashMap::new();
+        for txn in &transactions {
+            let txn_version = txn.version as i64;
+            let txn_timestamp = parse_timestamp(txn.timestamp.as_ref().unwrap(), txn_version);
+
+            // The reconfiguration is usually done by a block metadata transaction, but also by
+            // genesis and governance proposals
+            let mut new_epoch = None;
+            for event in get_events(txn) {
+                if let Some(event) = NewEpochEvent::from_event(event, txn_version)? {
+                    new_epoch = Some(event.epoch);
+                }
+            }
+            // Changes are made for the next epoch in the reconfiguration transaction
+            let epoch = new_epoch.unwrap_or(txn.epoch as i64);
+
+            let changes = &txn
+                .info
+                .as_ref()
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Transaction info doesn't exist! Transaction {}",
+                        txn_version
+                    )
+                })
+                .changes;
+            for wsc in changes {
+                let Some(Change::WriteResource(wr)) = wsc.change.as_ref() else {
+                    continue;
+                };
+                match EpochResource::from_write_resource(wr, txn_version)? {
+                    // The validator set is also written during the epoch when validators request
+                    // to join or leave, which only takes effect in the next epoch
+                    Some(EpochResource::ValidatorSet(validator_set)) if new_epoch.is_some() => {
+                        epochs.push(Epoch::from_validator_set(
+                            &validator_set,
+                            epoch,
+                            txn_version,
+                            txn.block_height as i64,
+                            txn_timestamp,
+                        ));
+                        epoch_validators.extend(EpochValidator::from_validator_set(
+                            &validator_set,
+                            epoch,
+                            txn_version,
+                            txn_timestamp,
+                        ));
+                    },
+                    Some(EpochResource::ValidatorPerformance(validator_performance)) => {
+                        for performance in EpochValidatorPerformance::from_validator_performance(
+                            &validator_performance,
+                            epoch,
+                            txn_version,
+                            txn_timestamp,
+                        ) {
+                            epoch_validator_performances.insert(
+                                (performance.epoch, performance.validator_index),
+                                performance,
+                            );
+                        }
+                    },
+                    _ => {},
+                }
+            }
+        }
+        // Sort by PK to avoid deadlocks between concurrent upserts
+        let mut epoch_validator_performances = epoch_validator_performances
+            .into_values()
+            .collect::<Vec<_>>();
+        epoch_validator_performances
+            .sort_by_key(|performance| (performance.epoch, performance.validator_index));
+
+        let processing_duration_in_secs = processing_start.elapsed().as_secs_f64();
+        let db_insertion_start = std::time::Instant::now();
+
+        let tx_result = insert_to_db(
+            self.get_pool(),
+            self.name(),
+            start_version,
+            end_version,
+            &epochs,
+            &epoch_validators,
+            &epoch_validator_performances,
+            &self.per_table_chunk_sizes,
+        )
+        .await;
+        let db_insertion_duration_in_secs = db_insertion_start.elapsed().as_secs_f64();
+
+        match tx_result {
+            Ok(_) => Ok(ProcessingResult::DefaultProcessingResult(
+                DefaultProcessingResult {
+                    start_version,
+                    end_version,
+                    processing_duration_in_secs,
+                    db_insertion_duration_in_secs,
+                    last_transaction_timestamp,
+                },
+            )),
+            Err(e) => {
+                error!(
+                    start_version = start_version,
+                    end_version = end_version,
+                    processor_name = self.name(),
+                    error = ?e,
+                    "[Parser] Error inserting transactions to db",
+                );
+                bail!(e)
+            },
+        }
+    }
+
+    fn connection_pool(&self) -> &ArcDbPool {
+        &self.connection_pool
+    }
+}
@@ -1,24 +1,45 @@
+use crate::utils::util::{get_events, normalize_type_str, standardize_address, strip_generics};
 use aptos_protos::transaction::v1::{
+    multisig_transaction_payload::Payload as MultisigPayloadType,
     transaction::{TransactionType, TxnData},
     transaction_payload::Payload,
-    Transaction,
+    write_set_change::Change,
+    EntryFunctionPayload, Transaction, UserTransactionRequest,
 };
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 
 /// Allows filtering transactions based on various criteria
 /// The criteria are combined with `AND`
 /// If a criteria is not set, it is ignored
 /// Criteria will be loaded from the config file
+/// Criteria that can't be expressed with `AND` can be combined in the `expression`
+/// All addresses are normalized with `standardize_address` before comparing, so `0x1` and
+/// `0x00..01` are the same address
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
 pub struct TransactionFilter {
     // Only allow transactions from these contract addresses
+    #[serde(deserialize_with = "deserialize_addresses")]
     focus_contract_addresses: Option<ahash::HashSet<String>>,
     // Skip transactions from these sender addresses
+    #[serde(deserialize_with = "deserialize_addresses")]
     skip_sender_addresses: Option<ahash::HashSet<String>>,
     // Skip all transactions that aren't user transactions
     focus_user_transactions: bool,
+    // Only allow transactions emitting an event of these types. Types can be given with or
+    // without generics, e.g. `0x1::coin::DepositEvent`
+    #[serde(deserialize_with = "deserialize_type_strs")]
+    focus_event_types: Option<ahash::HashSet<String>>,
+    // Only allow transactions calling these entry functions, e.g. `0x1::coin::transfer`
+    #[serde(deserialize_with = "deserialize_type_strs")]
+    focus_entry_functions: Option<ahash::HashSet<String>>,
+    // Only allow transactions changing resources or modules defined at these addresses
+    #[serde(deserialize_with = "deserialize_addresses")]
+    focus_write_set_addresses: Option<ahash::HashSet<String>>,
+    // Only allow transactions matching this expression
+    #[serde(deserialize_with = "deserialize_filter_expression")]
+    expression: Option<FilterExpression>,
 }
 
 impl TransactionFilter {
@@ -26,12 +47,19 @@ impl TransactionFilter {
         focus_contract_addresses: Option<ahash::HashSet<String>>,
         skip_sender_addresses: Option<ahash::HashSet<String>>,
         focus_user_transactions: bool,
+        focus_event_types: Option<ahash::HashSet<String>>,
+        focus_entry_functions: Option<ahash::HashSet<String>>,
+        focus_write_set_addresses: Option<ahash::HashSet<String>>,
+        expression: Option<FilterExpression>,
     ) -> Self {
-        // TODO: normalize addresses
         Self {
-            focus_contract_addresses,
-            skip_sender_addresses,
+            focus_contract_addresses: focus_contract_addresses.map(normalize_addresses),
+            skip_sender_addresses: skip_sender_addresses.map(normalize_addresses),
             focus_user_transactions,
+            focus_event_types: focus_event_types.map(normalize_type_strs),
+            focus_entry_functions: focus_entry_functions.map(normalize_type_strs),
+            focus_write_set_addresses: focus_write_set_addresses.map(normalize_addresses),
+            expression: expression.map(FilterExpression::normalize),
         }
     }
 
@@ -44,6 +72,25 @@ impl TransactionFilter {
             return false;
         }
 
+        if let Some(expression) = &self.expression {
+            if !expression.matches(transaction) {
+                return false;
+            }
+        }
+
+        // Events and write set changes exist for all transaction types
+        if let Some(focus_event_types) = &self.focus_event_types {
+            if !has_event_type(transaction, focus_event_types) {
+                return false;
+            }
+        }
+
+        if let Some(focus_write_set_addresses) = &self.focus_write_set_addresses {
+            if !has_write_set_address(transaction, focus_write_set_addresses) {
+                return false;
+            }
+        }
+
         // If it's not a user transaction, we can skip the rest of the checks
         if !is_user_txn {
             return true;
@@ -53,24 +100,51 @@ impl TransactionFilter {
             if let Some(utr) = user_transaction.request.as_ref() {
                 // Skip if sender is in the skip list
                 if let Some(skip_sender_addresses) = &self.skip_sender_addresses {
-                    if skip_sender_addresses.contains(&utr.sender) {
+                    if skip_sender_addresses.contains(&normalize_address(&utr.sender)) {
                         return false;
                     }
                 }
 
-                if let Some(focus_contract_addresses) = &self.focus_contract_addresses {
-                    // Skip if focus contract addresses are set and the transaction isn't in the list
-                    if let Some(payload) = utr.payload.as_ref() {
-                        if let Some(Payload::EntryFunctionPayload(efp)) = payload.payload.as_ref() {
-                            if let Some(function) = efp.function.as_ref() {
-                                if let Some(module) = function.module.as_ref() {
-                                    if !focus_contract_addresses.contains(&module.address) {
+                match get_entry_function_payload(utr) {
+                    Some(efp) => {
+                        if let Some(function) = efp.function.as_ref() {
+                            if let Some(module) = function.module.as_ref() {
+                                // Skip if focus contract addresses are set and the transaction isn't in the list
+                                if let Some(focus_contract_addresses) =
+                                    &self.focus_contract_addresses
+                                {
+                                    if !focus_contract_addresses
+                                        .contains(&normalize_address(&module.address))
+                                    {
+                                        return false;
+                                    }
+                                }
+
+                                if let Some(focus_entry_functions) = &self.focus_entry_functions {
+                                    if !focus_entry_functions.contains(&get_entry_function_id(efp))
+                                    {
                                         return false;
                                     }
                                 }
                             }
                         }
-                    }
+                    },
+                    None => {
+                        // Scripts and multisig transactions executing a stored payload don't call an entry function
+                        if self.focus_entry_functions.is_some() {
+                            return false;
+                        }
+
+                        // There's no module to match the contract addresses against, so match
+                        // the contracts whose resources or modules are changed instead
+                        if let Some(focus_contract_addresses) = &self.focus_contract_addresses {
+                            if is_script_or_multisig(utr)
+                                && !has_write_set_address(transaction, focus_contract_addresses)
+                            {
+                                return false;
+                            }
+                        }
+                    },
                 }
             }
         }
@@ -78,3 +152,534 @@ impl TransactionFilter {
         true
     }
 }
+
+/// A filter expression combining the primitive matchers with `and`, `or` and `not`, e.g.
+/// ```yaml
+/// expression:
+///   and:
+///     - user_transaction
+///     - or:
+///         - entry_function: ["0x1::coin::transfer"]
+///         - event_type: ["0x1::coin::DepositEvent"]
+///     - not:
+///         sender: ["0xdead"]
+/// ```
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterExpression {
+    And(Vec<FilterExpression>),
+    Or(Vec<FilterExpression>),
+    Not(Box<FilterExpression>),
+    // Matches user transactions
+    UserTransaction,
+    // Matches user transactions sent by these addresses
+    Sender(ahash::HashSet<String>),
+    // Matches user transactions calling an entry function of these contract addresses. Scripts
+    // and multisig transactions without a payload match the contracts they change instead.
+    ContractAddress(ahash::HashSet<String>),
+    // Matches user transactions calling these entry functions, e.g. `0x1::coin::transfer`
+    EntryFunction(ahash::HashSet<String>),
+    // Matches transactions emitting an event of these types, with or without generics
+    EventType(ahash::HashSet<String>),
+    // Matches transactions changing resources or modules defined at these addresses
+    WriteSetAddress(ahash::HashSet<String>),
+}
+
+impl FilterExpression {
+    /// Returns true if the transaction matches the expression
+    pub fn matches(&self, transaction: &Transaction) -> bool {
+        match self {
+            FilterExpression::And(expressions) => {
+                expressions.iter().all(|e| e.matches(transaction))
+            },
+            FilterExpression::Or(expressions) => expressions.iter().any(|e| e.matches(transaction)),
+            FilterExpression::Not(expression) => !expression.matches(transaction),
+            FilterExpression::UserTransaction => transaction.r#type == TransactionType::User as i32,
+            FilterExpression::Sender(senders) => get_user_transaction_request(transaction)
+                .is_some_and(|utr| senders.contains(&normalize_address(&utr.sender))),
+            FilterExpression::ContractAddress(addresses) => {
+                let utr = match get_user_transaction_request(transaction) {
+                    Some(utr) => utr,
+                    None => return false,
+                };
+                match get_entry_function_payload(utr) {
+                    Some(efp) => efp
+                        .function
+                        .as_ref()
+                        .and_then(|function| function.module.as_ref())
+                        .is_some_and(|module| {
+                            addresses.contains(&normalize_address(&module.address))
+                        }),
+                    None => {
+                        is_script_or_multisig(utr) && has_write_set_address(transaction, addresses)
+                    },
+                }
+            },
+            FilterExpression::EntryFunction(entry_functions) => {
+                get_user_transaction_request(transaction)
+                    .and_then(get_entry_function_payload)
+                    .is_some_and(|efp| entry_functions.contains(&get_entry_function_id(efp)))
+            },
+            FilterExpression::EventType(event_types) => has_event_type(transaction, event_types),
+            FilterExpression::WriteSetAddress(addresses) => {
+                has_write_set_address(transaction, addresses)
+            },
+        }
+    }
+
+    /// Normalizes the addresses of all the matchers in the expression
+    pub fn normalize(self) -> Self {
+        match self {
+            FilterExpression::And(expressions) => {
+                FilterExpression::And(expressions.into_iter().map(Self::normalize).collect())
+            },
+            FilterExpression::Or(expressions) => {
+                FilterExpression::Or(expressions.into_iter().map(Self::normalize).collect())
+            },
+            FilterExpression::Not(expression) => {
+                FilterExpression::Not(Box::new(expression.normalize()))
+            },
+            FilterExpression::UserTransaction => FilterExpression::UserTransaction,
+            FilterExpression::Sender(senders) => {
+                FilterExpression::Sender(normalize_addresses(senders))
+            },
+            FilterExpression::ContractAddress(addresses) => {
+                FilterExpression::ContractAddress(normalize_addresses(addresses))
+            },
+            FilterExpression::EntryFunction(entry_functions) => {
+                FilterExpression::EntryFunction(normalize_type_strs(entry_functions))
+            },
+            FilterExpression::EventType(event_types) => {
+                FilterExpression::EventType(normalize_type_strs(event_types))
+            },
+            FilterExpression::WriteSetAddress(addresses) => {
+                FilterExpression::WriteSetAddress(normalize_addresses(addresses))
+            },
+        }
+    }
+
+    /// Checks that the expression can match something: no empty `and`/`or` nodes or
+    /// matchers, and well formed entry function ids.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        match self {
+            FilterExpression::And(expressions) | FilterExpression::Or(expressions) => {
+                anyhow::ensure!(
+                    !expressions.is_empty(),
+                    "`and` and `or` need at least one expression"
+                );
+                expressions.iter().try_for_each(|e| e.validate())
+            },
+            FilterExpression::Not(expression) => expression.validate(),
+            FilterExpression::UserTransaction => Ok(()),
+            FilterExpression::Sender(values)
+            | FilterExpression::ContractAddress(values)
+            | FilterExpression::EventType(values)
+            | FilterExpression::WriteSetAddress(values) => {
+                anyhow::ensure!(!values.is_empty(), "Matchers need at least one value");
+                Ok(())
+            },
+            FilterExpression::EntryFunction(entry_functions) => {
+                anyhow::ensure!(
+                    !entry_functions.is_empty(),
+                    "Matchers need at least one value"
+                );
+                for entry_function in entry_functions {
+                    anyhow::ensure!(
+                        entry_function.split("::").count() == 3
+                            && entry_function.split("::").all(|part| !part.is_empty()),
+                        "Invalid entry function {}, expected <address>::<module>::<function>",
+                        entry_function
+                    );
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+/// Rejects invalid expressions when the config is loaded rather than silently filtering out
+/// every transaction.
+fn deserialize_filter_expression<'de, D>(
+    deserializer: D,
+) -> Result<Option<FilterExpression>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let expression = Option::<FilterExpression>::deserialize(deserializer)?;
+    if let Some(expression) = expression.as_ref() {
+        expression.validate().map_err(serde::de::Error::custom)?;
+    }
+    Ok(expression.map(FilterExpression::normalize))
+}
+
+fn deserialize_addresses<'de, D>(
+    deserializer: D,
+) -> Result<Option<ahash::HashSet<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<ahash::HashSet<String>>::deserialize(deserializer)?.map(normalize_addresses))
+}
+
+fn deserialize_type_strs<'de, D>(
+    deserializer: D,
+) -> Result<Option<ahash::HashSet<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<ahash::HashSet<String>>::deserialize(deserializer)?.map(normalize_type_strs))
+}
+
+/// `0x1`, `0x0000..01` and `0X01` all become `0x0000..01`
+fn normalize_address(address: &str) -> String {
+    standardize_address(&address.to_lowercase())
+}
+
+fn normalize_addresses(addresses: ahash::HashSet<String>) -> ahash::HashSet<String> {
+    addresses.iter().map(|a| normalize_address(a)).collect()
+}
+
+fn normalize_type_strs(type_strs: ahash::HashSet<String>) -> ahash::HashSet<String> {
+    type_strs.iter().map(|t| normalize_type_str(t)).collect()
+}
+
+fn get_user_transaction_request(transaction: &Transaction) -> Option<&UserTransactionRequest> {
+    match transaction.txn_data.as_ref()? {
+        TxnData::User(user_transaction) => user_transaction.request.as_ref(),
+        _ => None,
+    }
+}
+
+/// `0x1::coin::transfer`, with the address normalized
+fn get_entry_function_id(efp: &EntryFunctionPayload) -> String {
+    match efp.function.as_ref() {
+        Some(function) => match function.module.as_ref() {
+            Some(module) => format!(
+                "{}::{}::{}",
+                normalize_address(&module.address),
+                module.name,
+                function.name
+            ),
+            None => normalize_type_str(&efp.entry_function_id_str),
+        },
+        None => normalize_type_str(&efp.entry_function_id_str),
+    }
+}
+
+fn has_event_type(transaction: &Transaction, event_types: &ahash::HashSet<String>) -> bool {
+    get_events(transaction).iter().any(|event| {
+        let type_str = normalize_type_str(&event.type_str);
+        event_types.contains(&type_str) || event_types.contains(strip_generics(&type_str))
+    })
+}
+
+fn has_write_set_address(transaction: &Transaction, addresses: &ahash::HashSet<String>) -> bool {
+    get_write_set_addresses(transaction)
+        .any(|address| addresses.contains(&normalize_address(address)))
+}
+
+/// Returns the entry function called by the transaction, including entry functions called
+/// through a multisig account.
+fn get_entry_function_payload(utr: &UserTransactionRequest) -> Option<&EntryFunctionPayload> {
+    match utr.payload.as_ref()?.payload.as_ref()? {
+        Payload::EntryFunctionPayload(efp) => Some(efp),
+        Payload::MultisigPayload(multisig_payload) => {
+            match multisig_payload
+                .transaction_payload
+                .as_ref()?
+                .payload
+                .as_ref()?
+            {
+                MultisigPayloadType::EntryFunctionPayload(efp) => Some(efp),
+            }
+        },
+        _ => None,
+    }
+}
+
+fn is_script_or_multisig(utr: &UserTransactionRequest) -> bool {
+    matches!(
+        utr.payload
+            .as_ref()
+            .and_then(|payload| payload.payload.as_ref()),
+        Some(Payload::ScriptPayload(_) | Payload::MultisigPayload(_))
+    )
+}
+
+/// Addresses of the modules and resource types changed by the transaction
+fn get_write_set_addresses(transaction: &Transaction) -> impl Iterator<Item = &String> {
+    transaction
+        .info
+        .iter()
+        .flat_map(|info| info.changes.iter())
+        .filter_map(|wsc| match wsc.change.as_ref()? {
+            Change::WriteModule(inner) => Some(&inner.address),
+            Change::DeleteModule(inner) => Some(&inner.address),
+            Change::WriteResource(inner) => inner.r#type.as_ref().map(|t| &t.address),
+            Change::DeleteResource(inner) => inner.r#type.as_ref().map(|t| &t.address),
+            Change::WriteTableItem(_) | Change::DeleteTableItem(_) => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_protos::transaction::v1::{
+        EntryFunctionId, Event, MoveModuleId, MoveStructTag, MultisigPayload,
+        MultisigTransactionPayload, ScriptPayload, TransactionInfo, TransactionPayload,
+        UserTransaction, WriteResource, WriteSetChange,
+    };
+
+    fn user_transaction(sender: &str, payload: Payload) -> Transaction {
+        Transaction {
+            r#type: TransactionType::User as i32,
+            txn_data: Some(TxnData::User(UserTransaction {
+                request: Some(UserTransactionRequest {
+                    sender: sender.to_string(),
+                    payload: Some(TransactionPayload {
+                        payload: Some(payload),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                events: vec![Event {
+                    type_str: "0x1::coin::DepositEvent".to_string(),
+                    ..Default::default()
+                }],
+            })),
+            info: Some(TransactionInfo {
+                changes: vec![WriteSetChange {
+                    change: Some(Change::WriteResource(WriteResource {
+                        address: sender.to_string(),
+                        r#type: Some(MoveStructTag {
+                            address: "0x1".to_string(),
+                            module: "coin".to_string(),
+                            name: "CoinStore".to_string(),
+                            ..Default::default()
+                        }),
+                        type_str: "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>".to_string(),
+                        ..Default::default()
+                    })),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn entry_function_payload(address: &str, module: &str, function: &str) -> EntryFunctionPayload {
+        EntryFunctionPayload {
+            function: Some(EntryFunctionId {
+                module: Some(MoveModuleId {
+                    address: address.to_string(),
+                    name: module.to_string(),
+                }),
+                name: function.to_string(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    // Normalized like the sets loaded from the config
+    fn set(items: &[&str]) -> Option<ahash::HashSet<String>> {
+        Some(normalize_type_strs(
+            items.iter().map(|item| item.to_string()).collect(),
+        ))
+    }
+
+    #[test]
+    fn test_focus_entry_functions() {
+        let filter = TransactionFilter {
+            focus_entry_functions: set(&["0x1::coin::transfer"]),
+            ..Default::default()
+        };
+        let transfer = user_transaction(
+            "0xa",
+            Payload::EntryFunctionPayload(entry_function_payload("0x1", "coin", "transfer")),
+        );
+        let register = user_transaction(
+            "0xa",
+            Payload::EntryFunctionPayload(entry_function_payload("0x1", "coin", "register")),
+        );
+        let multisig_transfer = user_transaction(
+            "0xa",
+            Payload::MultisigPayload(MultisigPayload {
+                multisig_address: "0xb".to_string(),
+                transaction_payload: Some(MultisigTransactionPayload {
+                    payload: Some(MultisigPayloadType::EntryFunctionPayload(
+                        entry_function_payload("0x1", "coin", "transfer"),
+                    )),
+                    ..Default::default()
+                }),
+            }),
+        );
+        let script = user_transaction("0xa", Payload::ScriptPayload(ScriptPayload::default()));
+        assert!(filter.include(&transfer));
+        assert!(!filter.include(&register));
+        assert!(filter.include(&multisig_transfer));
+        assert!(!filter.include(&script));
+    }
+
+    #[test]
+    fn test_focus_contract_addresses_script() {
+        let filter = TransactionFilter {
+            focus_contract_addresses: set(&["0x1"]),
+            ..Default::default()
+        };
+        // The script changes a resource defined at 0x1
+        let script = user_transaction("0xa", Payload::ScriptPayload(ScriptPayload::default()));
+        assert!(filter.include(&script));
+
+        let filter = TransactionFilter {
+            focus_contract_addresses: set(&["0x2"]),
+            ..Default::default()
+        };
+        assert!(!filter.include(&script));
+    }
+
+    #[test]
+    fn test_filter_expression() {
+        // JSON is valid YAML, the config uses the same representation
+        let expression: FilterExpression = serde_json::from_value(serde_json::json!({
+            "and": [
+                "user_transaction",
+                { "or": [
+                    { "entry_function": ["0x1::coin::register"] },
+                    { "event_type": ["0x1::coin::DepositEvent"] },
+                ]},
+                { "not": { "sender": ["0xdead"] } },
+            ]
+        }))
+        .unwrap();
+        expression.validate().unwrap();
+        let expression = expression.normalize();
+
+        let transfer = user_transaction(
+            "0xa",
+            Payload::EntryFunctionPayload(entry_function_payload("0x1", "coin", "transfer")),
+        );
+        let transfer_from_dead = user_transaction(
+            "0xdead",
+            Payload::EntryFunctionPayload(entry_function_payload("0x1", "coin", "transfer")),
+        );
+        let block_metadata = Transaction {
+            r#type: TransactionType::BlockMetadata as i32,
+            ..Default::default()
+        };
+        assert!(expression.matches(&transfer));
+        assert!(!expression.matches(&transfer_from_dead));
+        assert!(!expression.matches(&block_metadata));
+
+        let filter = TransactionFilter {
+            expression: Some(expression),
+            ..Default::default()
+        };
+        assert!(filter.include(&transfer));
+        assert!(!filter.include(&transfer_from_dead));
+    }
+
+    #[test]
+    fn test_invalid_filter_expression() {
+        for config in [
+            serde_json::json!({ "expression": { "or": [] } }),
+            serde_json::json!({ "expression": { "not": { "sender": [] } } }),
+            serde_json::json!({ "expression": { "entry_function": ["0x1::coin"] } }),
+            serde_json::json!({ "expression": { "xor": [] } }),
+        ] {
+            assert!(serde_json::from_value::<TransactionFilter>(config).is_err());
+        }
+        let filter: TransactionFilter = serde_json::from_value(serde_json::json!({
+            "expression": { "not": "user_transaction" }
+        }))
+        .unwrap();
+        assert!(filter.expression.is_some());
+    }
+
+    #[test]
+    fn test_normalize_addresses() {
+        let long_form = format!("0x{:0>64}", "1");
+        assert_eq!(normalize_address("0x1"), long_form);
+        assert_eq!(normalize_address(&long_form), long_form);
+        assert_eq!(normalize_address("0x01"), long_form);
+        assert_eq!(normalize_address("0xABcd"), format!("0x{:0>64}", "abcd"));
+        assert_eq!(
+            normalize_type_str("0x1::coin::CoinStore<0X0A::my_coin::MyCoin>"),
+            format!(
+                "{}::coin::CoinStore<0x{:0>64}::my_coin::MyCoin>",
+                long_form, "a"
+            )
+        );
+    }
+
+    #[test]
+    fn test_filter_matches_any_address_form() {
+        let filter: TransactionFilter = serde_json::from_value(serde_json::json!({
+            "focus_contract_addresses": [format!("0x{:0>64}", "1")],
+            "skip_sender_addresses": ["0xDEAD"],
+            "focus_entry_functions": ["0x01::coin::transfer"],
+        }))
+        .unwrap();
+        let transfer = user_transaction(
+            "0xa",
+            Payload::EntryFunctionPayload(entry_function_payload("0x1", "coin", "transfer")),
+        );
+        let long_form_transfer = user_transaction(
+            &format!("0x{:0>64}", "a"),
+            Payload::EntryFunctionPayload(entry_function_payload(
+                &format!("0x{:0>64}", "1"),
+                "coin",
+                "transfer",
+            )),
+        );
+        let transfer_from_dead = user_transaction(
+            &format!("0x{:0>64}", "dead"),
+            Payload::EntryFunctionPayload(entry_function_payload("0x1", "coin", "transfer")),
+        );
+        assert!(filter.include(&transfer));
+        assert!(filter.include(&long_form_transfer));
+        assert!(!filter.include(&transfer_from_dead));
+
+        let filter = TransactionFilter::new(
+            None,
+            None,
+            false,
+            Some(
+                ["0x0001::coin::DepositEvent".to_string()]
+                    .into_iter()
+                    .collect(),
+            ),
+            None,
+            None,
+            None,
+        );
+        assert!(filter.include(&transfer));
+    }
+
+    #[test]
+    fn test_focus_event_types_and_write_set_addresses() {
+        let transaction = user_transaction(
+            "0xa",
+            Payload::EntryFunctionPayload(entry_function_payload("0x1", "coin", "transfer")),
+        );
+        let filter = TransactionFilter {
+            focus_event_types: set(&["0x1::coin::DepositEvent"]),
+            focus_write_set_addresses: set(&["0x1"]),
+            ..Default::default()
+        };
+        assert!(filter.include(&transaction));
+
+        let filter = TransactionFilter {
+            focus_event_types: set(&["0x1::coin::WithdrawEvent"]),
+            ..Default::default()
+        };
+        assert!(!filter.include(&transaction));
+
+        let filter = TransactionFilter {
+            focus_write_set_addresses: set(&["0xa"]),
+            ..Default::default()
+        };
+        assert!(!filter.include(&transaction));
+    }
+}
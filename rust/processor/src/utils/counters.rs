@@ -282,3 +282,23 @@ pub static PARQUET_HANDLER_BUFFER_SIZE: Lazy<IntGaugeVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+/// Number of transactions skipped and sent to the dead letter table
+pub static DEAD_LETTER_TRANSACTIONS_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "indexer_processor_dead_letter_transactions_count",
+        "Number of transactions skipped and sent to the dead letter table",
+        &["processor_name"]
+    )
+    .unwrap()
+});
+
+/// Number of discrepancies found by the audit processor
+pub static AUDIT_DISCREPANCIES_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "indexer_audit_discrepancies_count",
+        "Number of discrepancies found by the audit processor",
+        &["check_name"]
+    )
+    .unwrap()
+});
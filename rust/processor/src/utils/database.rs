@@ -103,6 +103,69 @@ fn parse_and_clean_db_url(url: &str) -> (String, Option<String>) {
     (db_url.to_string(), cert_path)
 }
 
+/// Makes the connections of the url use the given schema, so that several networks can be
+/// indexed in the same database, one schema each.
+pub fn database_url_with_schema(database_url: &str, schema: &str) -> anyhow::Result<String> {
+    anyhow::ensure!(
+        is_valid_identifier(schema),
+        "Invalid schema name {}, only lowercase letters, digits and underscores are allowed",
+        schema
+    );
+    let mut db_url = url::Url::parse(database_url)?;
+    let search_path = format!("-csearch_path={}", schema);
+    let mut has_options = false;
+    let mut query_pairs = vec![];
+    // Postgres only reads one options parameter, so the search path is added to existing ones
+    for (key, value) in db_url.query_pairs() {
+        if key == "options" {
+            anyhow::ensure!(
+                !value.contains("search_path"),
+                "The database url already sets a search_path, it can't be combined with db_schema"
+            );
+            has_options = true;
+            query_pairs.push((key.to_string(), format!("{} {}", value, search_path)));
+        } else {
+            query_pairs.push((key.to_string(), value.to_string()));
+        }
+    }
+    if !has_options {
+        query_pairs.push(("options".to_string(), search_path));
+    }
+    // tokio-postgres doesn't decode `+` as a space, and the form encoding only uses `+` for spaces
+    let query = url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(query_pairs)
+        .finish()
+        .replace('+', "%20");
+    db_url.set_query(Some(&query));
+    Ok(db_url.to_string())
+}
+
+/// Whether the name can be used as a postgres identifier built into a query as is
+pub fn is_valid_identifier(name: &str) -> bool {
+    name.len() <= 63
+        && name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// The schema has to exist before the migrations run in it
+pub async fn create_schema_if_not_exists(pool: ArcDbPool, schema: &str) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        is_valid_identifier(schema),
+        "Invalid schema name {}",
+        schema
+    );
+    let mut conn = pool.get().await?;
+    diesel::sql_query(format!("CREATE SCHEMA IF NOT EXISTS {}", schema))
+        .execute(&mut conn)
+        .await?;
+    Ok(())
+}
+
 pub async fn new_db_pool(
     database_url: &str,
     max_pool_size: Option<u32>,
@@ -283,3 +346,38 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_database_url_with_schema() {
+        assert_eq!(
+            database_url_with_schema("postgres://user:pw@localhost:5432/indexer", "testnet")
+                .unwrap(),
+            "postgres://user:pw@localhost:5432/indexer?options=-csearch_path%3Dtestnet"
+        );
+        assert_eq!(
+            database_url_with_schema("postgres://localhost/indexer?sslmode=require", "devnet_2")
+                .unwrap(),
+            "postgres://localhost/indexer?sslmode=require&options=-csearch_path%3Ddevnet_2"
+        );
+        assert_eq!(
+            database_url_with_schema(
+                "postgres://localhost/indexer?options=-cstatement_timeout%3D5000",
+                "testnet"
+            )
+            .unwrap(),
+            "postgres://localhost/indexer?options=-cstatement_timeout%3D5000%20-csearch_path%3Dtestnet"
+        );
+        assert!(database_url_with_schema(
+            "postgres://localhost/indexer?options=-csearch_path%3Dpublic",
+            "testnet"
+        )
+        .is_err());
+        assert!(database_url_with_schema("postgres://localhost/indexer", "Testnet").is_err());
+        assert!(database_url_with_schema("postgres://localhost/indexer", "a; DROP").is_err());
+        assert!(database_url_with_schema("postgres://localhost/indexer", "1net").is_err());
+    }
+}
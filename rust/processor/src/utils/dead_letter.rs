@@ -0,0 +1,312 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Dead letter mode. A batch that fails to process (error or panic) is retried one transaction
+//! at a time, and the transactions that still fail are written to `dead_letter_transactions`
+//! and skipped instead of taking the whole processor down. Transactions processed on their own
+//! lose the context of the rest of the batch, so this is only a fallback for poison transactions.
+
+use crate::{
+    db::common::models::dead_letter_transaction::DeadLetterTransaction,
+    gap_detectors::ProcessingResult,
+    processors::{DefaultProcessingResult, Processor, ProcessorTrait},
+    schema::dead_letter_transactions,
+    utils::{
+        counters::DEAD_LETTER_TRANSACTIONS_COUNT,
+        database::{execute_with_better_error, ArcDbPool},
+    },
+    worker::PROCESSOR_SERVICE_TYPE,
+};
+use anyhow::Result;
+use aptos_protos::transaction::v1::Transaction;
+use futures_util::FutureExt;
+use serde::{Deserialize, Serialize};
+use std::{
+    any::Any,
+    cell::Cell,
+    future::Future,
+    panic::AssertUnwindSafe,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Once,
+    },
+};
+use tokio::task::JoinHandle;
+use tracing::{error, warn};
+
+thread_local! {
+    // Set while polling a processor guarded by `catch_processing_panic`
+    static CATCHING_PANICS: Cell<bool> = const { Cell::new(false) };
+}
+
+static INSTALL_PANIC_HOOK: Once = Once::new();
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DeadLetterConfig {
+    // Hard cap on the number of dead lettered transactions of the processor, including the ones
+    // from previous runs. The processor fails once it's reached.
+    pub max_dead_letter_transactions: u64,
+}
+
+pub struct DeadLetterQueue {
+    processor_name: &'static str,
+    db_pool: ArcDbPool,
+    max_dead_letter_transactions: u64,
+    num_dead_letter_transactions: AtomicU64,
+}
+
+impl DeadLetterQueue {
+    pub async fn new(
+        processor_name: &'static str,
+        db_pool: ArcDbPool,
+        config: &DeadLetterConfig,
+    ) -> Result<Self> {
+        let mut conn = db_pool.get().await?;
+        let num_dead_letter_transactions =
+            DeadLetterTransaction::count_by_processor(processor_name, &mut conn).await? as u64;
+        drop(conn);
+        install_panic_hook();
+        Ok(Self {
+            processor_name,
+            db_pool,
+            max_dead_letter_transactions: config.max_dead_letter_transactions,
+            num_dead_letter_transactions: AtomicU64::new(num_dead_letter_transactions),
+        })
+    }
+
+    /// Processes the batch, falling back to processing its transactions one by one if it fails.
+    pub async fn process_transactions(
+        &self,
+        processor: &Processor,
+        transactions: Vec<Transaction>,
+        start_version: u64,
+        end_version: u64,
+        db_chain_id: Option<u64>,
+    ) -> Result<ProcessingResult> {
+        let batch_error = match catch_processing_panic(processor.process_transactions(
+            transactions.clone(),
+            start_version,
+            end_version,
+            db_chain_id,
+        ))
+        .await
+        {
+            Ok(result) => return Ok(result),
+            Err(e) => e,
+        };
+        warn!(
+            processor_name = self.processor_name,
+            service_type = PROCESSOR_SERVICE_TYPE,
+            start_version,
+            end_version,
+            error = ?batch_error,
+            "[Parser] Failed to process batch, retrying transactions one by one"
+        );
+
+        let processing_time = std::time::Instant::now();
+        let mut db_insertion_duration_in_secs = 0.0;
+        let last_transaction_timestamp = transactions.last().and_then(|t| t.timestamp.clone());
+        for transaction in transactions {
+            let version = transaction.version;
+            match catch_processing_panic(processor.process_transactions(
+                vec![transaction],
+                version,
+                version,
+                db_chain_id,
+            ))
+            .await
+            {
+                Ok(ProcessingResult::DefaultProcessingResult(result)) => {
+                    db_insertion_duration_in_secs += result.db_insertion_duration_in_secs;
+                },
+                Ok(ProcessingResult::ParquetProcessingResult(_)) => {
+                    anyhow::bail!("Dead letter mode isn't supported for parquet processors")
+                },
+                Err(e) => self.add(version, e).await?,
+            }
+        }
+        Ok(ProcessingResult::DefaultProcessingResult(
+            DefaultProcessingResult {
+                start_version,
+                end_version,
+                last_transaction_timestamp,
+                processing_duration_in_secs: processing_time.elapsed().as_secs_f64()
+                    - db_insertion_duration_in_secs,
+                db_insertion_duration_in_secs,
+            },
+        ))
+    }
+
+    async fn add(&self, transaction_version: u64, e: anyhow::Error) -> Result<()> {
+        error!(
+            processor_name = self.processor_name,
+            service_type = PROCESSOR_SERVICE_TYPE,
+            transaction_version,
+            error = ?e,
+            "[Parser] Sending transaction to the dead letter table"
+        );
+        execute_with_better_error(
+            self.db_pool.clone(),
+            diesel::insert_into(dead_letter_transactions::table)
+                .values(DeadLetterTransaction {
+                    processor: self.processor_name.to_string(),
+                    transaction_version: transaction_version as i64,
+                    error: format!("{:?}", e),
+                })
+                .on_conflict_do_nothing(),
+            None,
+        )
+        .await?;
+        DEAD_LETTER_TRANSACTIONS_COUNT
+            .with_label_values(&[self.processor_name])
+            .inc();
+        let num_dead_letter_transactions = self
+            .num_dead_letter_transactions
+            .fetch_add(1, Ordering::SeqCst)
+            + 1;
+        anyhow::ensure!(
+            num_dead_letter_transactions <= self.max_dead_letter_transactions,
+            "Reached the maximum of {} dead letter transactions, last error at version {}: {:?}",
+            self.max_dead_letter_transactions,
+            transaction_version,
+            e
+        );
+        Ok(())
+    }
+}
+
+/// Turns a panic of the processor into an error. Only panics raised while polling the future
+/// itself are caught, not the ones of tasks it spawns, except for blocking tasks spawned with
+/// `spawn_blocking`.
+async fn catch_processing_panic<F>(future: F) -> Result<ProcessingResult>
+where
+    F: Future<Output = Result<ProcessingResult>>,
+{
+    let mut future = Box::pin(future);
+    let result = AssertUnwindSafe(futures_util::future::poll_fn(move |cx| {
+        let was_catching = CATCHING_PANICS.with(|c| c.replace(true));
+        let poll = future.as_mut().poll(cx);
+        CATCHING_PANICS.with(|c| c.set(was_catching));
+        poll
+    }))
+    .catch_unwind()
+    .await;
+    match result {
+        Ok(result) => result,
+        Err(panic) => {
+            // The flag wasn't reset since the poll unwound
+            CATCHING_PANICS.with(|c| c.set(false));
+            Err(anyhow::anyhow!("Panicked: {}", panic_message(&*panic)))
+        },
+    }
+}
+
+/// `tokio::task::spawn_blocking` for processors, panics of the closure are caught when the
+/// processor is guarded by `catch_processing_panic`. The panic reaches the caller as a
+/// `JoinError`.
+pub fn spawn_blocking<F, R>(f: F) -> JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let catching_panics = CATCHING_PANICS.with(|c| c.get());
+    tokio::task::spawn_blocking(move || {
+        // Blocking threads are reused, the flag is reset even if the closure unwinds
+        let _reset = ResetCatchingPanics(CATCHING_PANICS.with(|c| c.replace(catching_panics)));
+        f()
+    })
+}
+
+struct ResetCatchingPanics(bool);
+
+impl Drop for ResetCatchingPanics {
+    fn drop(&mut self) {
+        CATCHING_PANICS.with(|c| c.set(self.0));
+    }
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// The server panic hook exits the process, so skip it for the panics we catch.
+fn install_panic_hook() {
+    INSTALL_PANIC_HOOK.call_once(|| {
+        let previous_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |panic_info| {
+            if CATCHING_PANICS.with(|c| c.get()) {
+                warn!("[Parser] Caught processor panic: {}", panic_info);
+            } else {
+                previous_hook(panic_info);
+            }
+        }));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_catch_processing_panic() {
+        install_panic_hook();
+        let result = catch_processing_panic(async {
+            let data: serde_json::Value = serde_json::from_str("not json").unwrap();
+            Ok::<_, anyhow::Error>(ProcessingResult::DefaultProcessingResult(
+                DefaultProcessingResult {
+                    start_version: data.as_u64().unwrap(),
+                    end_version: 0,
+                    last_transaction_timestamp: None,
+                    processing_duration_in_secs: 0.0,
+                    db_insertion_duration_in_secs: 0.0,
+                },
+            ))
+        })
+        .await;
+        assert!(result.err().unwrap().to_string().starts_with("Panicked: "));
+        assert!(!CATCHING_PANICS.with(|c| c.get()));
+
+        let result =
+            catch_processing_panic(async { Err::<ProcessingResult, _>(anyhow::anyhow!("failed")) })
+                .await;
+        assert_eq!(result.err().unwrap().to_string(), "failed");
+    }
+
+    #[tokio::test]
+    async fn test_catch_processing_panic_in_spawn_blocking() {
+        install_panic_hook();
+        let result = catch_processing_panic(async {
+            assert!(spawn_blocking(|| CATCHING_PANICS.with(|c| c.get()))
+                .await
+                .unwrap());
+            let version = spawn_blocking(|| -> u64 { panic!("poison transaction") })
+                .await
+                .expect("Failed to spawn_blocking");
+            Ok::<_, anyhow::Error>(ProcessingResult::DefaultProcessingResult(
+                DefaultProcessingResult {
+                    start_version: version,
+                    end_version: version,
+                    last_transaction_timestamp: None,
+                    processing_duration_in_secs: 0.0,
+                    db_insertion_duration_in_secs: 0.0,
+                },
+            ))
+        })
+        .await;
+        assert!(result
+            .err()
+            .unwrap()
+            .to_string()
+            .starts_with("Panicked: Failed to spawn_blocking"));
+        assert!(!spawn_blocking(|| CATCHING_PANICS.with(|c| c.get()))
+            .await
+            .unwrap());
+    }
+}
@@ -3,4 +3,6 @@
 
 pub mod counters;
 pub mod database;
+pub mod dead_letter;
+pub mod runtime_tables;
 pub mod util;
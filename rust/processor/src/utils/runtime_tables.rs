@@ -0,0 +1,180 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tables declared in processor configs instead of migrations, e.g. the event tables and the
+//! resource snapshots. They aren't part of the diesel schema, so they're created from the config
+//! with `CREATE TABLE IF NOT EXISTS` on startup, and their rows are bound as a single JSONB
+//! parameter. A name taken by another table or view, e.g. one of the processors' tables, or a
+//! column whose type changed would otherwise be silently left as it is.
+
+// This is required because a diesel macro makes clippy sad
+#![allow(clippy::extra_unused_lifetimes)]
+
+use crate::utils::database::{execute_with_better_error, ArcDbPool, DbPoolConnection};
+use ahash::{AHashMap, AHashSet};
+use anyhow::Result;
+use diesel::{
+    sql_query,
+    sql_types::{Jsonb, Nullable, Text},
+    QueryableByName,
+};
+use diesel_async::RunQueryDsl;
+use futures_util::future::try_join_all;
+use serde::Serialize;
+
+/// Rows per insert when the table isn't in per_table_chunk_sizes. Rows are bound as a single
+/// parameter so this isn't limited by the number of parameters.
+pub const DEFAULT_CHUNK_SIZE: usize = 1000;
+
+/// Comment of the tables created from a config, to tell them apart from other tables
+const RUNTIME_TABLE_COMMENT: &str = "Declared in the processor config";
+
+/// Table or view of the name in the schema the tables are created in, with its comment
+const EXISTING_TABLE_QUERY: &str = "
+SELECT table_type::TEXT AS table_type,
+    obj_description(format('%I.%I', table_schema, table_name)::regclass, 'pg_class') AS comment
+FROM information_schema.tables
+WHERE table_schema = current_schema() AND table_name = $1
+";
+
+/// Columns of a table and their type as formatted by postgres, e.g. `character varying(66)`
+const COLUMN_TYPES_QUERY: &str = "
+SELECT attname::TEXT AS column_name, format_type(atttypid, atttypmod) AS column_type
+FROM pg_attribute
+WHERE attrelid = to_regclass($1) AND attnum > 0 AND NOT attisdropped
+";
+
+pub trait RuntimeTable {
+    fn table_name(&self) -> String;
+
+    fn validate(&self) -> Result<()>;
+
+    /// Creates the table, or brings an existing one up to date with the config
+    fn ddl_statements(&self) -> Vec<String>;
+
+    /// Configured columns with the type postgres formats them with, e.g. `character varying(66)`
+    fn column_types(&self) -> Vec<(&str, &'static str)> {
+        vec![]
+    }
+
+    /// Insert of the rows bound as a JSONB array to the single parameter of the query
+    fn insert_statement(&self) -> String;
+}
+
+#[derive(Debug, QueryableByName)]
+struct ExistingTable {
+    #[diesel(sql_type = Text)]
+    table_type: String,
+    #[diesel(sql_type = Nullable<Text>)]
+    comment: Option<String>,
+}
+
+#[derive(Debug, QueryableByName)]
+struct ColumnType {
+    #[diesel(sql_type = Text)]
+    column_name: String,
+    #[diesel(sql_type = Text)]
+    column_type: String,
+}
+
+/// Validates the tables and creates them, or brings them up to date, in the database
+pub async fn create_tables<T: RuntimeTable>(pool: ArcDbPool, tables: &[T]) -> Result<()> {
+    let mut table_names = AHashSet::new();
+    for table in tables {
+        table.validate()?;
+        anyhow::ensure!(
+            table_names.insert(table.table_name()),
+            "Table {} is declared more than once",
+            table.table_name()
+        );
+    }
+    let mut conn = pool.get().await?;
+    for table in tables {
+        ensure_not_other_table(&mut conn, &table.table_name()).await?;
+        for statement in table.ddl_statements() {
+            sql_query(statement).execute(&mut conn).await?;
+        }
+        sql_query(format!(
+            "COMMENT ON TABLE \"{}\" IS '{}'",
+            table.table_name(),
+            RUNTIME_TABLE_COMMENT
+        ))
+        .execute(&mut conn)
+        .await?;
+        ensure_column_types(&mut conn, &table.table_name(), &table.column_types()).await?;
+    }
+    Ok(())
+}
+
+/// Inserts the rows of each table, in the order of the tables, in chunks of the table's chunk size
+pub async fn insert_rows<T: RuntimeTable, R: Serialize>(
+    conn: ArcDbPool,
+    tables: &[T],
+    rows: Vec<Vec<R>>,
+    per_table_chunk_sizes: &AHashMap<String, usize>,
+) -> Result<(), diesel::result::Error> {
+    let mut queries = vec![];
+    for (table, table_rows) in tables.iter().zip(rows) {
+        let chunk_size = per_table_chunk_sizes
+            .get(&table.table_name())
+            .copied()
+            .unwrap_or(DEFAULT_CHUNK_SIZE);
+        let statement = table.insert_statement();
+        for chunk in table_rows.chunks(chunk_size) {
+            let chunk = serde_json::to_value(chunk)
+                .map_err(|e| diesel::result::Error::SerializationError(Box::new(e)))?;
+            let query = sql_query(statement.clone()).bind::<Jsonb, _>(chunk);
+            queries.push(execute_with_better_error(conn.clone(), query, None));
+        }
+    }
+    try_join_all(queries).await?;
+    Ok(())
+}
+
+/// Fails if the name is taken by a table or view that wasn't created from a config, e.g. one of
+/// the processors' tables
+async fn ensure_not_other_table(conn: &mut DbPoolConnection<'_>, table_name: &str) -> Result<()> {
+    let existing_table = sql_query(EXISTING_TABLE_QUERY)
+        .bind::<Text, _>(table_name)
+        .load::<ExistingTable>(conn)
+        .await?
+        .pop();
+    if let Some(existing_table) = existing_table {
+        anyhow::ensure!(
+            existing_table.table_type == "BASE TABLE"
+                && existing_table.comment.as_deref() == Some(RUNTIME_TABLE_COMMENT),
+            "Table {} already exists and wasn't created from a processor config, pick another name",
+            table_name
+        );
+    }
+    Ok(())
+}
+
+/// Fails if a column of the table doesn't have the type of the config, since the table isn't
+/// altered when the type of a column changes
+async fn ensure_column_types(
+    conn: &mut DbPoolConnection<'_>,
+    table_name: &str,
+    columns: &[(&str, &str)],
+) -> Result<()> {
+    let existing_types = sql_query(COLUMN_TYPES_QUERY)
+        .bind::<Text, _>(format!("\"{}\"", table_name))
+        .load::<ColumnType>(conn)
+        .await?
+        .into_iter()
+        .map(|column| (column.column_name, column.column_type))
+        .collect::<AHashMap<_, _>>();
+    for (column_name, column_type) in columns {
+        if let Some(existing_type) = existing_types.get(*column_name) {
+            anyhow::ensure!(
+                existing_type == column_type,
+                "Column {} of table {} is {} in the database but {} in the config, the column has to be migrated by hand",
+                column_name,
+                table_name,
+                existing_type,
+                column_type
+            );
+        }
+    }
+    Ok(())
+}
@@ -7,16 +7,17 @@ use crate::{
 };
 use aptos_protos::{
     transaction::v1::{
-        multisig_transaction_payload::Payload as MultisigPayloadType,
+        multisig_transaction_payload::Payload as MultisigPayloadType, transaction::TxnData,
         transaction_payload::Payload as PayloadType, write_set::WriteSet as WriteSetType,
-        EntryFunctionId, EntryFunctionPayload, MoveScriptBytecode, MoveType, ScriptPayload,
-        TransactionPayload, UserTransactionRequest, WriteSet,
+        EntryFunctionId, EntryFunctionPayload, Event, MoveScriptBytecode, MoveType, ScriptPayload,
+        Transaction, TransactionPayload, UserTransactionRequest, WriteSet,
     },
     util::timestamp::Timestamp,
 };
 use bigdecimal::{BigDecimal, Signed, ToPrimitive, Zero};
 use chrono::NaiveDateTime;
 use lazy_static::lazy_static;
+use regex::{Captures, Regex};
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 use sha2::Digest;
@@ -38,6 +39,9 @@ lazy_static! {
     };
     pub static ref APT_METADATA_ADDRESS_HEX: String =
         format!("0x{}", hex::encode(*APT_METADATA_ADDRESS_RAW));
+    // Addresses in type strings and entry function ids, e.g. both addresses in
+    // `0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>`
+    static ref ADDRESS_RE: Regex = Regex::new(r"\b0[xX][0-9a-fA-F]+\b").unwrap();
 }
 // Supporting structs to get clean payload without escaped strings
 #[derive(Debug, Deserialize, Serialize)]
@@ -481,6 +485,34 @@ pub fn get_name_from_unnested_move_type(move_type: &str) -> &str {
     t.last().unwrap()
 }
 
+/// Events of the transaction, empty for the transaction types without events
+pub fn get_events(transaction: &Transaction) -> &[Event] {
+    match transaction.txn_data.as_ref() {
+        Some(TxnData::BlockMetadata(inner)) => &inner.events,
+        Some(TxnData::Genesis(inner)) => &inner.events,
+        Some(TxnData::User(inner)) => &inner.events,
+        Some(TxnData::Validator(inner)) => &inner.events,
+        _ => &[],
+    }
+}
+
+/// Standardizes every address in a type string or entry function id, e.g.
+/// `0x1::coin::CoinStore<0X0A::my_coin::MyCoin>`, so that type strings can be compared
+pub fn normalize_type_str(type_str: &str) -> String {
+    ADDRESS_RE
+        .replace_all(type_str, |caps: &Captures| {
+            standardize_address(&caps[0].to_lowercase())
+        })
+        .into_owned()
+}
+
+/// `0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>` -> `0x1::coin::CoinStore`
+pub fn strip_generics(type_str: &str) -> &str {
+    type_str
+        .split_once('<')
+        .map_or(type_str, |(outer_type, _)| outer_type)
+}
+
 /* COMMON STRUCTS */
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Aggregator {
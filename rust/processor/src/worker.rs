@@ -2,33 +2,52 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    config::IndexerGrpcHttp2Config,
-    db::common::models::{ledger_info::LedgerInfo, processor_status::ProcessorStatusQuery},
+    config::{ChainIdGuard, IndexerGrpcHttp2Config},
+    db::common::models::{
+        ledger_info::LedgerInfo,
+        parquet_table_status::ParquetTableStatusQuery,
+        parquet_upload_manifest::ParquetUploadManifest,
+        processor_chain_id::ProcessorChainId,
+        processor_gap::{ProcessorGap, ProcessorGapQuery},
+        processor_status::ProcessorStatusQuery,
+    },
     gap_detectors::{
-        create_gap_detector_status_tracker_loop, gap_detector::DefaultGapDetector,
-        parquet_gap_detector::ParquetFileGapDetector, GapDetector, ProcessingResult,
+        create_gap_detector_status_tracker_loop, create_processor_status_history_loop,
+        gap_detector::DefaultGapDetector, parquet_gap_detector::ParquetFileGapDetector,
+        GapDetector, ProcessingResult,
     },
     grpc_stream::TransactionsPBResponse,
     processors::{
+        account_keys_processor::AccountKeysProcessor,
         account_transactions_processor::AccountTransactionsProcessor,
         ans_processor::AnsProcessor,
+        audit_processor::AuditProcessor,
         default_processor::DefaultProcessor,
+        dex_processor::DexProcessor,
+        event_tables_processor::EventTablesProcessor,
         events_processor::EventsProcessor,
         fungible_asset_processor::FungibleAssetProcessor,
+        fungible_asset_transfer_processor::FungibleAssetTransferProcessor,
+        gas_fee_processor::GasFeeProcessor,
+        governance_processor::GovernanceProcessor,
         monitoring_processor::MonitoringProcessor,
+        multisig_processor::MultisigProcessor,
+        nft_marketplace_processor::NftMarketplaceProcessor,
         nft_metadata_processor::NftMetadataProcessor,
         objects_processor::ObjectsProcessor,
         parquet_processors::{
             parquet_default_processor::ParquetDefaultProcessor,
             parquet_fungible_asset_processor::ParquetFungibleAssetProcessor,
         },
+        resource_snapshot_processor::ResourceSnapshotProcessor,
         stake_processor::StakeProcessor,
         token_v2_processor::TokenV2Processor,
         transaction_metadata_processor::TransactionMetadataProcessor,
         user_transaction_processor::UserTransactionProcessor,
+        validator_set_processor::ValidatorSetProcessor,
         DefaultProcessingResult, Processor, ProcessorConfig, ProcessorTrait,
     },
-    schema::ledger_infos,
+    schema::{ledger_infos, processor_chain_ids},
     transaction_filter::TransactionFilter,
     utils::{
         counters::{
@@ -41,8 +60,11 @@ use crate::{
             SINGLE_BATCH_PROCESSING_TIME_IN_SECS, TRANSACTION_UNIX_TIMESTAMP,
         },
         database::{
-            execute_with_better_error_conn, new_db_pool, run_pending_migrations, ArcDbPool,
+            create_schema_if_not_exists, database_url_with_schema, execute_with_better_error_conn,
+            new_db_pool, run_pending_migrations, ArcDbPool,
         },
+        dead_letter::{DeadLetterConfig, DeadLetterQueue},
+        runtime_tables::create_tables,
         util::{time_diff_since_pb_timestamp_in_secs, timestamp_to_iso, timestamp_to_unixtime},
     },
 };
@@ -51,7 +73,7 @@ use anyhow::{Context, Result};
 use aptos_moving_average::MovingAverage;
 use bitflags::bitflags;
 use kanal::AsyncSender;
-use std::collections::HashSet;
+use std::{collections::HashSet, sync::Arc};
 use tokio::task::JoinHandle;
 use tracing::{debug, error, info};
 use url::Url;
@@ -124,6 +146,15 @@ pub struct Worker {
     pub transaction_filter: TransactionFilter,
     pub grpc_response_item_timeout_in_secs: u64,
     pub deprecated_tables: TableFlags,
+    // Parquet only, the version each table starts from
+    pub parquet_table_start_versions: AHashMap<String, u64>,
+    pub replay_gaps: bool,
+    pub dead_letter_config: Option<DeadLetterConfig>,
+    // Built from the dead letter config once the migrations ran
+    pub dead_letter_queue: Option<Arc<DeadLetterQueue>>,
+    pub processor_status_history_interval_secs: u64,
+    pub db_schema: Option<String>,
+    pub chain_id_guard: ChainIdGuard,
 }
 
 impl Worker {
@@ -147,15 +178,28 @@ impl Worker {
         transaction_filter: TransactionFilter,
         grpc_response_item_timeout_in_secs: u64,
         deprecated_tables: HashSet<String>,
+        replay_gaps: bool,
+        dead_letter_config: Option<DeadLetterConfig>,
+        processor_status_history_interval_secs: u64,
+        db_schema: Option<String>,
+        chain_id_guard: ChainIdGuard,
     ) -> Result<Self> {
         let processor_name = processor_config.name();
         info!(processor_name = processor_name, "[Parser] Kicking off");
+        processor_config
+            .validate()
+            .context("[Parser] Invalid processor config")?;
 
         info!(
             processor_name = processor_name,
             service_type = PROCESSOR_SERVICE_TYPE,
             "[Parser] Creating connection pool"
         );
+        // The migrations connect with the connection string as well, so they run in the schema too
+        let postgres_connection_string = match &db_schema {
+            Some(db_schema) => database_url_with_schema(&postgres_connection_string, db_schema)?,
+            None => postgres_connection_string,
+        };
         let conn_pool = new_db_pool(&postgres_connection_string, db_pool_size)
             .await
             .context("Failed to create connection pool")?;
@@ -192,6 +236,13 @@ impl Worker {
             transaction_filter,
             grpc_response_item_timeout_in_secs,
             deprecated_tables: deprecated_tables_flags,
+            parquet_table_start_versions: AHashMap::new(),
+            replay_gaps,
+            dead_letter_config,
+            dead_letter_queue: None,
+            processor_status_history_interval_secs,
+            db_schema,
+            chain_id_guard,
         })
     }
 
@@ -209,6 +260,11 @@ impl Worker {
             "[Parser] Running migrations"
         );
         let migration_time = std::time::Instant::now();
+        if let Some(db_schema) = &self.db_schema {
+            create_schema_if_not_exists(self.db_pool.clone(), db_schema)
+                .await
+                .expect("[Parser] Failed to create schema");
+        }
         self.run_migrations().await;
         info!(
             processor_name = processor_name,
@@ -217,6 +273,30 @@ impl Worker {
             "[Parser] Finished migrations"
         );
 
+        // Tables declared in the processor config instead of migrations
+        match &self.processor_config {
+            ProcessorConfig::EventTablesProcessor(config) => {
+                create_tables(self.db_pool.clone(), &config.tables).await
+            },
+            ProcessorConfig::ResourceSnapshotProcessor(config) => {
+                create_tables(self.db_pool.clone(), &config.resources).await
+            },
+            _ => Ok(()),
+        }
+        .expect("[Parser] Failed to create the tables of the processor config");
+
+        if let Some(dead_letter_config) = &self.dead_letter_config {
+            assert!(
+                !self.processor_config.is_parquet_processor(),
+                "[Parser] Dead letter mode isn't supported for parquet processors"
+            );
+            self.dead_letter_queue = Some(Arc::new(
+                DeadLetterQueue::new(processor_name, self.db_pool.clone(), dead_letter_config)
+                    .await
+                    .expect("[Parser] Failed to build dead letter queue"),
+            ));
+        }
+
         let starting_version_from_db = self
             .get_start_version()
             .await
@@ -230,7 +310,20 @@ impl Worker {
                 0
             });
 
-        let starting_version = self.starting_version.unwrap_or(starting_version_from_db);
+        let starting_version = if self.processor_config.is_parquet_processor() {
+            // Every table resumes from its own version, so stream from the earliest one
+            self.parquet_table_start_versions = self
+                .get_parquet_table_start_versions(starting_version_from_db)
+                .await
+                .expect("[Parser] Database error when getting parquet table starting versions");
+            self.parquet_table_start_versions
+                .values()
+                .min()
+                .copied()
+                .unwrap_or(starting_version_from_db)
+        } else {
+            self.starting_version.unwrap_or(starting_version_from_db)
+        };
 
         info!(
             processor_name = processor_name,
@@ -260,6 +353,11 @@ impl Worker {
 
         self.grpc_chain_id = Some(chain_id);
 
+        if self.replay_gaps {
+            self.replay_processor_gaps().await;
+            return;
+        }
+
         let ending_version = self.ending_version;
         let indexer_grpc_data_service_address = self.indexer_grpc_data_service_address.clone();
         let indexer_grpc_http2_ping_interval =
@@ -324,6 +422,7 @@ impl Worker {
             self.deprecated_tables,
             self.db_pool.clone(),
             maybe_gap_detector_sender,
+            self.parquet_table_start_versions.clone(),
         );
 
         let gap_detector = if is_parquet_processor {
@@ -342,6 +441,15 @@ impl Worker {
             .await;
         });
 
+        // 0 disables the processor status history
+        if self.processor_status_history_interval_secs > 0 {
+            tokio::spawn(create_processor_status_history_loop(
+                self.db_pool.clone(),
+                processor_name,
+                self.processor_status_history_interval_secs,
+            ));
+        }
+
         // This is the consumer side of the channel. These are the major states:
         // 1. We're backfilling so we should expect many concurrent threads to process transactions
         // 2. We're caught up so we should expect a single thread to process transactions
@@ -389,6 +497,8 @@ impl Worker {
         let stream_address = self.indexer_grpc_data_service_address.to_string();
         let receiver_clone = receiver.clone();
         let auth_token = self.auth_token.clone();
+        let db_pool = self.db_pool.clone();
+        let dead_letter_queue = self.dead_letter_queue.clone();
 
         // Build the processor based on the config.
         let processor = if self.processor_config.is_parquet_processor() {
@@ -398,6 +508,7 @@ impl Worker {
                 self.deprecated_tables,
                 self.db_pool.clone(),
                 Some(gap_detector_sender.clone()),
+                self.parquet_table_start_versions.clone(),
             )
         } else {
             build_processor(
@@ -406,6 +517,7 @@ impl Worker {
                 self.deprecated_tables,
                 self.db_pool.clone(),
                 None,
+                AHashMap::new(),
             )
         };
 
@@ -508,6 +620,7 @@ impl Worker {
                             processor_name,
                             &auth_token,
                             false, // enable_verbose_logging
+                            dead_letter_queue.as_deref(),
                         )
                         .await;
 
@@ -529,6 +642,21 @@ impl Worker {
                                 PROCESSOR_ERRORS_COUNT
                                     .with_label_values(&[processor_name])
                                     .inc();
+                                // Record the batch so it can be replayed after the restart
+                                let gap = ProcessorGap::new(
+                                    processor_name,
+                                    batch_first_txn_version,
+                                    batch_last_txn_version,
+                                    format!("{:?}", e),
+                                );
+                                if let Err(e) = gap.record(db_pool.clone()).await {
+                                    error!(
+                                        processor_name = processor_name,
+                                        error = ?e,
+                                        task_index,
+                                        "[Parser][T#{}] Failed to record processor gap", task_index
+                                    );
+                                }
                                 panic!(
                                     "[Parser][T#{}] Error processing '{:}' transactions: {:?}",
                                     task_index, processor_name, e
@@ -691,6 +819,127 @@ impl Worker {
         .expect("[Parser] Failed to run migrations");
     }
 
+    /// Re-runs the unresolved ranges of `processor_gaps` one at a time and exits. The processor
+    /// status isn't updated, so the regular deployment keeps its own checkpoint.
+    async fn replay_processor_gaps(&self) {
+        let processor_name = self.processor_config.name();
+        assert!(
+            !self.processor_config.is_parquet_processor(),
+            "[Parser] Replaying gaps isn't supported for parquet processors"
+        );
+        let gaps = {
+            let mut conn = self
+                .db_pool
+                .get()
+                .await
+                .expect("[Parser] Failed to get connection");
+            ProcessorGapQuery::get_unresolved(processor_name, &mut conn)
+                .await
+                .expect("[Parser] Database error when getting processor gaps")
+        };
+        info!(
+            processor_name = processor_name,
+            service_type = PROCESSOR_SERVICE_TYPE,
+            num_gaps = gaps.len(),
+            "[Parser] Replaying processor gaps"
+        );
+
+        let processor = build_processor(
+            &self.processor_config,
+            self.per_table_chunk_sizes.clone(),
+            self.deprecated_tables,
+            self.db_pool.clone(),
+            None,
+            AHashMap::new(),
+        );
+        for gap in gaps {
+            let start_version = gap.start_version as u64;
+            let end_version = gap.end_version as u64;
+            let processor_gap =
+                ProcessorGap::new(processor_name, start_version, end_version, gap.reason);
+            match self
+                .replay_version_range(&processor, start_version, end_version)
+                .await
+            {
+                Ok(()) => {
+                    info!(
+                        processor_name = processor_name,
+                        service_type = PROCESSOR_SERVICE_TYPE,
+                        start_version,
+                        end_version,
+                        "[Parser] Replayed processor gap"
+                    );
+                    processor_gap
+                        .mark_resolved(self.db_pool.clone())
+                        .await
+                        .expect("[Parser] Failed to resolve processor gap");
+                },
+                Err(e) => {
+                    error!(
+                        processor_name = processor_name,
+                        service_type = PROCESSOR_SERVICE_TYPE,
+                        start_version,
+                        end_version,
+                        error = ?e,
+                        "[Parser] Failed to replay processor gap"
+                    );
+                    ProcessorGap {
+                        reason: format!("{:?}", e),
+                        ..processor_gap
+                    }
+                    .record(self.db_pool.clone())
+                    .await
+                    .expect("[Parser] Failed to record processor gap");
+                },
+            }
+        }
+    }
+
+    async fn replay_version_range(
+        &self,
+        processor: &Processor,
+        start_version: u64,
+        end_version: u64,
+    ) -> Result<()> {
+        let processor_name = self.processor_config.name();
+        let chain_id = self
+            .grpc_chain_id
+            .expect("GRPC chain ID has not been fetched yet!");
+        let (tx, receiver) = kanal::bounded_async::<TransactionsPBResponse>(BUFFER_SIZE);
+        let fetcher_task = tokio::spawn(crate::grpc_stream::create_fetcher_loop(
+            tx,
+            self.indexer_grpc_data_service_address.clone(),
+            self.grpc_http2_config.grpc_http2_ping_interval_in_secs(),
+            self.grpc_http2_config.grpc_http2_ping_timeout_in_secs(),
+            self.grpc_http2_config.grpc_connection_timeout_secs(),
+            std::time::Duration::from_secs(self.grpc_response_item_timeout_in_secs),
+            start_version,
+            Some(end_version),
+            self.auth_token.clone(),
+            processor_name.to_string(),
+            self.transaction_filter.clone(),
+            self.pb_channel_txn_chunk_size,
+        ));
+        // The channel is closed once the fetcher reaches the end version
+        while let Ok(transactions_pb) = receiver.recv().await {
+            if let Err(e) = do_processor(
+                transactions_pb,
+                processor,
+                chain_id,
+                processor_name,
+                &self.auth_token,
+                false, // enable_verbose_logging
+                self.dead_letter_queue.as_deref(),
+            )
+            .await
+            {
+                fetcher_task.abort();
+                return Err(e);
+            }
+        }
+        fetcher_task.await.context("[Parser] Fetcher task failed")
+    }
+
     /// Gets the start version for the processor. If not found, start from 0.
     pub async fn get_start_version(&self) -> Result<Option<u64>> {
         let mut conn = self.db_pool.get().await?;
@@ -703,33 +952,100 @@ impl Worker {
         }
     }
 
-    /// Verify the chain id from GRPC against the database.
+    /// Gets the start version of every table of a parquet processor, in order of priority:
+    /// 1. The per table starting version from the processor config
+    /// 2. The starting version from the config
+    /// 3. The table checkpoint
+    /// 4. The first version not covered by the upload manifests of the table. Parquet processors
+    ///    buffer structs in memory before uploading, so the processor status can be ahead of
+    ///    what's actually in the bucket.
+    /// 5. The start version of the processor from the db
+    pub async fn get_parquet_table_start_versions(
+        &self,
+        starting_version_from_db: u64,
+    ) -> Result<AHashMap<String, u64>> {
+        let processor_name = self.processor_config.name();
+        let table_names = self.processor_config.parquet_table_names();
+        let per_table_starting_versions = self
+            .processor_config
+            .parquet_per_table_starting_versions()
+            .cloned()
+            .unwrap_or_default();
+        for table_name in per_table_starting_versions.keys() {
+            anyhow::ensure!(
+                table_names.contains(&table_name.as_str()),
+                "[Parser] Unknown table {} in per_table_starting_versions of {}",
+                table_name,
+                processor_name
+            );
+        }
+
+        let mut conn = self.db_pool.get().await?;
+        let mut table_start_versions = AHashMap::new();
+        for table_name in table_names {
+            let table_start_version = match per_table_starting_versions
+                .get(*table_name)
+                .copied()
+                .or(self.starting_version)
+            {
+                Some(version) => version,
+                None => match ParquetTableStatusQuery::get_by_processor_and_table(
+                    processor_name,
+                    table_name,
+                    &mut conn,
+                )
+                .await?
+                {
+                    Some(status) => status.last_success_version as u64 + 1,
+                    None => ParquetUploadManifest::get_resume_version(
+                        processor_name,
+                        table_name,
+                        &mut conn,
+                    )
+                    .await?
+                    .map(|version| version as u64)
+                    .unwrap_or(starting_version_from_db),
+                },
+            };
+            info!(
+                processor_name = processor_name,
+                table_name = table_name,
+                table_start_version = table_start_version,
+                "[Parser] Got parquet table start version"
+            );
+            table_start_versions.insert(table_name.to_string(), table_start_version);
+        }
+        Ok(table_start_versions)
+    }
+
+    /// Verify the chain id from GRPC against the chain previously indexed by the processor and,
+    /// depending on the guard, the chain of the database.
     pub async fn check_or_update_chain_id(&self, grpc_chain_id: i64) -> Result<u64> {
         let processor_name = self.processor_config.name();
         info!(
             processor_name = processor_name,
+            chain_id_guard = ?self.chain_id_guard,
             "[Parser] Checking if chain id is correct"
         );
         let mut conn = self.db_pool.get().await?;
 
-        let maybe_existing_chain_id = LedgerInfo::get(&mut conn).await?.map(|li| li.chain_id);
-
-        match maybe_existing_chain_id {
-            Some(chain_id) => {
-                anyhow::ensure!(chain_id == grpc_chain_id, "[Parser] Wrong chain detected! Trying to index chain {} now but existing data is for chain {}", grpc_chain_id, chain_id);
-                info!(
-                    processor_name = processor_name,
-                    chain_id = chain_id,
-                    "[Parser] Chain id matches! Continue to index...",
-                );
-                Ok(chain_id as u64)
-            },
-            None => {
-                info!(
-                    processor_name = processor_name,
-                    chain_id = grpc_chain_id,
-                    "[Parser] Adding chain id to db, continue to index..."
-                );
+        let maybe_processor_chain_id =
+            ProcessorChainId::get_by_processor(processor_name, &mut conn).await?;
+        if let Some(chain_id) = maybe_processor_chain_id {
+            anyhow::ensure!(chain_id == grpc_chain_id, "[Parser] Wrong chain detected! Trying to index chain {} now but processor {} indexed chain {}", grpc_chain_id, processor_name, chain_id);
+        }
+        // ledger_infos is the chain of the whole database, so it's only read and written with the
+        // database guard. Processors of other networks sharing the database only record their
+        // chain in processor_chain_ids.
+        if self.chain_id_guard == ChainIdGuard::Database {
+            let existing_chain_ids = LedgerInfo::get_chain_ids(&mut conn).await?;
+            if let Some(chain_id) = existing_chain_ids
+                .iter()
+                .find(|chain_id| **chain_id != grpc_chain_id)
+            {
+                anyhow::bail!("[Parser] Wrong chain detected! Trying to index chain {} now but existing data is for chain {}", grpc_chain_id, chain_id);
+            }
+            if existing_chain_ids.is_empty() {
                 execute_with_better_error_conn(
                     &mut conn,
                     diesel::insert_into(ledger_infos::table)
@@ -740,10 +1056,35 @@ impl Worker {
                     None,
                 )
                 .await
-                .context("[Parser] Error updating chain_id!")
-                .map(|_| grpc_chain_id as u64)
-            },
+                .context("[Parser] Error updating chain_id!")?;
+            }
         }
+        if maybe_processor_chain_id.is_none() {
+            info!(
+                processor_name = processor_name,
+                chain_id = grpc_chain_id,
+                "[Parser] Adding processor chain id to db, continue to index..."
+            );
+            execute_with_better_error_conn(
+                &mut conn,
+                diesel::insert_into(processor_chain_ids::table)
+                    .values(ProcessorChainId {
+                        processor: processor_name.to_string(),
+                        chain_id: grpc_chain_id,
+                    })
+                    .on_conflict_do_nothing(),
+                None,
+            )
+            .await
+            .context("[Parser] Error updating processor chain_id!")?;
+        } else {
+            info!(
+                processor_name = processor_name,
+                chain_id = grpc_chain_id,
+                "[Parser] Chain id matches! Continue to index...",
+            );
+        }
+        Ok(grpc_chain_id as u64)
     }
 }
 
@@ -785,6 +1126,7 @@ pub async fn do_processor(
     processor_name: &str,
     auth_token: &str,
     enable_verbose_logging: bool,
+    dead_letter_queue: Option<&DeadLetterQueue>,
 ) -> Result<ProcessingResult> {
     // We use the value passed from the `transactions_pb` as it may have been filtered
     let start_version = transactions_pb.start_version;
@@ -825,14 +1167,29 @@ pub async fn do_processor(
         );
     }
 
-    let processed_result = processor
-        .process_transactions(
-            transactions_pb.transactions,
-            start_version,
-            end_version,
-            Some(db_chain_id),
-        )
-        .await;
+    let processed_result = match dead_letter_queue {
+        Some(dead_letter_queue) => {
+            dead_letter_queue
+                .process_transactions(
+                    processor,
+                    transactions_pb.transactions,
+                    start_version,
+                    end_version,
+                    Some(db_chain_id),
+                )
+                .await
+        },
+        None => {
+            processor
+                .process_transactions(
+                    transactions_pb.transactions,
+                    start_version,
+                    end_version,
+                    Some(db_chain_id),
+                )
+                .await
+        },
+    };
 
     if let Some(ref t) = txn_time {
         PROCESSOR_DATA_PROCESSED_LATENCY_IN_SECS
@@ -854,8 +1211,12 @@ pub fn build_processor(
     deprecated_tables: TableFlags,
     db_pool: ArcDbPool,
     gap_detector_sender: Option<AsyncSender<ProcessingResult>>, // Parquet only
+    parquet_table_start_versions: AHashMap<String, u64>,        // Parquet only
 ) -> Processor {
     match config {
+        ProcessorConfig::AccountKeysProcessor => {
+            Processor::from(AccountKeysProcessor::new(db_pool, per_table_chunk_sizes))
+        },
         ProcessorConfig::AccountTransactionsProcessor => Processor::from(
             AccountTransactionsProcessor::new(db_pool, per_table_chunk_sizes),
         ),
@@ -865,11 +1226,24 @@ pub fn build_processor(
             per_table_chunk_sizes,
             deprecated_tables,
         )),
+        ProcessorConfig::AuditProcessor(config) => Processor::from(AuditProcessor::new(
+            db_pool,
+            config.clone(),
+            per_table_chunk_sizes,
+        )),
         ProcessorConfig::DefaultProcessor => Processor::from(DefaultProcessor::new(
             db_pool,
             per_table_chunk_sizes,
             deprecated_tables,
         )),
+        ProcessorConfig::DexProcessor(config) => Processor::from(DexProcessor::new(
+            db_pool,
+            config.clone(),
+            per_table_chunk_sizes,
+        )),
+        ProcessorConfig::EventTablesProcessor(config) => Processor::from(
+            EventTablesProcessor::new(db_pool, config.clone(), per_table_chunk_sizes),
+        ),
         ProcessorConfig::EventsProcessor => {
             Processor::from(EventsProcessor::new(db_pool, per_table_chunk_sizes))
         },
@@ -878,7 +1252,22 @@ pub fn build_processor(
             per_table_chunk_sizes,
             deprecated_tables,
         )),
+        ProcessorConfig::FungibleAssetTransferProcessor => Processor::from(
+            FungibleAssetTransferProcessor::new(db_pool, per_table_chunk_sizes),
+        ),
+        ProcessorConfig::GasFeeProcessor => {
+            Processor::from(GasFeeProcessor::new(db_pool, per_table_chunk_sizes))
+        },
+        ProcessorConfig::GovernanceProcessor => {
+            Processor::from(GovernanceProcessor::new(db_pool, per_table_chunk_sizes))
+        },
         ProcessorConfig::MonitoringProcessor => Processor::from(MonitoringProcessor::new(db_pool)),
+        ProcessorConfig::MultisigProcessor => {
+            Processor::from(MultisigProcessor::new(db_pool, per_table_chunk_sizes))
+        },
+        ProcessorConfig::NftMarketplaceProcessor(config) => Processor::from(
+            NftMarketplaceProcessor::new(db_pool, config.clone(), per_table_chunk_sizes),
+        ),
         ProcessorConfig::NftMetadataProcessor(config) => {
             Processor::from(NftMetadataProcessor::new(db_pool, config.clone()))
         },
@@ -888,6 +1277,9 @@ pub fn build_processor(
             per_table_chunk_sizes,
             deprecated_tables,
         )),
+        ProcessorConfig::ResourceSnapshotProcessor(config) => Processor::from(
+            ResourceSnapshotProcessor::new(db_pool, config.clone(), per_table_chunk_sizes),
+        ),
         ProcessorConfig::StakeProcessor(config) => Processor::from(StakeProcessor::new(
             db_pool,
             config.clone(),
@@ -905,11 +1297,15 @@ pub fn build_processor(
         ProcessorConfig::UserTransactionProcessor => Processor::from(
             UserTransactionProcessor::new(db_pool, per_table_chunk_sizes, deprecated_tables),
         ),
+        ProcessorConfig::ValidatorSetProcessor => {
+            Processor::from(ValidatorSetProcessor::new(db_pool, per_table_chunk_sizes))
+        },
         ProcessorConfig::ParquetDefaultProcessor(config) => {
             Processor::from(ParquetDefaultProcessor::new(
                 db_pool,
                 config.clone(),
                 gap_detector_sender.expect("Parquet processor requires a gap detector sender"),
+                parquet_table_start_versions,
             ))
         },
         ProcessorConfig::ParquetFungibleAssetProcessor(config) => {
@@ -917,6 +1313,7 @@ pub fn build_processor(
                 db_pool,
                 config.clone(),
                 gap_detector_sender.expect("Parquet processor requires a gap detector sender"),
+                parquet_table_start_versions,
             ))
         },
     }